@@ -0,0 +1,354 @@
+use std::fmt::Display;
+
+use bevy::{
+    asset::{
+        AssetEvent, AssetLoader, Assets, BoxedFuture, Handle, HandleId, LoadContext, LoadedAsset,
+    },
+    ecs::schedule::ScheduleLabel,
+    log::error,
+    prelude::{
+        AddAsset, App, Commands, Entity, EventReader, IntoSystem, IntoSystemConfigs, Plugin, Query,
+        Res, ResMut, Resource, System, Update, Without,
+    },
+    reflect::{TypePath, TypeUuid},
+    utils::{get_short_name, HashMap, HashSet},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    behaviour::{IntoBehaviour, IntoStatus},
+    compositor::{Select, Sequence},
+    prelude::{Behaviour, BehaviourId, BehaviourTreeSet, BehaviourTrees, Decorator},
+};
+
+/// A tree description, as stored in `.btree` files.
+///
+/// ```ron
+/// (
+///     node: "sequence",
+///     children: [
+///         (node: "find_target"),
+///         (node: "attack", decorators: ["invert", "latch"]),
+///     ],
+/// )
+/// ```
+///
+/// Node and decorator names are resolved with the [`BehaviourNodeRegistry`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BehaviourDefinition {
+    /// The name of a compositor or leaf.
+    pub node: String,
+    /// The children of a compositor. Ignored for leaves.
+    #[serde(default)]
+    pub children: Vec<BehaviourDefinition>,
+    /// Decorators applied to the node, innermost first.
+    #[serde(default)]
+    pub decorators: Vec<String>,
+}
+
+/// A tree loaded from a `.btree` file. See [`BehaviourAssetPlugin`].
+#[derive(Clone, Debug, TypeUuid, TypePath)]
+#[uuid = "f483b55e-2cfc-43bd-83e9-7a5ed36b9c5a"]
+pub struct BehaviourPrototype(pub BehaviourDefinition);
+
+/// Loads [`BehaviourPrototype`]s from `.btree` files in [RON](https://github.com/ron-rs/ron) format.
+#[derive(Default)]
+pub struct BehaviourLoader;
+
+impl AssetLoader for BehaviourLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let definition = ron::de::from_bytes::<BehaviourDefinition>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(BehaviourPrototype(definition)));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["btree"]
+    }
+}
+
+/// Why a [`BehaviourDefinition`] couldn't be built.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// No compositor or leaf with this name is registered.
+    UnknownNode(String),
+    /// No decorator with this name is registered.
+    UnknownDecorator(String),
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownNode(name) => write!(f, "unknown node `{name}`"),
+            Self::UnknownDecorator(name) => write!(f, "unknown decorator `{name}`"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+type LeafConstructor = Box<dyn Fn() -> Box<dyn Behaviour> + Send + Sync>;
+
+/// Constructs a compositor from its children.
+pub type CompositorConstructor = fn(Vec<Box<dyn Behaviour>>) -> Box<dyn Behaviour>;
+
+/// Wraps a behaviour in a decorator.
+pub type DecoratorConstructor = fn(Box<dyn Behaviour>) -> Box<dyn Behaviour>;
+
+/// Maps the names used in [`BehaviourDefinition`]s to the nodes they stand for.
+///
+/// `sequence`, `select`, `invert` and `latch` are registered by default. Leaves need to be registered, systems under their own name:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, assets::{BehaviourDefinition, BehaviourNodeRegistry}};
+/// # fn find_target(_: In<Entity>) -> Status { Status::Success }
+/// let mut registry = BehaviourNodeRegistry::default();
+/// registry.register_system(find_target);
+///
+/// let tree = registry.build(&BehaviourDefinition {
+///     node: "find_target".into(),
+///     children: vec![],
+///     decorators: vec!["invert".into()],
+/// });
+/// assert!(tree.is_ok());
+/// ```
+#[derive(Resource)]
+pub struct BehaviourNodeRegistry {
+    leaves: HashMap<String, LeafConstructor>,
+    compositors: HashMap<String, CompositorConstructor>,
+    decorators: HashMap<String, DecoratorConstructor>,
+}
+
+impl Default for BehaviourNodeRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            leaves: HashMap::default(),
+            compositors: HashMap::default(),
+            decorators: HashMap::default(),
+        };
+
+        registry.register_compositor("sequence", |children| Box::new(Sequence::new(children)));
+        registry.register_compositor("select", |children| Box::new(Select::new(children)));
+        registry.register_decorator("invert", |behaviour| Box::new(behaviour.invert()));
+        registry.register_decorator("latch", |behaviour| Box::new(behaviour.latch()));
+
+        registry
+    }
+}
+
+impl BehaviourNodeRegistry {
+    /// Registers a leaf. Every node using `name` gets its own clone of `leaf`.
+    pub fn register_leaf<Marker, T>(&mut self, name: impl Into<String>, leaf: T)
+    where
+        T: IntoBehaviour<Marker> + Clone + Send + Sync + 'static,
+    {
+        self.leaves.insert(
+            name.into(),
+            Box::new(move || Box::new(leaf.clone().into_behaviour())),
+        );
+    }
+
+    /// Registers a system as a leaf under its [name][bevy::ecs::system::System::name], both the full path and the bare function name, so `my_game::ai::find_target` can be used as `find_target` too.
+    ///
+    /// Use [`register_leaf`][BehaviourNodeRegistry::register_leaf] for closures, or if two systems share a function name.
+    pub fn register_system<Marker: 'static, S: IntoStatus, T>(&mut self, system: T)
+    where
+        T: IntoSystem<Entity, S, Marker> + Clone + Send + Sync + 'static,
+    {
+        let name = IntoSystem::into_system(system.clone()).name();
+        let short_name = get_short_name(&name);
+
+        if short_name != name {
+            self.register_leaf(short_name, system.clone());
+        }
+        self.register_leaf(name, system);
+    }
+
+    /// Registers a compositor.
+    pub fn register_compositor(
+        &mut self,
+        name: impl Into<String>,
+        compositor: CompositorConstructor,
+    ) {
+        self.compositors.insert(name.into(), compositor);
+    }
+
+    /// Registers a decorator.
+    pub fn register_decorator(&mut self, name: impl Into<String>, decorator: DecoratorConstructor) {
+        self.decorators.insert(name.into(), decorator);
+    }
+
+    /// Builds the tree described by `definition`.
+    pub fn build(
+        &self,
+        definition: &BehaviourDefinition,
+    ) -> Result<Box<dyn Behaviour>, BuildError> {
+        let mut behaviour = if let Some(compositor) = self.compositors.get(&definition.node) {
+            let children = definition
+                .children
+                .iter()
+                .map(|child| self.build(child))
+                .collect::<Result<_, _>>()?;
+
+            compositor(children)
+        } else if let Some(leaf) = self.leaves.get(&definition.node) {
+            leaf()
+        } else {
+            return Err(BuildError::UnknownNode(definition.node.clone()));
+        };
+
+        for name in &definition.decorators {
+            let Some(decorator) = self.decorators.get(name) else {
+                return Err(BuildError::UnknownDecorator(name.clone()));
+            };
+
+            behaviour = decorator(behaviour);
+        }
+
+        Ok(behaviour)
+    }
+}
+
+/// Loads trees from `.btree` files.
+///
+/// Entities with a [`Handle<BehaviourPrototype>`] get a [`BehaviourId`] once the prototype has loaded.
+/// All entities using the same prototype share one tree, which is [replaced][BehaviourTrees::replace] whenever the prototype changes.
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, assets::{BehaviourAssetPlugin, BehaviourPrototype}};
+/// fn spawn_agent(mut commands: Commands, assets: Res<AssetServer>) {
+///     commands.spawn(assets.load::<BehaviourPrototype, _>("agent.btree"));
+/// }
+///
+/// App::new()
+///     .add_plugins((
+///         DefaultPlugins,
+///         BehaviourTreePlugin::default(),
+///         BehaviourAssetPlugin::default(),
+///     ))
+///     .add_systems(Startup, spawn_agent);
+/// ```
+/// Requires the `assets` feature.
+pub struct BehaviourAssetPlugin<Label: ScheduleLabel + Clone = Update> {
+    label: Label,
+}
+
+impl<Label: ScheduleLabel + Clone> BehaviourAssetPlugin<Label> {
+    /// Assigns and replaces trees in the given schedule, which should be the one the [`BehaviourTreePlugin`][crate::plugin::BehaviourTreePlugin] runs in.
+    /// Defaults to [`Update`].
+    pub fn in_schedule(label: Label) -> Self {
+        Self { label }
+    }
+}
+
+impl Default for BehaviourAssetPlugin {
+    fn default() -> Self {
+        Self::in_schedule(Update)
+    }
+}
+
+impl<Label: ScheduleLabel + Clone> Plugin for BehaviourAssetPlugin<Label> {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<BehaviourPrototype>()
+            .init_asset_loader::<BehaviourLoader>()
+            .init_resource::<BehaviourNodeRegistry>()
+            .init_resource::<PrototypeTrees>()
+            .add_systems(
+                self.label.clone(),
+                (reload_prototypes, assign_prototype_trees).in_set(BehaviourTreeSet::PreTick),
+            );
+    }
+}
+
+/// Which tree each prototype was built into.
+#[derive(Resource, Default)]
+struct PrototypeTrees {
+    trees: HashMap<HandleId, BehaviourId>,
+    // so broken prototypes are only reported once per change.
+    failed: HashSet<HandleId>,
+}
+
+fn report(handle: HandleId, error: BuildError) {
+    error!("couldn't build behaviour prototype {handle:?}: {error}");
+}
+
+fn assign_prototype_trees(
+    mut commands: Commands,
+    query: Query<(Entity, &Handle<BehaviourPrototype>), Without<BehaviourId>>,
+    prototypes: Res<Assets<BehaviourPrototype>>,
+    registry: Res<BehaviourNodeRegistry>,
+    mut trees: ResMut<BehaviourTrees>,
+    mut prototype_trees: ResMut<PrototypeTrees>,
+) {
+    for (entity, prototype_handle) in &query {
+        let handle = prototype_handle.id();
+
+        let id = match prototype_trees.trees.get(&handle) {
+            Some(id) => *id,
+            None => {
+                if prototype_trees.failed.contains(&handle) {
+                    continue;
+                }
+
+                let Some(prototype) = prototypes.get(prototype_handle) else {
+                    continue;
+                };
+
+                match registry.build(&prototype.0) {
+                    Ok(behaviour) => {
+                        let id = trees.create_boxed(behaviour);
+                        prototype_trees.trees.insert(handle, id);
+                        id
+                    }
+                    Err(error) => {
+                        prototype_trees.failed.insert(handle);
+                        report(handle, error);
+                        continue;
+                    }
+                }
+            }
+        };
+
+        commands.entity(entity).insert(id);
+    }
+}
+
+fn reload_prototypes(
+    mut events: EventReader<AssetEvent<BehaviourPrototype>>,
+    prototypes: Res<Assets<BehaviourPrototype>>,
+    registry: Res<BehaviourNodeRegistry>,
+    mut trees: ResMut<BehaviourTrees>,
+    mut prototype_trees: ResMut<PrototypeTrees>,
+) {
+    for event in events.iter() {
+        let AssetEvent::Modified {
+            handle: prototype_handle,
+        } = event
+        else {
+            continue;
+        };
+
+        let handle = prototype_handle.id();
+        prototype_trees.failed.remove(&handle);
+
+        let (Some(id), Some(prototype)) = (
+            prototype_trees.trees.get(&handle),
+            prototypes.get(prototype_handle),
+        ) else {
+            continue;
+        };
+
+        match registry.build(&prototype.0) {
+            Ok(behaviour) => {
+                trees.replace_boxed(*id, behaviour);
+            }
+            Err(error) => report(handle, error),
+        }
+    }
+}
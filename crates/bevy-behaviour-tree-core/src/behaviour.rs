@@ -1,4 +1,22 @@
-use bevy::prelude::{Entity, In, IntoSystem, System, World};
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    marker::PhantomData,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use bevy::{
+    ecs::{
+        archetype::ArchetypeComponentId,
+        component::{ComponentId, Tick},
+        query::Access,
+        world::{unsafe_world_cell::UnsafeWorldCell, WorldId},
+    },
+    prelude::{Entity, IntoSystem, Resource, System, World},
+    utils::HashMap,
+};
+
+use crate::plugin::{BehaviourId, BehaviourTrees};
 
 /// The trait at the core of this crate.
 ///
@@ -27,17 +45,330 @@ pub trait Behaviour: Send + Sync + 'static {
     /// Initializes the behaviour. This registers component access for underlying systems, and does general setup work.
     /// Required to be called before [`run`][Behaviour::run].
     fn initialize(&mut self, world: &mut World);
+
+    /// The name of this behaviour's type. Used to identify trees in snapshots, see [`BehaviourTypeRegistry`][crate::plugin::BehaviourTypeRegistry].
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
+    /// The [`BehaviourTypeId`] of this behaviour's type, looking through boxes, [names][Behaviour::with_name] and [shared][Behaviour::shared] behaviours.
+    /// ```
+    /// # use bevy_behaviour_tree_core::{prelude::*, behaviour::behaviour_type_id, compositor::Sequence};
+    /// let tree: Box<dyn Behaviour> = Box::new(Sequence::new(vec![]).with_name("empty"));
+    /// assert_eq!(tree.behaviour_type_id(), behaviour_type_id::<Sequence>());
+    /// ```
+    fn behaviour_type_id(&self) -> BehaviourTypeId {
+        behaviour_type_id::<Self>()
+    }
+
+    /// Captures the per-entity state of this behaviour and its children.
+    ///
+    /// Stateless behaviours can keep the default implementation.
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState::default()
+    }
+
+    /// Restores state previously captured with [`save_state`][Behaviour::save_state].
+    fn load_state(&mut self, _state: &BehaviourState) {}
+
+    /// Forgets all progress this behaviour and its children made for the entity, as if it had never run for it.
+    ///
+    /// Stateless behaviours can keep the default implementation.
+    fn reset_entity(&mut self, _entity: Entity) {}
+
+    /// A short, human readable description of the behaviour's state for the entity, like `"tries: 2/5"`.
+    /// Shown by the [entity stepper][crate::debug::EntityStepper].
+    ///
+    /// Empty by default.
+    fn debug_state(&self, _entity: Entity) -> String {
+        String::new()
+    }
+
+    /// The components this behaviour and its children access, once [initialized][Behaviour::initialize].
+    ///
+    /// Systems report their component access automatically. Custom behaviours accessing the world directly can list what they use here,
+    /// e.g. with [`World::init_component`] during initialization. Recorded by [`BehaviourTrees::dependencies`][crate::plugin::BehaviourTrees::dependencies].
+    ///
+    /// Empty by default.
+    fn dependencies(&self) -> Vec<ComponentId> {
+        Vec::new()
+    }
+
+    /// Prepares the behaviour for an entity, before the first time it runs for it.
+    /// Unlike [`initialize`][Behaviour::initialize], this is called once per entity, e.g. to insert default components or send an on-enter event.
+    ///
+    /// Called again if the entity is [reset][crate::plugin::BehaviourTrees::reset_for_entity]. Does nothing by default.
+    fn warm_up(&mut self, _entity: Entity, _world: &mut World) {}
+
+    /// The structure of this behaviour and its children, without the systems inside it. Used to compare trees, see [`BehaviourShape`].
+    ///
+    /// A [leaf][BehaviourShape::Leaf] by default.
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::Leaf
+    }
+
+    /// Checks for mistakes in the tree, like compositors without children, before it's [created][BehaviourTrees::try_create].
+    ///
+    /// Behaviours with children should validate them too. Does nothing by default.
+    fn validate(&self, _trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        Ok(())
+    }
+
+    /// Gives the behaviour a custom name, returned by [`type_name`][Behaviour::type_name] instead of the type's.
+    ///
+    /// Named trees are looked up by that name when restoring snapshots with a [`BehaviourTypeRegistry`][crate::plugin::BehaviourTypeRegistry].
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::{prelude::*, behaviour::IntoBehaviour};
+    /// # fn walk_to_target(_: In<Entity>) -> Status { Status::Success }
+    /// let walk = walk_to_target
+    ///     .into_behaviour()
+    ///     .with_name("walk")
+    ///     .retry(3)
+    ///     .with_name("walk_with_retry");
+    ///
+    /// assert_eq!(walk.type_name(), "walk_with_retry");
+    /// ```
+    fn with_name(self, name: &'static str) -> Named<Self>
+    where
+        Self: Sized,
+    {
+        Named {
+            name,
+            behaviour: self,
+        }
+    }
+
+    /// Turns the behaviour into one that can be used in several places of a tree at once.
+    ///
+    /// Clones of the returned [`BehaviourRef`] all run the same instance, so they share its state:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// # fn find_cover(_: In<Entity>) -> Status { Status::Failure }
+    /// # fn attack(_: In<Entity>) -> Status { Status::Success }
+    /// # fn flee(_: In<Entity>) -> Status { Status::Success }
+    /// // both branches count towards the same three tries.
+    /// let find_cover = find_cover.retry(3).shared();
+    ///
+    /// let tree = (
+    ///     (find_cover.clone(), attack).sequence(),
+    ///     (find_cover, flee).sequence(),
+    /// ).select();
+    /// ```
+    /// The behaviour is only initialized once per world, no matter how many parents initialize it.
+    fn shared(self) -> BehaviourRef<Self>
+    where
+        Self: Sized,
+    {
+        BehaviourRef(Arc::new(Mutex::new(SharedBehaviour {
+            behaviour: self,
+            initialized_for: None,
+        })))
+    }
+
+    /// Boxes the behaviour, e.g. for building the children of [`Sequence::new`][crate::compositor::Sequence::new]:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::{prelude::*, behaviour::IntoBehaviour, compositor::Sequence};
+    /// # fn find_target(_: In<Entity>) -> Status { Status::Success }
+    /// let tree = Sequence::new(vec![
+    ///     find_target.into_behaviour().to_boxed(),
+    ///     always_succeed().to_boxed(),
+    /// ]);
+    /// ```
+    /// Boxes aren't boxed again.
+    fn to_boxed(self) -> Box<dyn Behaviour>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(self)
+    }
+}
+
+/// The structure of a tree, as returned by [`Behaviour::shape`].
+///
+/// Compositors and decorators are compared by their kind and configuration, like a retry's maximum tries.
+/// Leaves, usually systems, can't be compared, so they're all considered equal:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// # fn walk(_: In<Entity>) -> Status { Status::Success }
+/// # fn run(_: In<Entity>) -> Status { Status::Success }
+/// assert_eq!(walk.retry(5).shape(), run.retry(5).shape());
+/// assert_ne!(walk.retry(5).shape(), walk.retry(3).shape());
+/// ```
+/// Boxed trees can also be compared directly, which compares their shapes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BehaviourShape {
+    /// A node without children that are behaviours themselves.
+    Leaf,
+    /// A compositor or decorator.
+    Node {
+        /// The kind of node, like `"sequence"` or `"retry"`.
+        kind: String,
+        /// How the node is configured, like `"max_tries: 5"`. Empty if there's nothing to configure.
+        config: String,
+        /// The shapes of the node's children, in order.
+        children: Vec<BehaviourShape>,
+    },
+}
+
+impl BehaviourShape {
+    /// Shorthand for [`BehaviourShape::Node`].
+    pub fn node(
+        kind: impl Into<String>,
+        config: impl Into<String>,
+        children: Vec<BehaviourShape>,
+    ) -> Self {
+        Self::Node {
+            kind: kind.into(),
+            config: config.into(),
+            children,
+        }
+    }
+}
+
+impl PartialEq for dyn Behaviour {
+    fn eq(&self, other: &Self) -> bool {
+        self.shape() == other.shape()
+    }
+}
+
+/// Sorts and deduplicates component IDs, e.g. those of several children.
+pub(crate) fn union_dependencies(ids: impl IntoIterator<Item = ComponentId>) -> Vec<ComponentId> {
+    let mut ids = ids.into_iter().collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+/// The per-entity state of a [`Behaviour`] and its children, as returned by [`Behaviour::save_state`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BehaviourState {
+    /// Per-entity progress of this node, like the current child of a sequence or the attempt count of a retry.
+    pub entities: HashMap<Entity, usize>,
+    /// The state of each child node, in order.
+    pub children: Vec<BehaviourState>,
+}
+
+impl BehaviourState {
+    /// State of a node without any entity state of its own.
+    pub fn from_children(children: Vec<BehaviourState>) -> Self {
+        Self {
+            entities: HashMap::default(),
+            children,
+        }
+    }
+
+    /// Loads each child state into the matching behaviour.
+    pub(crate) fn load_children<'a>(
+        &self,
+        behaviours: impl IntoIterator<Item = &'a mut Box<dyn Behaviour>>,
+    ) {
+        for (behaviour, state) in behaviours.into_iter().zip(&self.children) {
+            behaviour.load_state(state);
+        }
+    }
+}
+
+/// Identifies a type of [`Behaviour`] at runtime, like [`ComponentId`] does for components. See [`Behaviour::behaviour_type_id`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BehaviourTypeId(TypeId);
+
+impl BehaviourTypeId {
+    /// The [`TypeId`] of the behaviour type.
+    pub fn type_id(&self) -> TypeId {
+        self.0
+    }
+}
+
+/// The [`BehaviourTypeId`] of `T`.
+pub fn behaviour_type_id<T: Behaviour + ?Sized>() -> BehaviourTypeId {
+    BehaviourTypeId(TypeId::of::<T>())
 }
 
 /// The status of a [`Behaviour`], returned when it's [`run`][Behaviour::run].
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+///
+/// Ordered from worst to best: `Failure < Running < Success`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
 pub enum Status {
-    /// Indicates a successful action.
-    Success,
     /// Indicates a failed action.
     Failure,
     /// Indicates that an action requires more time to complete.
     Running,
+    /// Indicates a successful action.
+    Success,
+}
+
+/// How [`Status::reduce`] combines statuses.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum ReducePolicy {
+    /// **Fails** if any status is a failure. Otherwise **running** if any status is running, and **succeeds** if all succeeded.
+    AnyFailure,
+    /// **Succeeds** if any status is a success. Otherwise **running** if any status is running, and **fails** if all failed.
+    AnySuccess,
+    /// **Succeeds** if all statuses are successes. Otherwise **running** while any status is running, and **fails** once none are.
+    AllSuccess,
+    /// The first status that isn't running, or **running** if there is none.
+    FirstTerminal,
+    /// **Succeeds** if more than half of the statuses are successes, and **fails** once that's no longer possible. **Running** otherwise.
+    Majority,
+}
+
+impl Status {
+    /// Combines statuses according to `policy`, e.g. in custom compositors.
+    ///
+    /// ```
+    /// # use bevy_behaviour_tree_core::{prelude::*, behaviour::ReducePolicy};
+    /// let statuses = [Status::Success, Status::Running, Status::Success];
+    ///
+    /// assert_eq!(Status::reduce(statuses, ReducePolicy::AllSuccess), Status::Running);
+    /// assert_eq!(Status::reduce(statuses, ReducePolicy::Majority), Status::Success);
+    /// ```
+    pub fn reduce(statuses: impl IntoIterator<Item = Status>, policy: ReducePolicy) -> Status {
+        let (mut successes, mut failures, mut total) = (0, 0, 0);
+
+        for status in statuses {
+            match (policy, status) {
+                (ReducePolicy::AnyFailure, Status::Failure)
+                | (ReducePolicy::AnySuccess, Status::Success)
+                | (ReducePolicy::FirstTerminal, Status::Failure | Status::Success) => {
+                    return status
+                }
+                (_, Status::Success) => successes += 1,
+                (_, Status::Failure) => failures += 1,
+                (_, Status::Running) => {}
+            }
+            total += 1;
+        }
+
+        let running = total - successes - failures;
+
+        match policy {
+            ReducePolicy::AnyFailure | ReducePolicy::AllSuccess
+                if running == 0 && failures == 0 =>
+            {
+                Status::Success
+            }
+            ReducePolicy::AnySuccess | ReducePolicy::AllSuccess if running == 0 => Status::Failure,
+            ReducePolicy::Majority if successes * 2 > total => Status::Success,
+            ReducePolicy::Majority if (total - failures) * 2 <= total => Status::Failure,
+            _ => Status::Running,
+        }
+    }
+
+    /// Combines statuses like a [sequence][crate::compositor::Compositor::sequence] would. See [`ReducePolicy::AnyFailure`].
+    pub fn reduce_sequence(statuses: impl IntoIterator<Item = Status>) -> Status {
+        Self::reduce(statuses, ReducePolicy::AnyFailure)
+    }
+
+    /// Combines statuses like a [select][crate::compositor::Compositor::select] would. See [`ReducePolicy::AnySuccess`].
+    pub fn reduce_select(statuses: impl IntoIterator<Item = Status>) -> Status {
+        Self::reduce(statuses, ReducePolicy::AnySuccess)
+    }
 }
 
 impl From<Option<Status>> for Status {
@@ -55,28 +386,200 @@ impl From<bool> for Status {
     }
 }
 
+/// Encodes statuses as `Success = 0`, `Failure = 1` and `Running = 2`, e.g. for network packets.
+impl From<Status> for u8 {
+    fn from(value: Status) -> Self {
+        match value {
+            Status::Success => 0,
+            Status::Failure => 1,
+            Status::Running => 2,
+        }
+    }
+}
+
+/// Decodes statuses encoded with [`u8::from`]. Numbers above 2 are rejected.
+impl TryFrom<u8> for Status {
+    type Error = InvalidStatus;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Status::Success),
+            1 => Ok(Status::Failure),
+            2 => Ok(Status::Running),
+            _ => Err(InvalidStatus(value)),
+        }
+    }
+}
+
+/// A number that doesn't encode a [`Status`]. Returned by the [`TryFrom<u8>`] impl of [`Status`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InvalidStatus(pub u8);
+
+impl std::fmt::Display for InvalidStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a valid status", self.0)
+    }
+}
+
+impl std::error::Error for InvalidStatus {}
+
+/// Types that can be returned from behaviour systems.
+///
+/// Implemented for everything that's [`Into<Status>`], and for [`Result<Status, E>`] where `E: Into<BehaviourError>`.
+/// Errors are turned into [`Status::Failure`] and stored in the [`BehaviourErrors`] resource.
+pub trait IntoStatus: Send + Sync + 'static {
+    /// Converts the output of a behaviour system into a status.
+    fn into_status(self, entity: Entity, world: &mut World) -> Status;
+}
+
+impl<S: Into<Status> + Send + Sync + 'static> IntoStatus for S {
+    #[inline]
+    fn into_status(self, _: Entity, _: &mut World) -> Status {
+        self.into()
+    }
+}
+
+impl<E: Into<BehaviourError> + Send + Sync + 'static> IntoStatus for Result<Status, E> {
+    #[inline]
+    fn into_status(self, entity: Entity, world: &mut World) -> Status {
+        match self {
+            Ok(status) => status,
+            Err(error) => {
+                world
+                    .get_resource_or_insert_with(BehaviourErrors::default)
+                    .errors
+                    .push((entity, error.into()));
+                Status::Failure
+            }
+        }
+    }
+}
+
+/// An error returned by a behaviour. See [`IntoStatus`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum BehaviourError {
+    /// A component the behaviour relies on is missing from the entity.
+    ComponentMissing(ComponentId),
+    /// A resource the behaviour relies on is missing.
+    ResourceMissing(ComponentId),
+    /// Any other error.
+    Custom(String),
+}
+
+impl std::fmt::Display for BehaviourError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ComponentMissing(id) => write!(f, "missing component {id:?}"),
+            Self::ResourceMissing(id) => write!(f, "missing resource {id:?}"),
+            Self::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for BehaviourError {}
+
+impl From<String> for BehaviourError {
+    fn from(value: String) -> Self {
+        Self::Custom(value)
+    }
+}
+
+impl From<&str> for BehaviourError {
+    fn from(value: &str) -> Self {
+        Self::Custom(value.to_owned())
+    }
+}
+
+/// A mistake in a tree, found by [`Behaviour::validate`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ValidationError {
+    /// A compositor has no children, so it can only ever succeed or fail right away.
+    EmptyCompositor,
+    /// A [subtree][crate::leaves::SubtreeBehaviour] uses an ID that doesn't belong to any tree.
+    InvalidSubtreeId(BehaviourId),
+    /// A [subtree][crate::leaves::SubtreeBehaviour] refers to the tree it's part of, directly or through other subtrees.
+    CircularReference,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyCompositor => write!(f, "compositor without children"),
+            Self::InvalidSubtreeId(id) => write!(f, "subtree {id} doesn't exist"),
+            Self::CircularReference => write!(f, "tree contains itself"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Fails for compositors without children, and validates the children otherwise.
+pub(crate) fn validate_compositor(
+    children: impl ExactSizeIterator<Item = Result<(), ValidationError>>,
+) -> Result<(), ValidationError> {
+    if children.len() == 0 {
+        return Err(ValidationError::EmptyCompositor);
+    }
+
+    children.collect()
+}
+
+/// Errors returned by behaviours, along with the entity they were returned for.
+///
+/// Errors pile up until they're [drained][BehaviourErrors::drain].
+#[derive(Resource, Default, Debug)]
+pub struct BehaviourErrors {
+    errors: Vec<(Entity, BehaviourError)>,
+}
+
+impl BehaviourErrors {
+    /// All errors since the last [`drain`][BehaviourErrors::drain], oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &(Entity, BehaviourError)> {
+        self.errors.iter()
+    }
+
+    /// Removes and returns all errors, oldest first.
+    pub fn drain(&mut self) -> impl Iterator<Item = (Entity, BehaviourError)> + '_ {
+        self.errors.drain(..)
+    }
+
+    /// The number of errors.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Whether there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
 struct SystemBehaviour<F>
 where
-    F: System<In = Entity, Out = Status>,
+    F: System<In = Entity, Out: IntoStatus>,
 {
     func: F,
 }
 
 impl<F> Behaviour for SystemBehaviour<F>
 where
-    F: System<In = Entity, Out = Status>,
+    F: System<In = Entity, Out: IntoStatus>,
 {
     #[inline]
     fn initialize(&mut self, world: &mut World) {
         self.func.initialize(world)
     }
 
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.func.component_access().reads_and_writes().collect()
+    }
+
     #[inline]
     fn run(&mut self, entity: Entity, world: &mut World) -> Status {
-        let status = self.func.run(entity, world);
+        let out = self.func.run(entity, world);
         self.func.apply_deferred(world);
 
-        status
+        out.into_status(entity, world)
     }
 }
 
@@ -89,19 +592,553 @@ pub trait IntoBehaviour<Marker> {
     fn into_behaviour(self) -> impl Behaviour;
 }
 
-#[inline]
-fn into_status<S: Into<Status>>(In(into): In<S>) -> Status {
-    Into::into(into)
-}
-
-impl<Marker: 'static, S: Into<Status> + 'static, T> IntoBehaviour<(Marker, S)> for T
+impl<Marker: 'static, S: IntoStatus, T> IntoBehaviour<(Marker, S)> for T
 where
     T: IntoSystem<Entity, S, Marker>,
 {
     #[inline]
     fn into_behaviour(self) -> impl Behaviour {
         SystemBehaviour {
-            func: self.pipe(into_status),
+            func: IntoSystem::into_system(self),
         }
     }
 }
+
+#[doc(hidden)]
+pub struct ClosureMarker<R>(PhantomData<fn() -> R>);
+
+/// Closures with direct world access are behaviours too, for when bevy's system parameters aren't needed:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// let mut trees = BehaviourTrees::default();
+/// trees.create(
+///     (|entity: Entity, world: &mut World| world.get::<Health>(entity).is_some_and(|health| health.0 > 0))
+///         .invert(),
+/// );
+/// ```
+impl<F, R> IntoBehaviour<ClosureMarker<R>> for F
+where
+    F: FnMut(Entity, &mut World) -> R + Send + Sync + 'static,
+    R: IntoStatus,
+{
+    #[inline]
+    fn into_behaviour(self) -> impl Behaviour {
+        ClosureBehaviour(self)
+    }
+}
+
+pub(crate) struct ClosureBehaviour<F>(pub(crate) F);
+
+impl<F, R> IntoBehaviour<SelfMarker> for ClosureBehaviour<F>
+where
+    F: FnMut(Entity, &mut World) -> R + Send + Sync + 'static,
+    R: IntoStatus,
+{
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<F, R> Behaviour for ClosureBehaviour<F>
+where
+    F: FnMut(Entity, &mut World) -> R + Send + Sync + 'static,
+    R: IntoStatus,
+{
+    // no system parameters to register.
+    fn initialize(&mut self, _: &mut World) {}
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        (self.0)(entity, world).into_status(entity, world)
+    }
+}
+
+/// A behaviour that always returns the same [`Status`]. Useful as a placeholder.
+///
+/// See [`always_succeed`] and [`always_fail`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Constant(pub Status);
+
+impl IntoBehaviour<SelfMarker> for Constant {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for Constant {
+    fn initialize(&mut self, _: &mut World) {}
+
+    #[inline]
+    fn run(&mut self, _: Entity, _: &mut World) -> Status {
+        self.0
+    }
+}
+
+impl Behaviour for Box<dyn Behaviour> {
+    fn to_boxed(self) -> Box<dyn Behaviour> {
+        self
+    }
+
+    #[inline]
+    fn initialize(&mut self, world: &mut World) {
+        (**self).initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        (**self).dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        (**self).validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        (**self).shape()
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        (**self).warm_up(entity, world);
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        (**self).run(entity, world)
+    }
+
+    fn type_name(&self) -> &'static str {
+        (**self).type_name()
+    }
+
+    fn behaviour_type_id(&self) -> BehaviourTypeId {
+        (**self).behaviour_type_id()
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        (**self).save_state()
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        (**self).load_state(state);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        (**self).reset_entity(entity);
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        (**self).debug_state(entity)
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for Box<dyn Behaviour> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+/// An optional behaviour. `None` always **succeeds**, so empty slots are skipped in [sequences][crate::compositor::Compositor::sequence]:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, behaviour::IntoBehaviour, compositor::Sequence};
+/// # fn find_target(_: In<Entity>) -> Status { Status::Success }
+/// # fn attack(_: In<Entity>) -> Status { Status::Success }
+/// # let has_weapon = false;
+/// let attack = has_weapon.then(|| attack.into_behaviour().to_boxed());
+/// let tree = Sequence::new(vec![find_target.into_behaviour().to_boxed(), attack.to_boxed()]);
+/// ```
+impl<T: Behaviour> Behaviour for Option<T> {
+    #[inline]
+    fn initialize(&mut self, world: &mut World) {
+        if let Some(behaviour) = self {
+            behaviour.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.as_ref()
+            .map(|behaviour| behaviour.dependencies())
+            .unwrap_or_default()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.as_ref()
+            .map_or(Ok(()), |behaviour| behaviour.validate(trees))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        self.as_ref()
+            .map_or(BehaviourShape::Leaf, |behaviour| behaviour.shape())
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        if let Some(behaviour) = self {
+            behaviour.warm_up(entity, world);
+        }
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        match self {
+            Some(behaviour) => behaviour.run(entity, world),
+            None => Status::Success,
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        self.as_ref()
+            .map(|behaviour| behaviour.save_state())
+            .unwrap_or_default()
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        if let Some(behaviour) = self {
+            behaviour.load_state(state);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        if let Some(behaviour) = self {
+            behaviour.reset_entity(entity);
+        }
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        self.as_ref()
+            .map(|behaviour| behaviour.debug_state(entity))
+            .unwrap_or_default()
+    }
+}
+
+impl<T: Behaviour> IntoBehaviour<SelfMarker> for Option<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+/// A behaviour with a custom [`type_name`][Behaviour::type_name]. See [`Behaviour::with_name`].
+#[derive(Clone)]
+pub struct Named<T: Behaviour> {
+    name: &'static str,
+    behaviour: T,
+}
+
+impl<T: Behaviour> Named<T> {
+    /// The wrapped behaviour.
+    pub fn inner(&self) -> &T {
+        &self.behaviour
+    }
+}
+
+impl<T: Behaviour> IntoBehaviour<SelfMarker> for Named<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour> Behaviour for Named<T> {
+    #[inline]
+    fn initialize(&mut self, world: &mut World) {
+        self.behaviour.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.behaviour.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.behaviour.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node("named", self.name.to_owned(), vec![self.behaviour.shape()])
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.behaviour.warm_up(entity, world);
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        self.behaviour.run(entity, world)
+    }
+
+    fn type_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn behaviour_type_id(&self) -> BehaviourTypeId {
+        self.behaviour.behaviour_type_id()
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        self.behaviour.save_state()
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.behaviour.load_state(state);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.behaviour.reset_entity(entity);
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        self.behaviour.debug_state(entity)
+    }
+}
+
+/// A behaviour shared between several parents. See [`Behaviour::shared`].
+pub struct BehaviourRef<T: Behaviour>(Arc<Mutex<SharedBehaviour<T>>>);
+
+struct SharedBehaviour<T> {
+    behaviour: T,
+    // every parent initializes its children, but systems can only be initialized once.
+    initialized_for: Option<WorldId>,
+}
+
+impl<T: Behaviour> BehaviourRef<T> {
+    // a behaviour that panicked is still usable, see `BehaviourTreePlugin::with_error_handler`.
+    fn lock(&self) -> MutexGuard<'_, SharedBehaviour<T>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Behaviour> Clone for BehaviourRef<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T: Behaviour> IntoBehaviour<SelfMarker> for BehaviourRef<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour> Behaviour for BehaviourRef<T> {
+    fn initialize(&mut self, world: &mut World) {
+        let mut shared = self.lock();
+        if shared.initialized_for != Some(world.id()) {
+            shared.initialized_for = Some(world.id());
+            shared.behaviour.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.lock().behaviour.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.lock().behaviour.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        self.lock().behaviour.shape()
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.lock().behaviour.warm_up(entity, world);
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        self.lock().behaviour.run(entity, world)
+    }
+
+    fn type_name(&self) -> &'static str {
+        self.lock().behaviour.type_name()
+    }
+
+    fn behaviour_type_id(&self) -> BehaviourTypeId {
+        self.lock().behaviour.behaviour_type_id()
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        self.lock().behaviour.save_state()
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.lock().behaviour.load_state(state);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.lock().behaviour.reset_entity(entity);
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        self.lock().behaviour.debug_state(entity)
+    }
+}
+
+/// A behaviour that always succeeds.
+pub fn always_succeed() -> impl Behaviour + IntoBehaviour<SelfMarker> + Clone {
+    Constant(Status::Success)
+}
+
+/// A behaviour that always fails.
+pub fn always_fail() -> impl Behaviour + IntoBehaviour<SelfMarker> + Clone {
+    Constant(Status::Failure)
+}
+
+/// A behaviour with its own per-entity state, without needing a component for it.
+///
+/// Every entity starts out with `S::default()`. The state is dropped when the entity is [reset][Behaviour::reset_entity].
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// // succeeds every third tick.
+/// let every_third = stateful(|_: Entity, _: &mut World, ticks: &mut u32| {
+///     *ticks += 1;
+///     *ticks % 3 == 0
+/// });
+/// ```
+pub fn stateful<S, F, R>(func: F) -> impl Behaviour + IntoBehaviour<SelfMarker>
+where
+    S: Default + Send + Sync + 'static,
+    F: FnMut(Entity, &mut World, &mut S) -> R + Send + Sync + 'static,
+    R: Into<Status>,
+{
+    Stateful {
+        func,
+        states: HashMap::default(),
+    }
+}
+
+struct Stateful<S, F> {
+    func: F,
+    states: HashMap<Entity, S>,
+}
+
+impl<S, F, R> IntoBehaviour<SelfMarker> for Stateful<S, F>
+where
+    S: Default + Send + Sync + 'static,
+    F: FnMut(Entity, &mut World, &mut S) -> R + Send + Sync + 'static,
+    R: Into<Status>,
+{
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<S, F, R> Behaviour for Stateful<S, F>
+where
+    S: Default + Send + Sync + 'static,
+    F: FnMut(Entity, &mut World, &mut S) -> R + Send + Sync + 'static,
+    R: Into<Status>,
+{
+    fn initialize(&mut self, _: &mut World) {}
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.states.remove(&entity);
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let state = self.states.entry(entity).or_default();
+        (self.func)(entity, world, state).into()
+    }
+}
+
+/// Turns a behaviour into a [`System`], so it can be used with bevy's system piping.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, behaviour::into_system};
+/// fn log_status(In(status): In<Status>) {
+///     info!("tree finished tick with {status:?}");
+/// }
+///
+/// # fn walk(_: In<Entity>) -> Status { Status::Success }
+/// let system = into_system(walk.retry(3)).pipe(log_status);
+/// ```
+///
+/// The resulting system is exclusive, since behaviours need full world access.
+pub fn into_system<T: Behaviour>(behaviour: T) -> BehaviourSystem<T> {
+    BehaviourSystem {
+        behaviour,
+        component_access: Access::default(),
+        archetype_component_access: Access::default(),
+        last_run: Tick::new(0),
+    }
+}
+
+/// See [`into_system`].
+pub struct BehaviourSystem<T: Behaviour> {
+    behaviour: T,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+    last_run: Tick,
+}
+
+impl<T: Behaviour> System for BehaviourSystem<T> {
+    type In = Entity;
+    type Out = Status;
+
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        Cow::Borrowed(self.behaviour.type_name())
+    }
+
+    #[inline]
+    fn type_id(&self) -> TypeId {
+        TypeId::of::<T>()
+    }
+
+    #[inline]
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    #[inline]
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    #[inline]
+    fn is_send(&self) -> bool {
+        // like other exclusive systems, we always run on the main thread.
+        false
+    }
+
+    #[inline]
+    fn is_exclusive(&self) -> bool {
+        true
+    }
+
+    unsafe fn run_unsafe(&mut self, _: Self::In, _: UnsafeWorldCell) -> Self::Out {
+        panic!("Cannot run behaviour systems with a shared World reference");
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let status = self.behaviour.run(entity, world);
+        self.last_run = world.change_tick();
+
+        status
+    }
+
+    #[inline]
+    fn apply_deferred(&mut self, _: &mut World) {
+        // behaviours apply their own buffers at the end of every run.
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.behaviour.initialize(world);
+        self.last_run = world.change_tick();
+    }
+
+    fn update_archetype_component_access(&mut self, _: UnsafeWorldCell) {}
+
+    // change detection is handled by the systems inside the behaviour.
+    fn check_change_tick(&mut self, _: Tick) {}
+
+    fn get_last_run(&self) -> Tick {
+        self.last_run
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.last_run = last_run;
+    }
+}
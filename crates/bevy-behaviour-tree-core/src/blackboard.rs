@@ -0,0 +1,276 @@
+use std::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use bevy::{
+    prelude::{Component, Entity, Resource, World},
+    utils::HashMap,
+};
+
+use crate::{
+    behaviour::{IntoBehaviour, SelfMarker},
+    prelude::{Behaviour, Status},
+};
+
+/// Typed values shared between all trees and entities, stored by key.
+///
+/// Values are stored in an [`Arc`], so reading them is cheap.
+/// ```
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// let mut blackboard = GlobalBlackboard::default();
+/// blackboard.set("alarm_raised", true);
+///
+/// assert_eq!(blackboard.get::<bool>("alarm_raised").as_deref(), Some(&true));
+/// // values of a different type are treated as missing.
+/// assert!(blackboard.get::<u32>("alarm_raised").is_none());
+/// ```
+#[derive(Resource, Default)]
+pub struct GlobalBlackboard {
+    entries: HashMap<String, Arc<dyn Any + Send + Sync>>,
+}
+
+impl GlobalBlackboard {
+    /// Stores `value` under `key`, replacing any previous value.
+    pub fn set<T: Send + Sync + 'static>(&mut self, key: impl Into<String>, value: T) {
+        self.set_arc(key, Arc::new(value));
+    }
+
+    fn set_arc<T: Send + Sync + 'static>(&mut self, key: impl Into<String>, value: Arc<T>) {
+        self.entries.insert(key.into(), value);
+    }
+
+    /// The value stored under `key`, if there is one of type `T`.
+    pub fn get<T: Send + Sync + 'static>(&self, key: &str) -> Option<Arc<T>> {
+        self.entries.get(key)?.clone().downcast().ok()
+    }
+
+    /// Whether any value is stored under `key`.
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Removes the value stored under `key`. Returns whether there was one.
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.entries.remove(key).is_some()
+    }
+}
+
+/// Per-entity values, stored by type and key.
+///
+/// Keys are `&'static str`s and values are keyed by their type as well, so the same key can hold one value of each type without clashing:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// let mut blackboard = TypedBlackboard::default();
+/// blackboard.set("target", Vec2::new(1., 2.));
+///
+/// assert_eq!(blackboard.get::<Vec2>("target"), Some(&Vec2::new(1., 2.)));
+/// assert_eq!(blackboard.get::<f32>("target"), None);
+/// ```
+#[derive(Component, Default)]
+pub struct TypedBlackboard {
+    entries: HashMap<(TypeId, &'static str), Box<dyn Any + Send + Sync>>,
+}
+
+impl TypedBlackboard {
+    /// Stores `value` under `key`, replacing any previous value of the same type.
+    pub fn set<T: Send + Sync + 'static>(&mut self, key: &'static str, value: T) {
+        self.entries
+            .insert((TypeId::of::<T>(), key), Box::new(value));
+    }
+
+    /// The value of type `T` stored under `key`, if there is one.
+    pub fn get<T: Send + Sync + 'static>(&self, key: &'static str) -> Option<&T> {
+        self.entries.get(&(TypeId::of::<T>(), key))?.downcast_ref()
+    }
+
+    /// Like [`get`][TypedBlackboard::get], but mutable.
+    pub fn get_mut<T: Send + Sync + 'static>(&mut self, key: &'static str) -> Option<&mut T> {
+        self.entries
+            .get_mut(&(TypeId::of::<T>(), key))?
+            .downcast_mut()
+    }
+
+    /// Removes the value of type `T` stored under `key`, returning it if there was one.
+    pub fn remove<T: Send + Sync + 'static>(&mut self, key: &'static str) -> Option<T> {
+        let value = self.entries.remove(&(TypeId::of::<T>(), key))?;
+        value.downcast().ok().map(|value| *value)
+    }
+}
+
+/// A resource holding a single value of type `T`, for sharing state between entities without defining a new resource type.
+///
+/// It can also hand out a limited number of slots, e.g. so only a few entities patrol the same sector at once:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// #[derive(Default)]
+/// struct SectorA;
+///
+/// fn start_patrol(_: In<Entity>, mut sector: ResMut<SharedState<SectorA>>) -> Status {
+///     sector.take_slot().into()
+/// }
+///
+/// let mut app = App::new();
+/// app.insert_resource(SharedState::new(SectorA).with_slots(3));
+/// ```
+/// Without [`with_slots`][SharedState::with_slots], there's no limit.
+#[derive(Resource, Default, Debug)]
+pub struct SharedState<T: Default + Send + Sync + 'static> {
+    value: T,
+    slots: Option<usize>,
+}
+
+impl<T: Default + Send + Sync + 'static> SharedState<T> {
+    /// Creates the state with an initial value.
+    pub fn new(initial: T) -> Self {
+        Self {
+            value: initial,
+            slots: None,
+        }
+    }
+
+    /// Limits how many [slots][SharedState::take_slot] can be taken at once.
+    pub fn with_slots(mut self, slots: usize) -> Self {
+        self.slots = Some(slots);
+        self
+    }
+
+    /// The shared value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Like [`get`][SharedState::get], but mutable.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Takes a slot if one is left. Returns whether it did.
+    pub fn take_slot(&mut self) -> bool {
+        match &mut self.slots {
+            Some(0) => false,
+            Some(slots) => {
+                *slots -= 1;
+                true
+            }
+            None => true,
+        }
+    }
+
+    /// Gives back a slot taken with [`take_slot`][SharedState::take_slot].
+    pub fn release_slot(&mut self) {
+        if let Some(slots) = &mut self.slots {
+            *slots += 1;
+        }
+    }
+
+    /// How many slots are left, or `None` if there's no limit.
+    pub fn slots_left(&self) -> Option<usize> {
+        self.slots
+    }
+}
+
+/// Stores a value in the [`GlobalBlackboard`], inserting the resource if needed.
+///
+/// **Succeeds** immediately.
+pub struct GlobalBlackboardSet<T: Send + Sync + 'static> {
+    key: String,
+    value: Arc<T>,
+}
+
+impl<T: Send + Sync + 'static> GlobalBlackboardSet<T> {
+    /// Stores `value` under `key` whenever this runs.
+    pub fn new(key: impl Into<String>, value: T) -> Self {
+        Self {
+            key: key.into(),
+            value: Arc::new(value),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Clone for GlobalBlackboardSet<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            value: Arc::clone(&self.value),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> IntoBehaviour<SelfMarker> for GlobalBlackboardSet<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Send + Sync + 'static> Behaviour for GlobalBlackboardSet<T> {
+    fn initialize(&mut self, world: &mut World) {
+        world.init_resource::<GlobalBlackboard>();
+    }
+
+    #[inline]
+    fn run(&mut self, _: Entity, world: &mut World) -> Status {
+        world
+            .get_resource_or_insert_with(GlobalBlackboard::default)
+            .set_arc(self.key.clone(), Arc::clone(&self.value));
+        Status::Success
+    }
+}
+
+/// Checks a value in the [`GlobalBlackboard`].
+///
+/// **Succeeds** if there's a value of type `T` under the key and the predicate returns true for it. **Fails** otherwise.
+/// ```
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// let enough_gold = GlobalBlackboardCheck::new("gold", |gold: &u32| *gold >= 100);
+/// ```
+pub struct GlobalBlackboardCheck<T, F> {
+    key: String,
+    predicate: F,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, F> GlobalBlackboardCheck<T, F>
+where
+    T: Send + Sync + 'static,
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+{
+    /// Checks the value stored under `key` with `predicate` whenever this runs.
+    pub fn new(key: impl Into<String>, predicate: F) -> Self {
+        Self {
+            key: key.into(),
+            predicate,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<T, F> IntoBehaviour<SelfMarker> for GlobalBlackboardCheck<T, F>
+where
+    T: Send + Sync + 'static,
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+{
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T, F> Behaviour for GlobalBlackboardCheck<T, F>
+where
+    T: Send + Sync + 'static,
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+{
+    fn initialize(&mut self, _: &mut World) {}
+
+    #[inline]
+    fn run(&mut self, _: Entity, world: &mut World) -> Status {
+        world
+            .get_resource::<GlobalBlackboard>()
+            .and_then(|blackboard| blackboard.get::<T>(&self.key))
+            .is_some_and(|value| (self.predicate)(&value))
+            .into()
+    }
+}
@@ -1,69 +1,358 @@
+use std::{marker::PhantomData, time::Duration};
+
 use bevy::{
-    prelude::{Entity, World},
+    ecs::component::ComponentId,
+    prelude::{Component, Entity, IntoSystem, System, Time, World},
     utils::{all_tuples, HashMap},
 };
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
 
 use crate::{
-    behaviour::{IntoBehaviour, SelfMarker},
+    behaviour::{
+        union_dependencies, validate_compositor, BehaviourShape, BehaviourState, IntoBehaviour,
+        SelfMarker, ValidationError,
+    },
+    decorator::try_if,
+    plugin::BehaviourTrees,
     prelude::{Behaviour, Status},
 };
 
-/// Helper trait for [`Behaviour`] tuples.
+/// The children of a [`Sequence`] or [`Select`].
+/// With the `smallvec` feature, up to 4 children are stored inline instead of on the heap.
+#[cfg(feature = "smallvec")]
+type Children = smallvec::SmallVec<[Box<dyn Behaviour>; 4]>;
+#[cfg(not(feature = "smallvec"))]
+type Children = Vec<Box<dyn Behaviour>>;
+
+/// Children added to a [`Sequence`] after it was initialized, which still need to be initialized themselves.
+///
+/// `None` until the compositor is initialized, since that initializes all children anyway.
+#[derive(Default)]
+struct AddedChildren(Option<Vec<usize>>);
+
+impl AddedChildren {
+    fn initialize_all(&mut self, funcs: &mut Children, world: &mut World) {
+        for func in funcs.iter_mut() {
+            func.initialize(world);
+        }
+        self.0 = Some(Vec::new());
+    }
+
+    /// Initializes the children added since the last run.
+    #[inline]
+    fn initialize(&mut self, funcs: &mut Children, world: &mut World) {
+        if let Some(added) = &mut self.0 {
+            for index in added.drain(..) {
+                funcs[index].initialize(world);
+            }
+        }
+    }
+
+    fn insert(&mut self, index: usize) {
+        if let Some(added) = &mut self.0 {
+            for added in added.iter_mut().filter(|added| **added >= index) {
+                *added += 1;
+            }
+            added.push(index);
+        }
+    }
+
+    fn drain(&mut self, count: usize) {
+        if let Some(added) = &mut self.0 {
+            added.retain(|index| *index >= count);
+            for index in added.iter_mut() {
+                *index -= count;
+            }
+        }
+    }
+}
+
+/// Helper trait for [`Behaviour`] tuples, arrays and [`Vec`]s.
 trait BehaviourGroup<Marker> {
-    fn group(self) -> Vec<Box<dyn Behaviour>>;
+    fn group(self) -> Children;
 }
 
 macro_rules! impl_behaviour_group {
     ($(($name:ident,$marker:ident)),*) => {
         impl<$($marker: 'static, $name: IntoBehaviour<$marker>),*> BehaviourGroup<($($marker,)*)> for ($($name,)*) {
-            fn group(self) -> Vec<Box<dyn Behaviour>> {
+            fn group(self) -> Children {
                 #[allow(non_snake_case)]
                 let ($($name,)*) = self;
 
-                vec![$(Box::new(IntoBehaviour::into_behaviour($name))),*]
+                [$(Box::new(IntoBehaviour::into_behaviour($name)) as Box<dyn Behaviour>),*]
+                    .into_iter()
+                    .collect()
             }
         }
     }
 }
 
-all_tuples!(impl_behaviour_group, 2, 15, B, M);
+all_tuples!(impl_behaviour_group, 1, 15, B, M);
+
+impl<M: 'static, T: IntoBehaviour<M>, const N: usize> BehaviourGroup<[M; N]> for [T; N] {
+    fn group(self) -> Children {
+        self.into_iter()
+            .map(|behaviour| Box::new(behaviour.into_behaviour()) as Box<dyn Behaviour>)
+            .collect()
+    }
+}
+
+impl BehaviourGroup<SelfMarker> for Vec<Box<dyn Behaviour>> {
+    fn group(self) -> Children {
+        self.into_iter().collect()
+    }
+}
 
 /// *Composite* nodes take a group of input nodes, run them and transform their ouput.
+///
+/// Groups are tuples of up to 15 nodes. Nodes of the same type can also be grouped as an array, and boxed nodes as a [`Vec`]:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// # fn walk(_: In<Entity>) -> Status { Status::Success }
+/// # fn look_around(_: In<Entity>) -> Status { Status::Success }
+/// let patrol = [walk, look_around, walk].sequence();
+/// ```
+///
+/// As with [`Decorator`][crate::decorator::Decorator], the trait needs to be in scope, e.g. with `use bevy_behaviour_tree::prelude::*`:
+/// ```compile_fail,E0599
+/// # use bevy::prelude::*;
+/// # fn walk(_: In<Entity>) -> bevy_behaviour_tree_core::prelude::Status { unimplemented!() }
+/// let patrol = (walk, walk).sequence();
+/// ```
 pub trait Compositor<Marker> {
     /// Runs the input nodes sequentially.
     ///
     /// **Succeeds** if all input nodes succeed.
     /// **Fails** if any input node fails.
+    ///
+    /// A sequence of a single node (`(node,).sequence()`) behaves exactly like that node, returning its status directly.
     fn sequence(self) -> Sequence;
     /// Selects between the input branches.
     ///
     /// **Succeeds** as soon as any node succeeds. **Fails** if all of them fail.
+    ///
+    /// Like with [`sequence`][Compositor::sequence], a select of a single node behaves exactly like that node.
     fn select(self) -> Select;
+    /// Runs all input nodes every tick, until the policy decides the outcome.
+    ///
+    /// See [`ParallelPolicy`] for when this **succeeds** or **fails**. Finished nodes aren't run again until the outcome is decided.
+    fn parallel(self, policy: ParallelPolicy) -> DynamicParallel;
+    /// Takes turns between the input nodes, running each of them up to `step` times per tick, in order.
+    ///
+    /// **Succeeds** once all input nodes succeeded. **Fails** as soon as any input node fails.
+    /// Finished nodes aren't run again until the outcome is decided.
+    fn interleave(self, step: usize) -> Interleave;
+    /// Runs all input nodes every tick, waiting for any of them to succeed, like when any one of several objectives will do.
+    ///
+    /// **Succeeds** as soon as any node succeeds, ignoring nodes that failed before. **Fails** once all of them failed.
+    fn race_first_success(self) -> RaceFirstSuccess;
+    /// Runs all input nodes every tick, until every one of them finished.
+    ///
+    /// **Succeeds** if any node succeeded. **Fails** if all of them failed.
+    fn race_last_success(self) -> RaceLastSuccess;
+    /// Runs all input nodes every tick, until every one of them finished. Nodes that failed aren't run again.
+    ///
+    /// **Succeeds** if all nodes succeeded. **Fails** if any of them failed, but only once the others finished too.
+    fn all_or_nothing(self) -> AllOrNothing;
+    /// Tries the input nodes from cheapest (first) to most expensive (last), one per tick.
+    ///
+    /// Moves on to the next node after the current one failed `escalation_threshold` times in a row,
+    /// and goes back to the first one `cooldown` after the last escalation, even if it kept failing in the meantime. See [`BackoffSelect`].
+    ///
+    /// **Succeeds** or **fails** when the current node does.
+    fn backoff_select(self, escalation_threshold: usize, cooldown: Duration) -> BackoffSelect;
+    /// Runs the input nodes sequentially, starting over from the first one after the last one succeeded.
+    ///
+    /// Never finishes on its own, so this is always **running**. A failing node also starts it over.
+    /// See [`repeat_sequence_until`] for a version that stops.
+    fn repeat_sequence(self) -> RepeatSequence;
+    /// Runs the input nodes sequentially, but never more than one per tick.
+    ///
+    /// Even a sequence of nodes that all succeed right away takes one tick per node, plus one more to report the result.
+    /// [`sequence`][Compositor::sequence] usually does the same, but runs a single node as is; this guarantees the timing either way.
+    ///
+    /// **Succeeds** the tick after the last node succeeded. **Fails** if any input node fails.
+    fn fenced_sequence(self) -> Fence;
+
+    /// Shorthand for [`sequence().with_memory()`][Sequence::with_memory].
+    fn sequence_memory(self) -> Sequence
+    where
+        Self: Sized,
+    {
+        self.sequence().with_memory()
+    }
+
+    /// Shorthand for [`select().with_memory()`][Select::with_memory].
+    fn select_memory(self) -> Select
+    where
+        Self: Sized,
+    {
+        self.select().with_memory()
+    }
+
+    /// The old name of [`sequence`][Compositor::sequence].
+    #[deprecated(since = "0.1.0", note = "Use `sequence` instead")]
+    fn chain(self) -> Sequence
+    where
+        Self: Sized,
+    {
+        self.sequence()
+    }
 }
 
 impl<Marker, T: BehaviourGroup<Marker>> Compositor<Marker> for T {
     fn sequence(self) -> Sequence {
-        Sequence {
-            funcs: BehaviourGroup::group(self),
-            indices: HashMap::default(),
-        }
+        Sequence::from_children(BehaviourGroup::group(self))
     }
 
     fn select(self) -> Select {
-        Select {
-            funcs: BehaviourGroup::group(self),
-            indices: HashMap::default(),
-        }
+        Select::from_children(BehaviourGroup::group(self))
+    }
+
+    fn parallel(self, policy: ParallelPolicy) -> DynamicParallel {
+        DynamicParallel::new(BehaviourGroup::group(self).into_iter().collect(), policy)
+    }
+
+    fn interleave(self, step: usize) -> Interleave {
+        Interleave::new(BehaviourGroup::group(self).into_iter().collect(), step)
+    }
+
+    fn race_first_success(self) -> RaceFirstSuccess {
+        RaceFirstSuccess::new(BehaviourGroup::group(self).into_iter().collect())
+    }
+
+    fn race_last_success(self) -> RaceLastSuccess {
+        RaceLastSuccess::new(BehaviourGroup::group(self).into_iter().collect())
+    }
+
+    fn all_or_nothing(self) -> AllOrNothing {
+        AllOrNothing::new(BehaviourGroup::group(self).into_iter().collect())
+    }
+
+    fn backoff_select(self, escalation_threshold: usize, cooldown: Duration) -> BackoffSelect {
+        BackoffSelect::new(
+            BehaviourGroup::group(self).into_iter().collect(),
+            escalation_threshold,
+            cooldown,
+        )
+    }
+
+    fn repeat_sequence(self) -> RepeatSequence {
+        RepeatSequence::new(BehaviourGroup::group(self).into_iter().collect())
+    }
+
+    fn fenced_sequence(self) -> Fence {
+        Fence::new(BehaviourGroup::group(self).into_iter().collect())
     }
 }
 
-/// See [`Compositor::chain`].
+/// See [`Compositor::sequence`].
 pub struct Sequence {
-    funcs: Vec<Box<dyn Behaviour>>,
+    funcs: Children,
+    // only entities partway through the sequence have an entry.
     indices: HashMap<Entity, usize>,
+    memory: bool,
+    added: AddedChildren,
 }
 
 impl Sequence {
+    /// A sequence of already boxed children, e.g. when building trees at runtime.
+    pub fn new(children: Vec<Box<dyn Behaviour>>) -> Self {
+        Self::from_children(children.into_iter().collect())
+    }
+
+    fn from_children(children: Children) -> Self {
+        Self {
+            funcs: children,
+            indices: HashMap::default(),
+            memory: false,
+            added: AddedChildren::default(),
+        }
+    }
+
+    /// Whether the children are stored inline, without a separate heap allocation. Only up to 4 children fit.
+    ///
+    /// Requires the `smallvec` feature.
+    #[cfg(feature = "smallvec")]
+    pub fn is_inline(&self) -> bool {
+        !self.funcs.spilled()
+    }
+
+    /// The number of children.
+    pub fn len(&self) -> usize {
+        self.funcs.len()
+    }
+
+    /// Whether there are no children.
+    pub fn is_empty(&self) -> bool {
+        self.funcs.is_empty()
+    }
+
+    /// The index of the child currently active for the entity.
+    pub fn current_index(&self, entity: Entity) -> usize {
+        self.indices.get(&entity).copied().unwrap_or(0)
+    }
+
+    /// Keeps the entity's current child when a child fails, instead of starting over from the first one.
+    /// The next time the sequence runs, it retries the failed child.
+    pub fn with_memory(mut self) -> Self {
+        self.memory = true;
+        self
+    }
+
+    /// Whether this sequence [remembers][Sequence::with_memory] its position when a child fails.
+    pub fn has_memory(&self) -> bool {
+        self.memory
+    }
+
+    /// Removes all children the entity has already completed, so they aren't kept around for the rest of the sequence.
+    /// Returns how many were removed.
+    ///
+    /// This changes the sequence for *every* entity: other entities' indices are shifted to match,
+    /// and entities that hadn't gotten past the removed children yet continue with the entity's current child.
+    pub fn drain_completed(&mut self, entity: Entity) -> usize {
+        let completed = self.current_index(entity).min(self.funcs.len());
+
+        self.funcs.drain(..completed);
+        self.added.drain(completed);
+        for index in self.indices.values_mut() {
+            *index = index.saturating_sub(completed);
+        }
+
+        completed
+    }
+
+    /// Adds a child in front of all others. Entities already inside the sequence keep their current child,
+    /// and only run the new one the next time they start the sequence over.
+    ///
+    /// Works on trees that are already running, too: like with [`append`][Sequence::append], the child is initialized the next time the sequence runs.
+    pub fn prepend<Marker>(&mut self, behaviour: impl IntoBehaviour<Marker> + 'static) {
+        self.funcs
+            .insert(0, Box::new(IntoBehaviour::into_behaviour(behaviour)));
+        self.added.insert(0);
+        for index in self.indices.values_mut() {
+            *index += 1;
+        }
+    }
+
+    /// Adds a child after all others.
+    pub fn append<Marker>(&mut self, behaviour: impl IntoBehaviour<Marker> + 'static) {
+        self.funcs
+            .push(Box::new(IntoBehaviour::into_behaviour(behaviour)));
+        self.added.insert(self.funcs.len() - 1);
+    }
+
+    /// Turns this into a [`Select`] over the same children. Entities keep their current child.
+    pub fn into_select(self) -> Select {
+        Select {
+            funcs: self.funcs,
+            indices: self.indices,
+            memory: self.memory,
+            added: self.added,
+        }
+    }
+
     #[inline]
     fn index(&mut self, entity: Entity) -> usize {
         match self.indices.get(&entity) {
@@ -75,18 +364,17 @@ impl Sequence {
         }
     }
 
+    /// Forgets the entity's position, so [`prepend`][Sequence::prepend] can tell it apart from entities still on the first child.
+    /// No-op for entities we haven't seen yet, since they're implicitly at index 0.
     #[inline]
     fn reset(&mut self, entity: Entity) {
-        if let Some(index) = self.indices.get_mut(&entity) {
-            *index = 0;
-        }
+        self.indices.remove(&entity);
     }
 
     #[inline]
     fn increase(&mut self, entity: Entity) {
-        if let Some(index) = self.indices.get_mut(&entity) {
-            *index += 1;
-        }
+        // entities we haven't seen yet start at 0, so advancing them moves them to the second child.
+        *self.indices.entry(entity).or_insert(0) += 1;
     }
 
     #[inline]
@@ -104,18 +392,77 @@ impl IntoBehaviour<SelfMarker> for Sequence {
 
 impl Behaviour for Sequence {
     fn initialize(&mut self, world: &mut World) {
+        self.added.initialize_all(&mut self.funcs, world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|func| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "sequence",
+            format!("memory: {}", self.memory),
+            self.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
         for func in &mut self.funcs {
-            func.initialize(world);
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.indices.clone(),
+            children: self.funcs.iter().map(|func| func.save_state()).collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.indices = state.entities.clone();
+        state.load_children(&mut self.funcs);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.indices.remove(&entity);
+        for func in &mut self.funcs {
+            func.reset_entity(entity);
         }
     }
 
+    fn debug_state(&self, entity: Entity) -> String {
+        format!("index: {}", self.current_index(entity))
+    }
+
     #[inline]
     fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        self.added.initialize(&mut self.funcs, world);
+
+        // a single node has nothing to move on to, so it's run as is.
+        if let [func] = &mut self.funcs[..] {
+            let status = func.run(entity, world);
+            // entities still on the node keep their index, in case others are prepended.
+            if status == Status::Running || (status == Status::Failure && self.memory) {
+                self.indices.insert(entity, 0);
+            } else {
+                self.indices.remove(&entity);
+            }
+            return status;
+        }
+
         if let Some(behaviour) = self.behaviour_mut(entity) {
             match behaviour.run(entity, world) {
                 Status::Running => Status::Running,
                 Status::Failure => {
-                    self.reset(entity);
+                    if !self.memory {
+                        self.reset(entity);
+                    }
                     Status::Failure
                 }
                 Status::Success => {
@@ -130,77 +477,2256 @@ impl Behaviour for Sequence {
     }
 }
 
-/// See [`CompositeInput::select`].
-pub struct Select {
+/// See [`Compositor::repeat_sequence`] and [`repeat_sequence_until`].
+pub struct RepeatSequence {
     funcs: Vec<Box<dyn Behaviour>>,
     indices: HashMap<Entity, usize>,
+    stop: Option<Box<dyn System<In = Entity, Out = bool>>>,
 }
 
-impl Select {
-    #[inline]
-    pub(crate) fn behaviour_mut(&mut self, entity: Entity) -> Option<&mut Box<dyn Behaviour>> {
-        let index = self.index(entity);
-        self.funcs.get_mut(index)
+impl RepeatSequence {
+    /// A repeating sequence of already boxed children, e.g. when building trees at runtime.
+    pub fn new(children: Vec<Box<dyn Behaviour>>) -> Self {
+        Self {
+            funcs: children,
+            indices: HashMap::default(),
+            stop: None,
+        }
     }
 
-    #[inline]
-    fn index(&mut self, entity: Entity) -> usize {
-        match self.indices.get(&entity) {
-            Some(index) => *index,
-            None => {
-                self.indices.insert(entity, 0);
-                0
-            }
+    /// The index of the child currently active for the entity.
+    pub fn current_index(&self, entity: Entity) -> usize {
+        self.indices.get(&entity).copied().unwrap_or(0)
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for RepeatSequence {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for RepeatSequence {
+    fn initialize(&mut self, world: &mut World) {
+        for func in &mut self.funcs {
+            func.initialize(world);
+        }
+
+        if let Some(stop) = &mut self.stop {
+            stop.initialize(world);
         }
     }
 
-    #[inline]
-    fn reset(&mut self, entity: Entity) {
-        if let Some(index) = self.indices.get_mut(&entity) {
-            *index = 0;
+    fn dependencies(&self) -> Vec<ComponentId> {
+        let stop = self
+            .stop
+            .iter()
+            .flat_map(|stop| stop.component_access().reads_and_writes());
+
+        union_dependencies(
+            self.funcs
+                .iter()
+                .flat_map(|func| func.dependencies())
+                .chain(stop),
+        )
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "repeat_sequence",
+            format!("until: {}", self.stop.is_some()),
+            self.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for func in &mut self.funcs {
+            func.warm_up(entity, world);
         }
     }
 
-    #[inline]
-    pub(crate) fn increase(&mut self, entity: Entity) {
-        if let Some(index) = self.indices.get_mut(&entity) {
-            *index += 1;
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.indices.clone(),
+            children: self.funcs.iter().map(|func| func.save_state()).collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.indices = state.entities.clone();
+        state.load_children(&mut self.funcs);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.indices.remove(&entity);
+        for func in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        format!("index: {}", self.current_index(entity))
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let index = self.current_index(entity);
+        let Some(behaviour) = self.funcs.get_mut(index) else {
+            return Status::Running;
+        };
+
+        match behaviour.run(entity, world) {
+            Status::Running => Status::Running,
+            Status::Failure => {
+                self.indices.remove(&entity);
+                Status::Running
+            }
+            Status::Success if index + 1 < self.funcs.len() => {
+                self.indices.insert(entity, index + 1);
+                Status::Running
+            }
+            Status::Success => {
+                self.indices.remove(&entity);
+
+                let stopped = self
+                    .stop
+                    .as_mut()
+                    .is_some_and(|stop| stop.run(entity, world));
+
+                if stopped {
+                    Status::Success
+                } else {
+                    Status::Running
+                }
+            }
         }
     }
 }
 
-impl IntoBehaviour<SelfMarker> for Select {
-    #[inline]
+/// See [`Compositor::fenced_sequence`].
+pub struct Fence {
+    funcs: Vec<Box<dyn Behaviour>>,
+    // reaches `funcs.len()` once the last child succeeded, until the next tick reports it.
+    indices: HashMap<Entity, usize>,
+}
+
+impl Fence {
+    /// A fenced sequence of already boxed children, e.g. when building trees at runtime.
+    pub fn new(children: Vec<Box<dyn Behaviour>>) -> Self {
+        Self {
+            funcs: children,
+            indices: HashMap::default(),
+        }
+    }
+
+    /// The index of the child that runs next for the entity.
+    pub fn current_index(&self, entity: Entity) -> usize {
+        self.indices.get(&entity).copied().unwrap_or(0)
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for Fence {
     fn into_behaviour(self) -> impl Behaviour {
         self
     }
 }
 
-impl Behaviour for Select {
+impl Behaviour for Fence {
     fn initialize(&mut self, world: &mut World) {
         for func in &mut self.funcs {
             func.initialize(world);
         }
     }
 
-    #[inline]
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|func| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "fenced_sequence",
+            "",
+            self.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for func in &mut self.funcs {
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.indices.clone(),
+            children: self.funcs.iter().map(|func| func.save_state()).collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.indices = state.entities.clone();
+        state.load_children(&mut self.funcs);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.indices.remove(&entity);
+        for func in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        format!("index: {}", self.current_index(entity))
+    }
+
     fn run(&mut self, entity: Entity, world: &mut World) -> Status {
-        if let Some(behaviour) = self.behaviour_mut(entity) {
-            match behaviour.run(entity, world) {
-                Status::Running => Status::Running,
-                Status::Failure => {
-                    self.increase(entity);
-                    Status::Running
-                }
-                Status::Success => {
-                    self.reset(entity);
-                    Status::Success
-                }
+        let index = self.current_index(entity);
+        let Some(behaviour) = self.funcs.get_mut(index) else {
+            self.indices.remove(&entity);
+            return Status::Success;
+        };
+
+        match behaviour.run(entity, world) {
+            Status::Running => Status::Running,
+            Status::Failure => {
+                self.indices.remove(&entity);
+                Status::Failure
+            }
+            Status::Success => {
+                self.indices.insert(entity, index + 1);
+                Status::Running
             }
-        } else {
-            self.reset(entity);
-            // we tried everything; no branch was successful
-            Status::Failure
         }
     }
 }
+
+/// Like [`repeat_sequence`][Compositor::repeat_sequence], but checks the condition after every full pass over the children.
+///
+/// **Succeeds** once the condition is true at the end of a pass. A failing node starts the pass over without checking the condition.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// #[derive(Component)]
+/// struct Alarmed;
+///
+/// # fn walk_to_next_waypoint(_: In<Entity>) -> Status { Status::Success }
+/// # fn look_around(_: In<Entity>) -> Status { Status::Success }
+/// let patrol = repeat_sequence_until((walk_to_next_waypoint, look_around), has_component::<Alarmed>());
+/// ```
+pub fn repeat_sequence_until<Marker, CMarker, B, C>(behaviours: B, condition: C) -> RepeatSequence
+where
+    B: Compositor<Marker>,
+    C: IntoSystem<Entity, bool, CMarker>,
+{
+    RepeatSequence {
+        stop: Some(Box::new(IntoSystem::into_system(condition))),
+        ..behaviours.repeat_sequence()
+    }
+}
+
+/// See [`CompositeInput::select`].
+pub struct Select {
+    funcs: Children,
+    indices: HashMap<Entity, usize>,
+    memory: bool,
+    added: AddedChildren,
+}
+
+impl Select {
+    /// A select over already boxed children, e.g. when building trees at runtime.
+    pub fn new(children: Vec<Box<dyn Behaviour>>) -> Self {
+        Self::from_children(children.into_iter().collect())
+    }
+
+    fn from_children(children: Children) -> Self {
+        Self {
+            funcs: children,
+            indices: HashMap::default(),
+            memory: false,
+            added: AddedChildren::default(),
+        }
+    }
+
+    /// Whether the children are stored inline, without a separate heap allocation. Only up to 4 children fit.
+    ///
+    /// Requires the `smallvec` feature.
+    #[cfg(feature = "smallvec")]
+    pub fn is_inline(&self) -> bool {
+        !self.funcs.spilled()
+    }
+
+    /// The number of children.
+    pub fn len(&self) -> usize {
+        self.funcs.len()
+    }
+
+    /// Whether there are no children.
+    pub fn is_empty(&self) -> bool {
+        self.funcs.is_empty()
+    }
+
+    /// The index of the child currently active for the entity.
+    pub fn current_index(&self, entity: Entity) -> usize {
+        self.indices.get(&entity).copied().unwrap_or(0)
+    }
+
+    /// Keeps the entity's current child when a child succeeds, instead of starting over from the first one.
+    /// The next time the select runs, it continues with the successful child.
+    pub fn with_memory(mut self) -> Self {
+        self.memory = true;
+        self
+    }
+
+    /// Whether this select [remembers][Select::with_memory] its position when a child succeeds.
+    pub fn has_memory(&self) -> bool {
+        self.memory
+    }
+
+    /// Turns this into a [`Sequence`] over the same children. Entities keep their current child.
+    pub fn into_sequence(self) -> Sequence {
+        Sequence {
+            funcs: self.funcs,
+            indices: self.indices,
+            memory: self.memory,
+            added: self.added,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn behaviour_mut(&mut self, entity: Entity) -> Option<&mut Box<dyn Behaviour>> {
+        let index = self.index(entity);
+        self.funcs.get_mut(index)
+    }
+
+    #[inline]
+    fn index(&mut self, entity: Entity) -> usize {
+        match self.indices.get(&entity) {
+            Some(index) => *index,
+            None => {
+                self.indices.insert(entity, 0);
+                0
+            }
+        }
+    }
+
+    /// No-op for entities we haven't seen yet, since they're implicitly at index 0.
+    #[inline]
+    fn reset(&mut self, entity: Entity) {
+        if let Some(index) = self.indices.get_mut(&entity) {
+            *index = 0;
+        }
+    }
+
+    #[inline]
+    pub(crate) fn increase(&mut self, entity: Entity) {
+        // entities we haven't seen yet start at 0, so advancing them moves them to the second child.
+        *self.indices.entry(entity).or_insert(0) += 1;
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for Select {
+    #[inline]
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for Select {
+    fn initialize(&mut self, world: &mut World) {
+        self.added.initialize_all(&mut self.funcs, world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|func| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "select",
+            format!("memory: {}", self.memory),
+            self.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for func in &mut self.funcs {
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.indices.clone(),
+            children: self.funcs.iter().map(|func| func.save_state()).collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.indices = state.entities.clone();
+        state.load_children(&mut self.funcs);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.indices.remove(&entity);
+        for func in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        format!("index: {}", self.current_index(entity))
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        // children added to a sequence before it was turned into a select.
+        self.added.initialize(&mut self.funcs, world);
+
+        // like with `Sequence`, a single branch is run as is.
+        if let [func] = &mut self.funcs[..] {
+            let status = func.run(entity, world);
+            if status == Status::Running || (status == Status::Success && self.memory) {
+                self.indices.insert(entity, 0);
+            } else {
+                self.indices.remove(&entity);
+            }
+            return status;
+        }
+
+        if let Some(behaviour) = self.behaviour_mut(entity) {
+            match behaviour.run(entity, world) {
+                Status::Running => Status::Running,
+                Status::Failure => {
+                    self.increase(entity);
+                    Status::Running
+                }
+                Status::Success => {
+                    if !self.memory {
+                        self.reset(entity);
+                    }
+                    Status::Success
+                }
+            }
+        } else {
+            self.reset(entity);
+            // we tried everything; no branch was successful
+            Status::Failure
+        }
+    }
+}
+
+/// What's saved of a [`Sequence`] or [`Select`]. Children can't be serialized, so only entity progress is kept.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedProgress {
+    indices: HashMap<Entity, usize>,
+    len: usize,
+    memory: bool,
+}
+
+macro_rules! impl_serde_progress {
+    ($compositor:ident) => {
+        /// Only the progress of each entity is serialized, not the children. Requires the `serde` feature.
+        ///
+        /// A deserialized compositor has no children, so they need to be [reattached][Self::reattach] from a freshly built one.
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $compositor {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                SavedProgress {
+                    indices: self.indices.clone(),
+                    len: self.funcs.len(),
+                    memory: self.memory,
+                }
+                .serialize(serializer)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $compositor {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let saved = SavedProgress::deserialize(deserializer)?;
+
+                let mut compositor = Self::new(Vec::new());
+                compositor.indices = saved.indices;
+                compositor.indices.retain(|_, index| *index < saved.len);
+                compositor.memory = saved.memory;
+                Ok(compositor)
+            }
+        }
+
+        impl $compositor {
+            /// Takes the children of `fresh`, keeping the progress of `self`. Meant for compositors that were deserialized without their children.
+            ///
+            /// Entities that were past the last child of `fresh` start over.
+            pub fn reattach(&mut self, fresh: Self) {
+                self.funcs = fresh.funcs;
+                self.added = fresh.added;
+                let len = self.funcs.len();
+                self.indices.retain(|_, index| *index < len);
+            }
+        }
+    };
+}
+
+impl_serde_progress!(Sequence);
+impl_serde_progress!(Select);
+
+/// When a [`DynamicParallel`] finishes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParallelPolicy {
+    /// **Succeeds** once all children succeeded. **Fails** as soon as any child fails.
+    RequireAll,
+    /// **Succeeds** as soon as any child succeeds. **Fails** once all children failed.
+    RequireOne,
+}
+
+/// Runs children side by side (within the same tick). See [`Compositor::parallel`].
+pub struct DynamicParallel {
+    funcs: Vec<Box<dyn Behaviour>>,
+    policy: ParallelPolicy,
+    // bitmask of children that finished without deciding the outcome.
+    finished: HashMap<Entity, usize>,
+}
+
+impl DynamicParallel {
+    /// Runs `children` according to `policy`.
+    ///
+    /// # Panics
+    /// If there are more than [`usize::BITS`] children.
+    pub fn new(children: Vec<Box<dyn Behaviour>>, policy: ParallelPolicy) -> Self {
+        assert!(
+            children.len() <= usize::BITS as usize,
+            "parallel compositors support at most {} children",
+            usize::BITS
+        );
+
+        Self {
+            funcs: children,
+            policy,
+            finished: HashMap::default(),
+        }
+    }
+
+    /// The policy deciding when this finishes.
+    pub fn policy(&self) -> ParallelPolicy {
+        self.policy
+    }
+}
+
+/// Runs all children until they all succeed. See [`ParallelPolicy::RequireAll`].
+pub fn parallel_all(children: Vec<Box<dyn Behaviour>>) -> DynamicParallel {
+    DynamicParallel::new(children, ParallelPolicy::RequireAll)
+}
+
+/// Runs all children until one succeeds. See [`ParallelPolicy::RequireOne`].
+pub fn parallel_any(children: Vec<Box<dyn Behaviour>>) -> DynamicParallel {
+    DynamicParallel::new(children, ParallelPolicy::RequireOne)
+}
+
+impl IntoBehaviour<SelfMarker> for DynamicParallel {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for DynamicParallel {
+    fn initialize(&mut self, world: &mut World) {
+        for func in &mut self.funcs {
+            func.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|func| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "parallel",
+            format!("policy: {:?}", self.policy),
+            self.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for func in &mut self.funcs {
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.finished.clone(),
+            children: self.funcs.iter().map(|func| func.save_state()).collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.finished = state.entities.clone();
+        state.load_children(&mut self.funcs);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.finished.remove(&entity);
+        for func in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        // the status that ends the parallel early, and the one that ends it once every child reached it.
+        let (decisive, exhaustive) = match self.policy {
+            ParallelPolicy::RequireAll => (Status::Failure, Status::Success),
+            ParallelPolicy::RequireOne => (Status::Success, Status::Failure),
+        };
+
+        let mut finished = self.finished.get(&entity).copied().unwrap_or(0);
+
+        for (index, func) in self.funcs.iter_mut().enumerate() {
+            if finished & (1 << index) != 0 {
+                continue;
+            }
+
+            match func.run(entity, world) {
+                Status::Running => {}
+                status if status == decisive => {
+                    // the outcome is decided, so children still running start over next time.
+                    self.finished.remove(&entity);
+                    for func in &mut self.funcs {
+                        func.reset_entity(entity);
+                    }
+                    return decisive;
+                }
+                _ => finished |= 1 << index,
+            }
+        }
+
+        if finished.count_ones() as usize == self.funcs.len() {
+            self.finished.remove(&entity);
+            exhaustive
+        } else {
+            self.finished.insert(entity, finished);
+            Status::Running
+        }
+    }
+}
+
+/// Escalates from cheap to expensive fallbacks, and backs off to the cheap ones again after a cooldown. See [`Compositor::backoff_select`].
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// # fn pathfind_local(_: In<Entity>) -> Status { Status::Success }
+/// # fn pathfind_global(_: In<Entity>) -> Status { Status::Success }
+/// // fall back to the expensive search after 3 failed local ones, and try local again 5 seconds later.
+/// let pathfind = (pathfind_local, pathfind_global).backoff_select(3, Duration::from_secs(5));
+/// ```
+/// The cooldown is measured with the [`Time`] resource. Without one, nodes are never backed off from.
+pub struct BackoffSelect {
+    funcs: Vec<Box<dyn Behaviour>>,
+    cooldown: Duration,
+    escalation_threshold: usize,
+    state: HashMap<Entity, BackoffState>,
+}
+
+#[derive(Clone, Copy, Default)]
+struct BackoffState {
+    level: usize,
+    failures: usize,
+    // how long the entity has been at `level`, and `Time::elapsed` when that was last updated.
+    // Only the first is saved, since the time of a previous session means nothing after restoring.
+    waited: Duration,
+    last_run: Option<Duration>,
+}
+
+impl BackoffState {
+    // the level, failures and milliseconds waited as the digits of a single number, with `levels`, `threshold + 1` and `cooldown + 1` as their bases.
+    // Failures beyond the threshold and waiting beyond the cooldown don't change anything, so they're capped. `None` if the number doesn't fit into a `usize`.
+    fn to_state(self, levels: usize, threshold: usize, cooldown: Duration) -> Option<usize> {
+        let waited = usize::try_from(self.waited.min(cooldown).as_millis()).ok()?;
+        waited
+            .checked_mul(threshold + 1)?
+            .checked_add(self.failures.min(threshold))?
+            .checked_mul(levels)?
+            .checked_add(self.level)
+    }
+
+    // `None` for numbers that don't belong to a select with these settings.
+    fn from_state(
+        state: usize,
+        levels: usize,
+        threshold: usize,
+        cooldown: Duration,
+    ) -> Option<Self> {
+        let level = state.checked_rem(levels)?;
+        let rest = state / levels;
+        let failures = rest % (threshold + 1);
+        let waited = Duration::from_millis((rest / (threshold + 1)) as u64);
+
+        (waited <= cooldown).then_some(Self {
+            level,
+            failures,
+            waited,
+            last_run: None,
+        })
+    }
+}
+
+impl BackoffSelect {
+    /// Escalates between already boxed children, ordered from cheapest to most expensive.
+    pub fn new(
+        children: Vec<Box<dyn Behaviour>>,
+        escalation_threshold: usize,
+        cooldown: Duration,
+    ) -> Self {
+        Self {
+            funcs: children,
+            cooldown,
+            escalation_threshold,
+            state: HashMap::default(),
+        }
+    }
+
+    /// The index of the child the entity is currently escalated to.
+    pub fn level(&self, entity: Entity) -> usize {
+        self.state.get(&entity).map_or(0, |state| state.level)
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for BackoffSelect {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for BackoffSelect {
+    fn initialize(&mut self, world: &mut World) {
+        for func in &mut self.funcs {
+            func.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|func| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "backoff_select",
+            format!(
+                "escalation_threshold: {}, cooldown: {:?}",
+                self.escalation_threshold, self.cooldown
+            ),
+            self.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for func in &mut self.funcs {
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self
+                .state
+                .iter()
+                .filter_map(|(entity, state)| {
+                    let state = state.to_state(
+                        self.funcs.len(),
+                        self.escalation_threshold,
+                        self.cooldown,
+                    )?;
+                    Some((*entity, state))
+                })
+                .collect(),
+            children: self.funcs.iter().map(|func| func.save_state()).collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        // entries that don't fit this select are dropped, so those entities start over.
+        self.state = state
+            .entities
+            .iter()
+            .filter_map(|(entity, state)| {
+                let state = BackoffState::from_state(
+                    *state,
+                    self.funcs.len(),
+                    self.escalation_threshold,
+                    self.cooldown,
+                )?;
+                Some((*entity, state))
+            })
+            .collect();
+        state.load_children(&mut self.funcs);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.state.remove(&entity);
+        for func in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        format!("level: {}", self.level(entity))
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        if self.funcs.is_empty() {
+            return Status::Failure;
+        }
+
+        let now = world.get_resource::<Time>().map(Time::elapsed);
+        let mut state = self.state.get(&entity).copied().unwrap_or_default();
+
+        if let Some(now) = now.filter(|_| state.level > 0) {
+            // restored entities pick up where they were saved, whatever the time is now.
+            state.waited += state
+                .last_run
+                .map_or(Duration::ZERO, |last_run| now.saturating_sub(last_run));
+            state.last_run = Some(now);
+
+            if state.waited >= self.cooldown {
+                self.funcs[state.level].reset_entity(entity);
+                state = BackoffState::default();
+            }
+        }
+
+        let status = self.funcs[state.level].run(entity, world);
+
+        match status {
+            Status::Running => {}
+            Status::Success => {
+                state.level = 0;
+                state.failures = 0;
+            }
+            Status::Failure => {
+                state.failures += 1;
+                if state.failures >= self.escalation_threshold && state.level + 1 < self.funcs.len()
+                {
+                    state.level += 1;
+                    state.failures = 0;
+                    state.waited = Duration::ZERO;
+                    state.last_run = now;
+                }
+            }
+        }
+
+        self.state.insert(entity, state);
+        status
+    }
+}
+
+/// See [`Compositor::race_first_success`].
+pub struct RaceFirstSuccess(DynamicParallel);
+
+impl RaceFirstSuccess {
+    /// Races already boxed children.
+    ///
+    /// # Panics
+    /// If there are more than [`usize::BITS`] children.
+    pub fn new(children: Vec<Box<dyn Behaviour>>) -> Self {
+        Self(DynamicParallel::new(children, ParallelPolicy::RequireOne))
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for RaceFirstSuccess {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for RaceFirstSuccess {
+    fn initialize(&mut self, world: &mut World) {
+        self.0.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.0.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.0.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "race_first_success",
+            String::new(),
+            self.0.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.0.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        self.0.save_state()
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.0.load_state(state);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.0.reset_entity(entity);
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        self.0.run(entity, world)
+    }
+}
+
+/// See [`Compositor::race_last_success`].
+pub struct RaceLastSuccess {
+    funcs: Vec<Box<dyn Behaviour>>,
+    // bitmask of finished children. The highest bit is set once any of them succeeded.
+    finished: HashMap<Entity, usize>,
+}
+
+impl RaceLastSuccess {
+    const SUCCEEDED: usize = 1 << (usize::BITS - 1);
+
+    /// Races already boxed children.
+    ///
+    /// # Panics
+    /// If there are more than `usize::BITS - 1` children.
+    pub fn new(children: Vec<Box<dyn Behaviour>>) -> Self {
+        assert!(
+            children.len() < usize::BITS as usize,
+            "race compositors support at most {} children",
+            usize::BITS - 1
+        );
+
+        Self {
+            funcs: children,
+            finished: HashMap::default(),
+        }
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for RaceLastSuccess {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for RaceLastSuccess {
+    fn initialize(&mut self, world: &mut World) {
+        for func in &mut self.funcs {
+            func.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|func| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "race_last_success",
+            String::new(),
+            self.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for func in &mut self.funcs {
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.finished.clone(),
+            children: self.funcs.iter().map(|func| func.save_state()).collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.finished = state.entities.clone();
+        state.load_children(&mut self.funcs);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.finished.remove(&entity);
+        for func in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let mut finished = self.finished.get(&entity).copied().unwrap_or(0);
+
+        for (index, func) in self.funcs.iter_mut().enumerate() {
+            if finished & (1 << index) != 0 {
+                continue;
+            }
+
+            match func.run(entity, world) {
+                Status::Running => {}
+                Status::Success => finished |= 1 << index | Self::SUCCEEDED,
+                Status::Failure => finished |= 1 << index,
+            }
+        }
+
+        if (finished & !Self::SUCCEEDED).count_ones() as usize == self.funcs.len() {
+            self.finished.remove(&entity);
+            if finished & Self::SUCCEEDED != 0 {
+                Status::Success
+            } else {
+                Status::Failure
+            }
+        } else {
+            self.finished.insert(entity, finished);
+            Status::Running
+        }
+    }
+}
+
+/// See [`Compositor::all_or_nothing`].
+pub struct AllOrNothing {
+    funcs: Vec<Box<dyn Behaviour>>,
+    // bitmask of finished children. The highest bit is set once any of them failed.
+    finished: HashMap<Entity, usize>,
+}
+
+impl AllOrNothing {
+    const FAILED: usize = 1 << (usize::BITS - 1);
+
+    /// Runs already boxed children.
+    ///
+    /// # Panics
+    /// If there are more than `usize::BITS - 1` children.
+    pub fn new(children: Vec<Box<dyn Behaviour>>) -> Self {
+        assert!(
+            children.len() < usize::BITS as usize,
+            "all_or_nothing supports at most {} children",
+            usize::BITS - 1
+        );
+
+        Self {
+            funcs: children,
+            finished: HashMap::default(),
+        }
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for AllOrNothing {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for AllOrNothing {
+    fn initialize(&mut self, world: &mut World) {
+        for func in &mut self.funcs {
+            func.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|func| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "all_or_nothing",
+            String::new(),
+            self.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for func in &mut self.funcs {
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.finished.clone(),
+            children: self.funcs.iter().map(|func| func.save_state()).collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.finished = state.entities.clone();
+        state.load_children(&mut self.funcs);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.finished.remove(&entity);
+        for func in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let mut finished = self.finished.get(&entity).copied().unwrap_or(0);
+
+        for (index, func) in self.funcs.iter_mut().enumerate() {
+            if finished & (1 << index) != 0 {
+                continue;
+            }
+
+            match func.run(entity, world) {
+                Status::Running => {}
+                Status::Success => finished |= 1 << index,
+                Status::Failure => finished |= 1 << index | Self::FAILED,
+            }
+        }
+
+        if (finished & !Self::FAILED).count_ones() as usize == self.funcs.len() {
+            self.finished.remove(&entity);
+            if finished & Self::FAILED != 0 {
+                Status::Failure
+            } else {
+                Status::Success
+            }
+        } else {
+            self.finished.insert(entity, finished);
+            Status::Running
+        }
+    }
+}
+
+/// See [`Compositor::interleave`].
+pub struct Interleave {
+    funcs: Vec<Box<dyn Behaviour>>,
+    step: usize,
+    // bitmask of children that succeeded.
+    finished: HashMap<Entity, usize>,
+}
+
+impl Interleave {
+    /// Runs each of `children` up to `step` times per tick.
+    ///
+    /// # Panics
+    /// If there are more than [`usize::BITS`] children, or `step` is 0.
+    pub fn new(children: Vec<Box<dyn Behaviour>>, step: usize) -> Self {
+        assert!(
+            children.len() <= usize::BITS as usize,
+            "interleave compositors support at most {} children",
+            usize::BITS
+        );
+        assert!(step > 0, "interleave step must be at least 1");
+
+        Self {
+            funcs: children,
+            step,
+            finished: HashMap::default(),
+        }
+    }
+
+    /// How many times each child runs per tick, at most.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for Interleave {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for Interleave {
+    fn initialize(&mut self, world: &mut World) {
+        for func in &mut self.funcs {
+            func.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|func| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|func| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "interleave",
+            format!("step: {}", self.step),
+            self.funcs.iter().map(|func| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for func in &mut self.funcs {
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.finished.clone(),
+            children: self.funcs.iter().map(|func| func.save_state()).collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.finished = state.entities.clone();
+        state.load_children(&mut self.funcs);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.finished.remove(&entity);
+        for func in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let mut finished = self.finished.get(&entity).copied().unwrap_or(0);
+
+        for (index, func) in self.funcs.iter_mut().enumerate() {
+            if finished & (1 << index) != 0 {
+                continue;
+            }
+
+            for _ in 0..self.step {
+                match func.run(entity, world) {
+                    Status::Running => {}
+                    Status::Success => {
+                        finished |= 1 << index;
+                        break;
+                    }
+                    Status::Failure => {
+                        // children still running start over next time.
+                        self.finished.remove(&entity);
+                        for func in &mut self.funcs {
+                            func.reset_entity(entity);
+                        }
+                        return Status::Failure;
+                    }
+                }
+            }
+        }
+
+        if finished.count_ones() as usize == self.funcs.len() {
+            self.finished.remove(&entity);
+            Status::Success
+        } else {
+            self.finished.insert(entity, finished);
+            Status::Running
+        }
+    }
+}
+
+/// A weight for a [`WeightedSelect`] branch, evaluated every time a new branch is picked.
+///
+/// Implemented for `f32` (a static weight). See [`weight_by_component`] for weights that change at runtime.
+pub trait DynamicWeight: Send + Sync + 'static {
+    /// The weight of the branch for the given entity. Non-positive weights are never picked.
+    fn get_weight(&self, entity: Entity, world: &World) -> f32;
+}
+
+impl DynamicWeight for f32 {
+    #[inline]
+    fn get_weight(&self, _: Entity, _: &World) -> f32 {
+        *self
+    }
+}
+
+/// Uses a value extracted from the component `C` on the entity as a weight.
+///
+/// Entities without `C` get a weight of 0.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, compositor::weight_by_component};
+/// #[derive(Component)]
+/// struct Hunger(f32);
+///
+/// # fn eat(_: In<Entity>) -> Status { Status::Success }
+/// # fn wander(_: In<Entity>) -> Status { Status::Success }
+/// let behaviour = (
+///     (weight_by_component::<Hunger, _>(|hunger: &Hunger| hunger.0), eat),
+///     (1.0, wander),
+/// ).weighted_select();
+/// ```
+pub fn weight_by_component<C, F>(extractor: F) -> impl DynamicWeight
+where
+    C: Component,
+    F: Fn(&C) -> f32 + Send + Sync + 'static,
+{
+    ComponentWeight {
+        extractor,
+        _component: PhantomData::<fn() -> C>,
+    }
+}
+
+struct ComponentWeight<C, F> {
+    extractor: F,
+    _component: PhantomData<fn() -> C>,
+}
+
+impl<C, F> DynamicWeight for ComponentWeight<C, F>
+where
+    C: Component,
+    F: Fn(&C) -> f32 + Send + Sync + 'static,
+{
+    #[inline]
+    fn get_weight(&self, entity: Entity, world: &World) -> f32 {
+        world.get::<C>(entity).map_or(0., &self.extractor)
+    }
+}
+
+/// Helper trait for tuples of `(weight, behaviour)` pairs.
+trait WeightedBehaviourGroup<Marker> {
+    fn group(self) -> Vec<(Box<dyn DynamicWeight>, Box<dyn Behaviour>)>;
+}
+
+macro_rules! impl_weighted_behaviour_group {
+    ($(($name:ident,$marker:ident,$weight:ident)),*) => {
+        impl<$($marker: 'static, $name: IntoBehaviour<$marker>, $weight: DynamicWeight),*> WeightedBehaviourGroup<($($marker,)*)> for ($(($weight, $name),)*) {
+            fn group(self) -> Vec<(Box<dyn DynamicWeight>, Box<dyn Behaviour>)> {
+                #[allow(non_snake_case)]
+                let ($(($weight, $name),)*) = self;
+
+                vec![$((Box::new($weight) as Box<dyn DynamicWeight>, Box::new(IntoBehaviour::into_behaviour($name)) as Box<dyn Behaviour>)),*]
+            }
+        }
+    }
+}
+
+all_tuples!(impl_weighted_behaviour_group, 2, 15, B, M, W);
+
+/// Compositors for tuples of `(weight, behaviour)` pairs.
+pub trait WeightedCompositor<Marker> {
+    /// Randomly picks one branch, weighted by its [`DynamicWeight`], and runs it until it completes.
+    ///
+    /// **Succeeds or fails** depending on the picked branch. **Fails** if no branch has a positive weight.
+    fn weighted_select(self) -> WeightedSelect;
+
+    /// Runs the branches sequentially, like [`sequence`][Compositor::sequence], but skips each one with a chance of `1 - weight`.
+    ///
+    /// Whether a branch is skipped is decided once it's reached. Weights of 1 or more always run the branch, 0 or less always skip it.
+    ///
+    /// **Succeeds** if all branches that weren't skipped succeed. **Fails** if any of them fails.
+    fn weighted_sequence(self) -> WeightedSequence;
+}
+
+impl<Marker, T: WeightedBehaviourGroup<Marker>> WeightedCompositor<Marker> for T {
+    fn weighted_select(self) -> WeightedSelect {
+        WeightedSelect {
+            funcs: WeightedBehaviourGroup::group(self),
+            active: HashMap::default(),
+        }
+    }
+
+    fn weighted_sequence(self) -> WeightedSequence {
+        WeightedSequence {
+            funcs: WeightedBehaviourGroup::group(self),
+            rng: StdRng::from_entropy(),
+            indices: HashMap::default(),
+        }
+    }
+}
+
+/// See [`WeightedCompositor::weighted_select`].
+pub struct WeightedSelect {
+    funcs: Vec<(Box<dyn DynamicWeight>, Box<dyn Behaviour>)>,
+    active: HashMap<Entity, usize>,
+}
+
+impl WeightedSelect {
+    /// Picks a new branch for the entity, or `None` if no branch has a positive weight.
+    fn pick(&self, entity: Entity, world: &World) -> Option<usize> {
+        let weights = self
+            .funcs
+            .iter()
+            .map(|(weight, _)| weight.get_weight(entity, world).max(0.))
+            .collect::<Vec<_>>();
+
+        let total: f32 = weights.iter().sum();
+        if total <= 0. {
+            return None;
+        }
+
+        let mut roll = thread_rng().gen_range(0.0..total);
+        for (index, weight) in weights.iter().enumerate() {
+            if *weight > 0. && roll < *weight {
+                return Some(index);
+            }
+            roll -= weight;
+        }
+
+        // floating point imprecision; fall back to the last viable branch.
+        weights.iter().rposition(|weight| *weight > 0.)
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for WeightedSelect {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for WeightedSelect {
+    fn initialize(&mut self, world: &mut World) {
+        for (_, func) in &mut self.funcs {
+            func.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|(_, func)| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|(_, func)| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "weighted_select",
+            String::new(),
+            self.funcs.iter().map(|(_, func)| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for (_, func) in &mut self.funcs {
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.active.clone(),
+            children: self
+                .funcs
+                .iter()
+                .map(|(_, func)| func.save_state())
+                .collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        // branches that don't exist here are dropped, so those entities pick again.
+        self.active = state
+            .entities
+            .iter()
+            .filter(|(_, index)| **index < self.funcs.len())
+            .map(|(entity, index)| (*entity, *index))
+            .collect();
+        state.load_children(self.funcs.iter_mut().map(|(_, func)| func));
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.active.remove(&entity);
+        for (_, func) in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let index = match self.active.get(&entity) {
+            Some(index) => *index,
+            None => {
+                let Some(index) = self.pick(entity, world) else {
+                    return Status::Failure;
+                };
+                self.active.insert(entity, index);
+                index
+            }
+        };
+
+        let status = self.funcs[index].1.run(entity, world);
+        if status != Status::Running {
+            self.active.remove(&entity);
+        }
+
+        status
+    }
+}
+
+/// See [`WeightedCompositor::weighted_sequence`].
+pub struct WeightedSequence {
+    funcs: Vec<(Box<dyn DynamicWeight>, Box<dyn Behaviour>)>,
+    rng: StdRng,
+    // only holds branches that were already picked to run.
+    indices: HashMap<Entity, usize>,
+}
+
+impl WeightedSequence {
+    /// Seeds the random number generator deciding which branches are skipped, e.g. for reproducible tests.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// The first branch from `from` on that isn't skipped, or the number of branches if all of them are.
+    fn next_branch(&mut self, from: usize, entity: Entity, world: &World) -> usize {
+        (from..self.funcs.len())
+            .find(|index| {
+                let weight = self.funcs[*index].0.get_weight(entity, world);
+                self.rng.gen::<f32>() < weight
+            })
+            .unwrap_or(self.funcs.len())
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for WeightedSequence {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for WeightedSequence {
+    fn initialize(&mut self, world: &mut World) {
+        for (_, func) in &mut self.funcs {
+            func.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.funcs.iter().flat_map(|(_, func)| func.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.funcs.iter().map(|(_, func)| func.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "weighted_sequence",
+            String::new(),
+            self.funcs.iter().map(|(_, func)| func.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for (_, func) in &mut self.funcs {
+            func.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.indices.clone(),
+            children: self
+                .funcs
+                .iter()
+                .map(|(_, func)| func.save_state())
+                .collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.indices = state.entities.clone();
+        state.load_children(self.funcs.iter_mut().map(|(_, func)| func));
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.indices.remove(&entity);
+        for (_, func) in &mut self.funcs {
+            func.reset_entity(entity);
+        }
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        match self.indices.get(&entity) {
+            Some(index) => format!("index: {index}"),
+            None => String::new(),
+        }
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let index = match self.indices.get(&entity) {
+            Some(index) => *index,
+            None => self.next_branch(0, entity, world),
+        };
+
+        let Some((_, func)) = self.funcs.get_mut(index) else {
+            self.indices.remove(&entity);
+            return Status::Success;
+        };
+
+        match func.run(entity, world) {
+            Status::Running => {
+                self.indices.insert(entity, index);
+                Status::Running
+            }
+            Status::Failure => {
+                self.indices.remove(&entity);
+                Status::Failure
+            }
+            Status::Success => {
+                let next = self.next_branch(index + 1, entity, world);
+                if next < self.funcs.len() {
+                    self.indices.insert(entity, next);
+                    Status::Running
+                } else {
+                    self.indices.remove(&entity);
+                    Status::Success
+                }
+            }
+        }
+    }
+}
+
+/// A branch of a [`UtilitySelect`]: a behaviour that can also score how useful running it would be.
+pub trait ScoringBehaviour: Behaviour {
+    /// How useful running this branch would be for the entity right now. The highest scoring branch is picked.
+    fn score(&mut self, entity: Entity, world: &mut World) -> f32;
+}
+
+/// A behaviour scored by a system. See [`UtilityCompositor::utility_select`].
+struct Scored<S: System<In = Entity, Out = f32>, B: Behaviour> {
+    scorer: S,
+    func: B,
+}
+
+impl<S: System<In = Entity, Out = f32>, B: Behaviour> Behaviour for Scored<S, B> {
+    fn initialize(&mut self, world: &mut World) {
+        self.scorer.initialize(world);
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(
+            self.func
+                .dependencies()
+                .into_iter()
+                .chain(self.scorer.component_access().reads_and_writes()),
+        )
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        self.func.shape()
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        self.func.save_state()
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.func.load_state(state);
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.func.reset_entity(entity);
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        self.func.debug_state(entity)
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        self.func.run(entity, world)
+    }
+}
+
+impl<S: System<In = Entity, Out = f32>, B: Behaviour> ScoringBehaviour for Scored<S, B> {
+    fn score(&mut self, entity: Entity, world: &mut World) -> f32 {
+        self.scorer.run(entity, world)
+    }
+}
+
+/// Helper trait for tuples of `(scorer, behaviour)` pairs.
+trait ScoredBehaviourGroup<Marker> {
+    fn group(self) -> Vec<Box<dyn ScoringBehaviour>>;
+}
+
+macro_rules! impl_scored_behaviour_group {
+    ($(($name:ident,$marker:ident,$scorer:ident,$scorer_marker:ident)),*) => {
+        impl<$($marker: 'static, $name: IntoBehaviour<$marker>, $scorer_marker, $scorer: IntoSystem<Entity, f32, $scorer_marker>),*> ScoredBehaviourGroup<($(($marker, $scorer_marker),)*)> for ($(($scorer, $name),)*) {
+            fn group(self) -> Vec<Box<dyn ScoringBehaviour>> {
+                #[allow(non_snake_case)]
+                let ($(($scorer, $name),)*) = self;
+
+                vec![$(Box::new(Scored {
+                    scorer: IntoSystem::into_system($scorer),
+                    func: IntoBehaviour::into_behaviour($name),
+                }) as Box<dyn ScoringBehaviour>),*]
+            }
+        }
+    }
+}
+
+all_tuples!(impl_scored_behaviour_group, 2, 15, B, M, S, N);
+
+/// Compositors for tuples of `(scorer, behaviour)` pairs, where scorers are systems returning an `f32`.
+pub trait UtilityCompositor<Marker> {
+    /// Scores all branches, then runs the highest scoring one until it completes. Ties go to the earlier branch.
+    ///
+    /// **Succeeds or fails** depending on the picked branch.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// #[derive(Component)]
+    /// struct Hunger(f32);
+    ///
+    /// fn hunger(In(entity): In<Entity>, hunger: Query<&Hunger>) -> f32 {
+    ///     hunger.get(entity).map_or(0., |hunger| hunger.0)
+    /// }
+    ///
+    /// # fn eat(_: In<Entity>) -> Status { Status::Success }
+    /// # fn wander(_: In<Entity>) -> Status { Status::Success }
+    /// let tree = ((hunger, eat), (|_: In<Entity>| 0.5, wander)).utility_select();
+    /// ```
+    fn utility_select(self) -> UtilitySelect;
+
+    /// Runs all branches sequentially, like [`sequence`][Compositor::sequence], highest scoring first. Ties keep their order.
+    ///
+    /// Branches are scored whenever the sequence starts over for an entity, so the order stays the same until it finishes.
+    ///
+    /// **Succeeds** if all branches succeed. **Fails** if any of them fails.
+    fn sorted_sequence(self) -> SortedSequence;
+}
+
+impl<Marker, T: ScoredBehaviourGroup<Marker>> UtilityCompositor<Marker> for T {
+    fn utility_select(self) -> UtilitySelect {
+        UtilitySelect {
+            scorers: ScoredBehaviourGroup::group(self),
+            active: HashMap::default(),
+        }
+    }
+
+    fn sorted_sequence(self) -> SortedSequence {
+        SortedSequence {
+            scorers: ScoredBehaviourGroup::group(self),
+            orders: HashMap::default(),
+            indices: HashMap::default(),
+        }
+    }
+}
+
+/// See [`UtilityCompositor::utility_select`].
+pub struct UtilitySelect {
+    scorers: Vec<Box<dyn ScoringBehaviour>>,
+    active: HashMap<Entity, usize>,
+}
+
+impl UtilitySelect {
+    /// Picks the highest scoring branch for the entity.
+    fn pick(&mut self, entity: Entity, world: &mut World) -> Option<usize> {
+        let mut best: Option<(usize, f32)> = None;
+
+        for (index, scorer) in self.scorers.iter_mut().enumerate() {
+            let score = scorer.score(entity, world);
+            if best.map_or(true, |(_, best)| score > best) {
+                best = Some((index, score));
+            }
+        }
+
+        best.map(|(index, _)| index)
+    }
+
+    /// The branch the entity is currently running, if any.
+    pub fn active(&self, entity: Entity) -> Option<usize> {
+        self.active.get(&entity).copied()
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for UtilitySelect {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for UtilitySelect {
+    fn initialize(&mut self, world: &mut World) {
+        for scorer in &mut self.scorers {
+            scorer.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.scorers.iter().flat_map(|scorer| scorer.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.scorers.iter().map(|scorer| scorer.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "utility_select",
+            String::new(),
+            self.scorers.iter().map(|scorer| scorer.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for scorer in &mut self.scorers {
+            scorer.warm_up(entity, world);
+        }
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.active.clone(),
+            children: self
+                .scorers
+                .iter()
+                .map(|scorer| scorer.save_state())
+                .collect(),
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        // branches that don't exist here are dropped, so those entities pick again.
+        self.active = state
+            .entities
+            .iter()
+            .filter(|(_, index)| **index < self.scorers.len())
+            .map(|(entity, index)| (*entity, *index))
+            .collect();
+        for (scorer, state) in self.scorers.iter_mut().zip(&state.children) {
+            scorer.load_state(state);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.active.remove(&entity);
+        for scorer in &mut self.scorers {
+            scorer.reset_entity(entity);
+        }
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let index = match self.active.get(&entity) {
+            Some(index) => *index,
+            None => {
+                let Some(index) = self.pick(entity, world) else {
+                    return Status::Failure;
+                };
+                self.active.insert(entity, index);
+                index
+            }
+        };
+
+        let status = self.scorers[index].run(entity, world);
+        if status != Status::Running {
+            self.active.remove(&entity);
+        }
+
+        status
+    }
+}
+
+/// See [`UtilityCompositor::sorted_sequence`].
+pub struct SortedSequence {
+    scorers: Vec<Box<dyn ScoringBehaviour>>,
+    // branch indices, highest scoring first. Only held while the sequence runs for the entity.
+    orders: HashMap<Entity, Vec<usize>>,
+    indices: HashMap<Entity, usize>,
+}
+
+impl SortedSequence {
+    /// Scores all branches for the entity, highest scoring first.
+    fn sort(&mut self, entity: Entity, world: &mut World) -> Vec<usize> {
+        let scores = self
+            .scorers
+            .iter_mut()
+            .map(|scorer| scorer.score(entity, world))
+            .collect::<Vec<_>>();
+
+        let mut order = (0..self.scorers.len()).collect::<Vec<_>>();
+        order.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+        order
+    }
+
+    /// The order the entity currently runs the branches in, if it's running.
+    pub fn order(&self, entity: Entity) -> Option<&[usize]> {
+        self.orders.get(&entity).map(Vec::as_slice)
+    }
+
+    fn reset(&mut self, entity: Entity) {
+        self.orders.remove(&entity);
+        self.indices.remove(&entity);
+    }
+}
+
+impl IntoBehaviour<SelfMarker> for SortedSequence {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for SortedSequence {
+    fn initialize(&mut self, world: &mut World) {
+        for scorer in &mut self.scorers {
+            scorer.initialize(world);
+        }
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.scorers.iter().flat_map(|scorer| scorer.dependencies()))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        validate_compositor(self.scorers.iter().map(|scorer| scorer.validate(trees)))
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "sorted_sequence",
+            String::new(),
+            self.scorers.iter().map(|scorer| scorer.shape()).collect(),
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        for scorer in &mut self.scorers {
+            scorer.warm_up(entity, world);
+        }
+    }
+
+    // the scorers' states are followed by one more child holding the orders, with each of its children mapping entities to the branch at one position.
+    fn save_state(&self) -> BehaviourState {
+        let positions = (0..self.scorers.len())
+            .map(|position| BehaviourState {
+                entities: self
+                    .orders
+                    .iter()
+                    .map(|(entity, order)| (*entity, order[position]))
+                    .collect(),
+                children: Vec::new(),
+            })
+            .collect();
+
+        let mut children = self
+            .scorers
+            .iter()
+            .map(|scorer| scorer.save_state())
+            .collect::<Vec<_>>();
+        children.push(BehaviourState::from_children(positions));
+
+        BehaviourState {
+            entities: self.indices.clone(),
+            children,
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        for (scorer, state) in self.scorers.iter_mut().zip(&state.children) {
+            scorer.load_state(state);
+        }
+
+        let branches = self.scorers.len();
+        let positions = state
+            .children
+            .get(branches)
+            .map(|orders| orders.children.as_slice())
+            .unwrap_or_default();
+
+        // orders that don't fit this sequence's branches are dropped, so those entities start over.
+        self.orders = positions
+            .first()
+            .filter(|_| positions.len() == branches)
+            .into_iter()
+            .flat_map(|first| first.entities.keys())
+            .filter_map(|entity| {
+                let order = positions
+                    .iter()
+                    .map(|position| position.entities.get(entity).copied())
+                    .collect::<Option<Vec<_>>>()?;
+                order
+                    .iter()
+                    .all(|branch| *branch < branches)
+                    .then_some((*entity, order))
+            })
+            .collect();
+        self.indices = state
+            .entities
+            .iter()
+            .filter(|(entity, _)| self.orders.contains_key(*entity))
+            .map(|(entity, index)| (*entity, *index))
+            .collect();
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.reset(entity);
+        for scorer in &mut self.scorers {
+            scorer.reset_entity(entity);
+        }
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        match self.indices.get(&entity) {
+            Some(index) => format!("index: {index}"),
+            None => String::new(),
+        }
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        if !self.orders.contains_key(&entity) {
+            let order = self.sort(entity, world);
+            self.orders.insert(entity, order);
+        }
+
+        let index = self.indices.get(&entity).copied().unwrap_or(0);
+        let Some(branch) = self.orders[&entity].get(index).copied() else {
+            self.reset(entity);
+            return Status::Success;
+        };
+
+        match self.scorers[branch].run(entity, world) {
+            Status::Running => Status::Running,
+            Status::Failure => {
+                self.reset(entity);
+                Status::Failure
+            }
+            Status::Success if index + 1 < self.scorers.len() => {
+                self.indices.insert(entity, index + 1);
+                Status::Running
+            }
+            Status::Success => {
+                self.reset(entity);
+                Status::Success
+            }
+        }
+    }
+}
+
+/// Runs `then` if the condition is true, and skips it otherwise. An `if` without an `else`.
+///
+/// **Succeeds** right away if the condition is false. **Succeeds or fails** depending on `then` otherwise.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// #[derive(Component)]
+/// struct Wounded;
+///
+/// # fn patrol(_: In<Entity>) -> Status { Status::Success }
+/// # fn heal(_: In<Entity>) -> Status { Status::Success }
+/// let tree = (if_then(has_component::<Wounded>(), heal), patrol).sequence();
+/// ```
+/// Same as [`try_if`] and [`run_if`][crate::decorator::Decorator::run_if], spelled like a compositor so it reads in line with the rest of the tree.
+pub fn if_then<CMarker, C, Marker: 'static, B: IntoBehaviour<Marker>>(
+    condition: C,
+    then: B,
+) -> impl Behaviour + IntoBehaviour<SelfMarker>
+where
+    C: IntoSystem<Entity, bool, CMarker> + Clone,
+    <C as IntoSystem<Entity, bool, CMarker>>::System: Clone,
+{
+    try_if(condition, then)
+}
+
+/// Runs `on_enter` once, then `body` until it finishes, then `on_exit` until *it* finishes.
+///
+/// **Succeeds or fails** with the status of `body`, once `on_exit` is done. The statuses of `on_enter` and `on_exit` are ignored,
+/// so the cleanup in `on_exit` always runs, no matter how `body` ended:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// # fn draw_weapon(_: In<Entity>) -> Status { Status::Success }
+/// # fn attack(_: In<Entity>) -> Status { Status::Failure }
+/// # fn holster_weapon(_: In<Entity>) -> Status { Status::Success }
+/// let attack = scoped(draw_weapon, attack, holster_weapon);
+/// ```
+/// Entities that are [reset][Behaviour::reset_entity] while in the scope don't run `on_exit`.
+pub fn scoped<EnterMarker, BodyMarker, ExitMarker>(
+    on_enter: impl IntoBehaviour<EnterMarker>,
+    body: impl IntoBehaviour<BodyMarker>,
+    on_exit: impl IntoBehaviour<ExitMarker>,
+) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+    ScopedBehaviour {
+        on_enter: on_enter.into_behaviour(),
+        body: body.into_behaviour(),
+        on_exit: on_exit.into_behaviour(),
+        phases: HashMap::default(),
+    }
+}
+
+/// Where an entity is in a [`scoped`] behaviour. Entities that haven't started yet have no phase.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ScopePhase {
+    Running,
+    /// `on_exit` is running, and the scope will end with the contained status.
+    Exiting(Status),
+}
+
+impl ScopePhase {
+    // 0 while running, and one more than the status' `u8` encoding while exiting.
+    fn to_state(self) -> usize {
+        match self {
+            Self::Running => 0,
+            Self::Exiting(status) => 1 + usize::from(u8::from(status)),
+        }
+    }
+
+    fn from_state(state: usize) -> Option<Self> {
+        let Some(exiting) = state.checked_sub(1) else {
+            return Some(Self::Running);
+        };
+
+        let status = Status::try_from(u8::try_from(exiting).ok()?).ok()?;
+        (status != Status::Running).then_some(Self::Exiting(status))
+    }
+}
+
+/// See [`scoped`].
+struct ScopedBehaviour<OnEnter: Behaviour, Body: Behaviour, OnExit: Behaviour> {
+    on_enter: OnEnter,
+    body: Body,
+    on_exit: OnExit,
+    phases: HashMap<Entity, ScopePhase>,
+}
+
+impl<OnEnter: Behaviour, Body: Behaviour, OnExit: Behaviour> IntoBehaviour<SelfMarker>
+    for ScopedBehaviour<OnEnter, Body, OnExit>
+{
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<OnEnter: Behaviour, Body: Behaviour, OnExit: Behaviour> Behaviour
+    for ScopedBehaviour<OnEnter, Body, OnExit>
+{
+    fn initialize(&mut self, world: &mut World) {
+        self.on_enter.initialize(world);
+        self.body.initialize(world);
+        self.on_exit.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(
+            [
+                &self.on_enter.dependencies(),
+                &self.body.dependencies(),
+                &self.on_exit.dependencies(),
+            ]
+            .into_iter()
+            .flatten()
+            .copied(),
+        )
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.on_enter.validate(trees)?;
+        self.body.validate(trees)?;
+        self.on_exit.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "scoped",
+            String::new(),
+            vec![
+                self.on_enter.shape(),
+                self.body.shape(),
+                self.on_exit.shape(),
+            ],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.on_enter.warm_up(entity, world);
+        self.body.warm_up(entity, world);
+        self.on_exit.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self
+                .phases
+                .iter()
+                .map(|(entity, phase)| (*entity, phase.to_state()))
+                .collect(),
+            children: vec![
+                self.on_enter.save_state(),
+                self.body.save_state(),
+                self.on_exit.save_state(),
+            ],
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.phases = state
+            .entities
+            .iter()
+            .filter_map(|(entity, phase)| Some((*entity, ScopePhase::from_state(*phase)?)))
+            .collect();
+
+        if let [on_enter, body, on_exit] = state.children.as_slice() {
+            self.on_enter.load_state(on_enter);
+            self.body.load_state(body);
+            self.on_exit.load_state(on_exit);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.phases.remove(&entity);
+        self.on_enter.reset_entity(entity);
+        self.body.reset_entity(entity);
+        self.on_exit.reset_entity(entity);
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let phase = match self.phases.get(&entity) {
+            Some(phase) => *phase,
+            None => {
+                self.on_enter.run(entity, world);
+                ScopePhase::Running
+            }
+        };
+
+        let result = match phase {
+            ScopePhase::Running => match self.body.run(entity, world) {
+                Status::Running => {
+                    self.phases.insert(entity, ScopePhase::Running);
+                    return Status::Running;
+                }
+                result => result,
+            },
+            ScopePhase::Exiting(result) => result,
+        };
+
+        if self.on_exit.run(entity, world) == Status::Running {
+            self.phases.insert(entity, ScopePhase::Exiting(result));
+            return Status::Running;
+        }
+
+        self.phases.remove(&entity);
+        result
+    }
+}
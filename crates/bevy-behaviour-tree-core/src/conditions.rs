@@ -0,0 +1,161 @@
+use bevy::{
+    ecs::{
+        component::ComponentId,
+        system::{SystemParamFunction, SystemParamItem},
+    },
+    prelude::{
+        Children, Component, Entity, Event, EventReader, In, IntoSystem, Query, Res, Resource,
+        System, With, World,
+    },
+};
+
+use crate::{
+    behaviour::{IntoBehaviour, SelfMarker},
+    prelude::{Behaviour, Status},
+};
+
+/// Whether the entity has the component `C`.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// #[derive(Component)]
+/// struct Target;
+///
+/// # fn walk_to_target(_: In<Entity>) -> Status { Status::Success }
+/// let walk = walk_to_target.retry_while(has_component::<Target>());
+/// ```
+pub fn has_component<C: Component>() -> impl FnMut(In<Entity>, Query<(), With<C>>) -> bool + Clone {
+    move |In(entity): In<Entity>, query: Query<(), With<C>>| query.contains(entity)
+}
+
+/// Whether the resource `R` exists.
+///
+/// Not part of the prelude, since it would clash with bevy's own [`resource_exists`][bevy::ecs::schedule::common_conditions::resource_exists].
+pub fn resource_exists<R: Resource>() -> impl FnMut(In<Entity>, Option<Res<R>>) -> bool + Clone {
+    move |_: In<Entity>, resource: Option<Res<R>>| resource.is_some()
+}
+
+/// Whether any `E` events have been sent recently.
+///
+/// Doesn't consume the events, so every entity checking this condition sees the same result in a given tick.
+pub fn event_received<E: Event>() -> impl FnMut(In<Entity>, EventReader<E>) -> bool + Clone {
+    move |_: In<Entity>, events: EventReader<E>| !events.is_empty()
+}
+
+/// Whether the entity has children, and all of them have the component `C`.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// #[derive(Component)]
+/// struct InPosition;
+///
+/// # fn charge(_: In<Entity>) -> Status { Status::Success }
+/// let charge = (children_all_have::<InPosition>(), charge).sequence();
+/// ```
+pub fn children_all_have<C: Component>(
+) -> impl FnMut(In<Entity>, Query<&Children>, Query<(), With<C>>) -> bool + Clone {
+    move |In(entity): In<Entity>, children: Query<&Children>, query: Query<(), With<C>>| {
+        let Ok(children) = children.get(entity) else {
+            return false;
+        };
+
+        !children.is_empty() && children.iter().all(|child| query.contains(*child))
+    }
+}
+
+/// Whether any of the entity's children have the component `C`.
+pub fn children_any_have<C: Component>(
+) -> impl FnMut(In<Entity>, Query<&Children>, Query<(), With<C>>) -> bool + Clone {
+    move |In(entity): In<Entity>, children: Query<&Children>, query: Query<(), With<C>>| {
+        children
+            .get(entity)
+            .is_ok_and(|children| children.iter().any(|child| query.contains(*child)))
+    }
+}
+
+/// A condition that looks at the whole world instead of the entity, like "are there any enemies left?".
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// # fn celebrate(_: In<Entity>) -> Status { Status::Success }
+/// let no_enemies_left = world_condition(|world: &World| {
+///     world.iter_entities().all(|entity| !entity.contains::<Enemy>())
+/// });
+/// let celebrate = when(no_enemies_left, celebrate);
+/// ```
+/// Decorators that only take systems, like [`run_if`][crate::decorator::Decorator::run_if], need it to be [converted][IntoSystem::into_system] first.
+pub fn world_condition<F>(condition: F) -> WorldCondition<F>
+where
+    F: Fn(&World) -> bool + Clone + Send + Sync + 'static,
+{
+    WorldCondition(condition)
+}
+
+/// See [`world_condition`].
+#[derive(Clone)]
+pub struct WorldCondition<F>(F);
+
+impl<F> SystemParamFunction<()> for WorldCondition<F>
+where
+    F: Fn(&World) -> bool + Send + Sync + 'static,
+{
+    type In = Entity;
+    type Out = bool;
+    type Param = &'static World;
+
+    fn run(&mut self, _: Entity, world: SystemParamItem<Self::Param>) -> bool {
+        (self.0)(world)
+    }
+}
+
+/// Uses a condition as a leaf. **Succeeds** if the condition is true, **fails** if it's false.
+///
+/// Conditions are behaviours already, so this is mostly useful for giving them a concrete behaviour type,
+/// e.g. to [name][Behaviour::with_name] them:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// #[derive(Component)]
+/// struct Target;
+///
+/// let has_target = predicate(has_component::<Target>()).with_name("has_target");
+/// ```
+pub fn predicate<Marker, C>(condition: C) -> impl Behaviour + IntoBehaviour<SelfMarker>
+where
+    C: IntoSystem<Entity, bool, Marker>,
+{
+    Predicate(IntoSystem::into_system(condition))
+}
+
+/// See [`predicate`].
+struct Predicate<C: System<In = Entity, Out = bool>>(C);
+
+impl<C: System<In = Entity, Out = bool>> IntoBehaviour<SelfMarker> for Predicate<C> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<C: System<In = Entity, Out = bool>> Behaviour for Predicate<C> {
+    fn initialize(&mut self, world: &mut World) {
+        self.0.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.0.component_access().reads_and_writes().collect()
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let result = self.0.run(entity, world);
+        self.0.apply_deferred(world);
+
+        result.into()
+    }
+}
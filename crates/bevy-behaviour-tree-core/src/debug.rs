@@ -0,0 +1,252 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    ecs::{component::Tick, query::ReadOnlyWorldQuery},
+    log::info,
+    prelude::{App, Component, Entity, Input, KeyCode, Plugin, Res, ResMut, Resource, World},
+    utils::Instant,
+};
+
+use crate::{
+    behaviour::Status,
+    diagnostics::{record_timings, BehaviourTreeDiagnostics},
+    plugin::{
+        finish_tick_once, queued_entities, tick_queued, BehaviourId, BehaviourTrees,
+        EntityBehaviourTree,
+    },
+};
+
+/// Steps through trees one entity at a time. Added by [`BehaviourTreePlugin::with_entity_stepper`][crate::plugin::BehaviourTreePlugin::with_entity_stepper].
+///
+/// While this resource exists, trees aren't ticked every frame. Instead, each time [`should_advance`][Self::should_advance] is set,
+/// a single entity's tree is ticked and the resulting status is logged, e.g.:
+///
+/// ```text
+/// INFO bevy_behaviour_tree_core::debug: step 2: 3v0 ran tree 0 (bevy_behaviour_tree_core::compositor::Sequence) -> Running [index: 1]
+/// ```
+///
+/// Each step runs the entity's whole tree, like a regular tick would; behaviours can't be paused halfway through a run, so there's no stepping through single nodes.
+/// The part in brackets is the tree's [debug state][crate::behaviour::Behaviour::debug_state], if it has any, which shows where inside the tree the entity is.
+/// [`EntityBehaviourTree`]s are stepped through as well, after the shared trees, and show up as `ran its entity tree`.
+///
+/// Pressing [`key`][Self::key] sets the flag, but it can also be set manually, e.g. from a debug UI:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, debug::EntityStepper};
+/// let mut app = App::new();
+/// app.add_plugins(BehaviourTreePlugin::default().with_entity_stepper());
+///
+/// // advance with Enter instead of Space.
+/// app.world.resource_mut::<EntityStepper>().key = KeyCode::Return;
+///
+/// // or advance by one step right away.
+/// app.world.resource_mut::<EntityStepper>().should_advance = true;
+/// app.update();
+/// ```
+///
+/// Entities queued up for a round are ticked in the same order as without the stepper.
+/// A new round starts once all of them have been processed.
+#[derive(Resource)]
+pub struct EntityStepper {
+    /// Whether to tick the next entity. Reset after every step.
+    pub should_advance: bool,
+    /// The key that advances the stepper. Defaults to [`KeyCode::Space`].
+    pub key: KeyCode,
+    processed: usize,
+    // reversed, so the next entity can be popped off the end.
+    pending: Vec<(Entity, BehaviourId)>,
+}
+
+impl EntityStepper {
+    /// How many entities have been processed in the current round.
+    pub fn processed(&self) -> usize {
+        self.processed
+    }
+
+    /// How many entities are left in the current round.
+    pub fn remaining(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl Default for EntityStepper {
+    fn default() -> Self {
+        Self {
+            should_advance: false,
+            key: KeyCode::Space,
+            processed: 0,
+            pending: Vec::new(),
+        }
+    }
+}
+
+pub(crate) fn advance_on_key(
+    input: Option<Res<Input<KeyCode>>>,
+    mut stepper: ResMut<EntityStepper>,
+) {
+    let Some(input) = input else {
+        return;
+    };
+
+    if input.just_pressed(stepper.key) {
+        stepper.should_advance = true;
+    }
+}
+
+/// Runs in place of the regular tick while a [`EntityStepper`] exists.
+pub(crate) fn step<Filter: ReadOnlyWorldQuery + 'static>(world: &mut World) {
+    let mut stepper = world.resource_mut::<EntityStepper>();
+
+    if !stepper.should_advance {
+        return;
+    }
+
+    stepper.should_advance = false;
+
+    let new_round = stepper.pending.is_empty();
+
+    if new_round {
+        let mut queued = queued_entities::<Filter>(world);
+        world
+            .resource_mut::<BehaviourTrees>()
+            .count_entities(&queued);
+        queued.reverse();
+
+        let mut stepper = world.resource_mut::<EntityStepper>();
+        stepper.pending = queued;
+        stepper.processed = 0;
+    }
+
+    let mut pending = std::mem::take(&mut world.resource_mut::<EntityStepper>().pending);
+
+    // entities may have been despawned since the round started.
+    let next = std::iter::from_fn(|| pending.pop())
+        .find(|(entity, _)| world.get_entity(*entity).is_some());
+
+    world.resource_mut::<EntityStepper>().pending = pending;
+
+    let Some((entity, id)) = next else {
+        return;
+    };
+
+    let start = world
+        .contains_resource::<BehaviourTreeDiagnostics>()
+        .then(Instant::now);
+    let status = tick_queued(world, entity, id);
+    if let Some(start) = start {
+        record_timings(world, [(id, start.elapsed())].into_iter().collect());
+    }
+
+    let Some(status) = status else {
+        return;
+    };
+
+    world
+        .resource_mut::<BehaviourTrees>()
+        .finish_run(id, entity, status);
+    finish_tick_once(world, entity, id, status);
+    record_status(world, entity, id, status);
+
+    let (tree, type_name, state) = match id.is_null() {
+        true => {
+            let behaviour = world
+                .get::<EntityBehaviourTree>(entity)
+                .and_then(EntityBehaviourTree::behaviour);
+            (
+                "its entity tree".to_owned(),
+                behaviour.map(|behaviour| behaviour.type_name()),
+                behaviour.map(|behaviour| behaviour.debug_state(entity)),
+            )
+        }
+        false => {
+            let trees = world.resource::<BehaviourTrees>();
+            (
+                format!("tree {}", id.index),
+                trees.type_name(id),
+                trees.debug_state(id, entity),
+            )
+        }
+    };
+    let state = match state {
+        Some(state) if !state.is_empty() => format!(" [{state}]"),
+        _ => String::new(),
+    };
+
+    let mut stepper = world.resource_mut::<EntityStepper>();
+    stepper.processed += 1;
+
+    info!(
+        "step {}: {:?} ran {} ({}) -> {:?}{}",
+        stepper.processed,
+        entity,
+        tree,
+        type_name.unwrap_or("unknown"),
+        status,
+        state,
+    );
+}
+
+/// Keeps the last few statuses of every ticked entity in a [`BehaviourStatusHistory`] component,
+/// e.g. to find out what an entity was doing right before something went wrong.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, debug::BehaviourHistoryPlugin};
+/// App::new().add_plugins((BehaviourTreePlugin::default(), BehaviourHistoryPlugin::new(10)));
+/// ```
+/// Works with the [`EntityStepper`] as well.
+pub struct BehaviourHistoryPlugin {
+    capacity: usize,
+}
+
+impl BehaviourHistoryPlugin {
+    /// Keeps up to `capacity` statuses per entity.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity }
+    }
+}
+
+impl Plugin for BehaviourHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HistoryCapacity(self.capacity));
+    }
+}
+
+/// Its presence enables recording in `run_ticks`.
+#[derive(Resource)]
+struct HistoryCapacity(usize);
+
+/// The most recent statuses of an entity's trees, oldest first, with the [change tick][World::read_change_tick] they were returned at.
+///
+/// Added to entities the first time they're ticked while the [`BehaviourHistoryPlugin`] is active.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BehaviourStatusHistory(pub VecDeque<(BehaviourId, Status, Tick)>);
+
+/// Adds the status to the entity's history, dropping the oldest one if it's full.
+pub(crate) fn record_status(world: &mut World, entity: Entity, id: BehaviourId, status: Status) {
+    let Some(capacity) = world
+        .get_resource::<HistoryCapacity>()
+        .map(|capacity| capacity.0)
+    else {
+        return;
+    };
+
+    let tick = world.read_change_tick();
+
+    // the tree may have despawned the entity.
+    let Some(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+
+    if !entity.contains::<BehaviourStatusHistory>() {
+        entity.insert(BehaviourStatusHistory(VecDeque::with_capacity(capacity)));
+    }
+
+    let mut history = entity.get_mut::<BehaviourStatusHistory>().unwrap();
+    if history.0.len() >= capacity {
+        history.0.pop_front();
+    }
+    if capacity > 0 {
+        history.0.push_back((id, status, tick));
+    }
+}
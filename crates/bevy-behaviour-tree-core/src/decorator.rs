@@ -1,17 +1,25 @@
+use std::{marker::PhantomData, time::Duration};
+
 use bevy::{
-    prelude::{Entity, IntoSystem, System, World},
+    ecs::component::ComponentId,
+    log::warn,
+    prelude::{Component, Entity, IntoSystem, System, Time, World},
     utils::HashMap,
 };
 
 use crate::{
-    behaviour::{IntoBehaviour, SelfMarker},
+    behaviour::{
+        union_dependencies, BehaviourRef, BehaviourShape, BehaviourState, IntoBehaviour,
+        SelfMarker, ValidationError,
+    },
+    plugin::BehaviourTrees,
     prelude::{Behaviour, Status},
     TodoBehaviour,
 };
 
 // TODO: we can simplify Decorators massively by having a `decorate` function that accepts an IntoBehaviour, then reimplementing everything on top of that.
 // could look something like
-// 
+//
 // struct DecoratorBehaviour<D: Decoratable, B: Behaviour> {
 //     source: D,
 //     decorator: B,
@@ -27,7 +35,21 @@ use crate::{
 
 /// Types that can be used with the built-in decorator functions.
 /// - [`Behaviour`]
-/// - Nothing else lol
+/// - Anything else that's [`IntoBehaviour`], like systems taking `In<Entity>` and returning a [`Status`]
+///
+/// Like any extension trait, it has to be in scope for its methods to be available:
+/// ```compile_fail,E0599
+/// # use bevy::prelude::*;
+/// # fn walk(_: In<Entity>) -> bevy_behaviour_tree_core::prelude::Status { unimplemented!() }
+/// let walk = walk.invert();
+/// ```
+/// The prelude brings it (and [`Compositor`][crate::compositor::Compositor]) into scope:
+/// ```
+/// # use bevy::prelude::*;
+/// use bevy_behaviour_tree_core::prelude::*;
+/// # fn walk(_: In<Entity>) -> Status { unimplemented!() }
+/// let walk = walk.invert();
+/// ```
 pub trait Decorator<Marker> {
     /// Inverts the output.
     ///
@@ -62,6 +84,32 @@ pub trait Decorator<Marker> {
     /// **Fails** when the maximum amount of retries has been reached.
     fn retry(self, tries: usize) -> impl Behaviour + IntoBehaviour<SelfMarker>;
 
+    /// Another name for [`retry`][Decorator::retry].
+    fn try_n_times(self, tries: usize) -> impl Behaviour + IntoBehaviour<SelfMarker>;
+
+    /// Like [`retry`][Decorator::retry], but only retries failures the condition considers transient, like a resource being taken for now.
+    /// Other failures are permanent, and fail right away without using up a try.
+    ///
+    /// **Succeeds** when the underlying behaviour succeeds.
+    /// **Fails** on a permanent failure, or when the maximum amount of tries has been reached.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// #[derive(Component)]
+    /// struct Target(Entity);
+    ///
+    /// # fn grab_target(_: In<Entity>) -> Status { Status::Failure }
+    /// // retry as long as there's still something to grab.
+    /// let grab = grab_target.retry_transient(5, has_component::<Target>());
+    /// ```
+    fn retry_transient<CMarker, C>(
+        self,
+        tries: usize,
+        is_transient: C,
+    ) -> impl Behaviour + IntoBehaviour<SelfMarker>
+    where
+        C: IntoSystem<Entity, bool, CMarker>;
+
     /// Retries while the condition is true.
     ///
     /// **Succeeds** when the underlying behaviour succeeds.
@@ -73,9 +121,12 @@ pub trait Decorator<Marker> {
 
     /// Repeat a fixed number of times, regardless of whether or not the underlying behaviour fails or not.
     ///
-    /// **Succeeds** after running `repeats` times.
+    /// **Succeeds** after running `repeats` times, right away if that's 0.
     fn repeat(self, repeats: usize) -> impl Behaviour + IntoBehaviour<SelfMarker>;
 
+    /// Another name for [`repeat`][Decorator::repeat].
+    fn times(self, repeats: usize) -> impl Behaviour + IntoBehaviour<SelfMarker>;
+
     /// Repeat while the condition is true, regardless of whether or not the underlying behaviour fails or not.
     ///
     /// **Succeeds** after the condition becomes false.
@@ -83,6 +134,143 @@ pub trait Decorator<Marker> {
     where
         C: IntoSystem<Entity, bool, ()> + Clone,
         <C as IntoSystem<Entity, bool, ()>>::System: Clone;
+
+    /// Repeats for a fixed amount of time, regardless of whether or not the underlying behaviour fails or not.
+    ///
+    /// The time is measured with the [`Time`] resource, starting from the first run for each entity. Never finishes without one.
+    /// [Snapshots][crate::plugin::BehaviourTrees::snapshot] keep how much time has passed, so restored entities only wait for the rest.
+    ///
+    /// **Succeeds** once `duration` has passed.
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// # fn look_around(_: In<Entity>) -> Status { Status::Success }
+    /// let look_around = look_around.repeat_for_duration(Duration::from_secs(3));
+    /// ```
+    fn repeat_for_duration(self, duration: Duration) -> impl Behaviour + IntoBehaviour<SelfMarker>;
+
+    /// Runs the underlying behaviour until it completes, then keeps returning that result without running it again.
+    ///
+    /// **Succeeds or fails** forever after the underlying behaviour first does, until the entity is reset with [`BehaviourTrees::reset_for_entity`][crate::plugin::BehaviourTrees::reset_for_entity].
+    fn latch(self) -> impl Behaviour + IntoBehaviour<SelfMarker>;
+
+    /// Runs the underlying behaviour on the entity returned by `f` instead, e.g. to have a commander act through its subordinates.
+    ///
+    /// **Succeeds or fails** depending on the underlying behaviour.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// #[derive(Component)]
+    /// struct SelectedTarget(Entity);
+    ///
+    /// # fn attack(_: In<Entity>) -> Status { Status::Success }
+    /// let order_attack = attack.map_entity(|entity, world| {
+    ///     world.get::<SelectedTarget>(entity).map_or(entity, |target| target.0)
+    /// });
+    /// ```
+    fn map_entity<F>(self, f: F) -> impl Behaviour + IntoBehaviour<SelfMarker>
+    where
+        F: Fn(Entity, &World) -> Entity + Send + Sync + 'static;
+
+    /// [Increments][Increment] the entity's `C` every time the underlying behaviour succeeds, e.g. to keep score.
+    ///
+    /// **Succeeds or fails** depending on the underlying behaviour. Entities without `C` aren't counted.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::{prelude::*, decorator::Counter};
+    /// # fn hunt(_: In<Entity>) -> Status { Status::Success }
+    /// let hunt = hunt.count_successes::<Counter>();
+    /// ```
+    fn count_successes<C: Component + Increment>(
+        self,
+    ) -> impl Behaviour + IntoBehaviour<SelfMarker>;
+
+    /// Runs the underlying behaviour up to `n` times per tick, for cheap behaviours that should progress faster than once a frame.
+    ///
+    /// Unlike [`repeat`][Decorator::repeat], all runs happen within a single tick.
+    ///
+    /// **Succeeds or fails** as soon as the underlying behaviour does. Returns `Running` if it's still running after `n` runs.
+    fn run_n_times_per_tick(self, n: usize) -> impl Behaviour + IntoBehaviour<SelfMarker>;
+
+    /// Counts how many times the underlying behaviour ran for each entity, regardless of its status.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// # fn patrol(_: In<Entity>) -> Status { Status::Running }
+    /// let mut world = World::default();
+    /// let entity = world.spawn_empty().id();
+    ///
+    /// let mut patrol = patrol.track_ticks();
+    /// patrol.initialize(&mut world);
+    /// patrol.run(entity, &mut world);
+    ///
+    /// assert_eq!(patrol.tick_count(entity), 1);
+    /// ```
+    fn track_ticks(self) -> Tracked<impl Behaviour>;
+
+    /// Pauses the underlying behaviour while the condition is true, and resumes it where it left off once it's false again.
+    ///
+    /// **Running** while suspended. **Succeeds or fails** depending on the underlying behaviour otherwise.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// #[derive(Component)]
+    /// struct Stunned;
+    ///
+    /// # fn walk_to_target(_: In<Entity>) -> Status { Status::Success }
+    /// let walk = walk_to_target.suspend_if(has_component::<Stunned>());
+    /// ```
+    fn suspend_if<CMarker, C>(self, condition: C) -> impl Behaviour + IntoBehaviour<SelfMarker>
+    where
+        C: IntoSystem<Entity, bool, CMarker>;
+
+    /// Runs the underlying behaviour only on the entity's turn, i.e. while it has a `C` component.
+    /// The component is removed once the behaviour finishes, to end the turn.
+    ///
+    /// **Running** while it's not the entity's turn. **Succeeds or fails** depending on the underlying behaviour otherwise.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// #[derive(Component)]
+    /// struct TurnToken;
+    ///
+    /// # fn take_turn(_: In<Entity>) -> Status { Status::Success }
+    /// let take_turn = take_turn.run_on_turn::<TurnToken>();
+    /// ```
+    fn run_on_turn<C: Component>(self) -> impl Behaviour + IntoBehaviour<SelfMarker>;
+
+    /// Wraps the underlying behaviour in `decorator` while the condition is true, and in `else_decorator` while it's false.
+    ///
+    /// The condition is checked whenever the behaviour starts for an entity, not every tick, so an entity keeps its decorator until it finishes.
+    /// Both decorators wrap the same [shared][Behaviour::shared] instance of the underlying behaviour.
+    ///
+    /// **Succeeds or fails** depending on the active decorator.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// #[derive(Component)]
+    /// struct InCombat;
+    ///
+    /// # fn find_cover(_: In<Entity>) -> Status { Status::Success }
+    /// let find_cover = find_cover.conditional(
+    ///     has_component::<InCombat>(),
+    ///     |find_cover| find_cover.retry(5),
+    ///     |find_cover| find_cover.retry(1),
+    /// );
+    /// ```
+    fn conditional<CMarker, C, M1: 'static, B1, D1, M2: 'static, B2, D2>(
+        self,
+        condition: C,
+        decorator: D1,
+        else_decorator: D2,
+    ) -> impl Behaviour + IntoBehaviour<SelfMarker>
+    where
+        C: IntoSystem<Entity, bool, CMarker>,
+        B1: IntoBehaviour<M1>,
+        D1: FnOnce(BehaviourRef<Box<dyn Behaviour>>) -> B1,
+        B2: IntoBehaviour<M2>,
+        D2: FnOnce(BehaviourRef<Box<dyn Behaviour>>) -> B2;
 }
 
 impl<Marker: 'static, T: IntoBehaviour<Marker>> Decorator<Marker> for T {
@@ -110,6 +298,7 @@ impl<Marker: 'static, T: IntoBehaviour<Marker>> Decorator<Marker> for T {
         RunIf {
             func: IntoBehaviour::into_behaviour(self),
             condition: IntoSystem::into_system(condition),
+            run_when: true,
             short_circuit,
         }
     }
@@ -122,6 +311,26 @@ impl<Marker: 'static, T: IntoBehaviour<Marker>> Decorator<Marker> for T {
         }
     }
 
+    fn try_n_times(self, tries: usize) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+        self.retry(tries)
+    }
+
+    fn retry_transient<CMarker, C>(
+        self,
+        tries: usize,
+        is_transient: C,
+    ) -> impl Behaviour + IntoBehaviour<SelfMarker>
+    where
+        C: IntoSystem<Entity, bool, CMarker>,
+    {
+        RetryTransient {
+            max_tries: tries,
+            tries: HashMap::default(),
+            func: IntoBehaviour::into_behaviour(self),
+            is_transient: IntoSystem::into_system(is_transient),
+        }
+    }
+
     fn retry_while<CMarker, C>(self, condition: C) -> impl Behaviour + IntoBehaviour<SelfMarker>
     where
         C: IntoSystem<Entity, bool, CMarker> + Clone,
@@ -133,8 +342,16 @@ impl<Marker: 'static, T: IntoBehaviour<Marker>> Decorator<Marker> for T {
         }
     }
 
-    fn repeat(self, _times: usize) -> impl Behaviour + IntoBehaviour<SelfMarker> {
-        TodoBehaviour
+    fn repeat(self, repeats: usize) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+        Repeat {
+            func: IntoBehaviour::into_behaviour(self),
+            repeats,
+            runs: HashMap::default(),
+        }
+    }
+
+    fn times(self, repeats: usize) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+        self.repeat(repeats)
     }
 
     fn repeat_while<C>(self, _condition: C) -> impl Behaviour + IntoBehaviour<SelfMarker>
@@ -144,6 +361,93 @@ impl<Marker: 'static, T: IntoBehaviour<Marker>> Decorator<Marker> for T {
     {
         TodoBehaviour
     }
+
+    fn repeat_for_duration(self, duration: Duration) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+        RepeatForDuration {
+            func: IntoBehaviour::into_behaviour(self),
+            duration,
+            windows: HashMap::default(),
+        }
+    }
+
+    fn latch(self) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+        Latch {
+            func: IntoBehaviour::into_behaviour(self),
+            latched: HashMap::default(),
+        }
+    }
+
+    fn map_entity<F>(self, f: F) -> impl Behaviour + IntoBehaviour<SelfMarker>
+    where
+        F: Fn(Entity, &World) -> Entity + Send + Sync + 'static,
+    {
+        MapEntity {
+            func: IntoBehaviour::into_behaviour(self),
+            map: f,
+            mapped: HashMap::default(),
+        }
+    }
+
+    fn count_successes<C: Component + Increment>(
+        self,
+    ) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+        CountSuccesses {
+            func: IntoBehaviour::into_behaviour(self),
+            _counter: PhantomData::<fn() -> C>,
+        }
+    }
+
+    fn run_n_times_per_tick(self, n: usize) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+        RunNTimesPerTick(IntoBehaviour::into_behaviour(self), n)
+    }
+
+    fn suspend_if<CMarker, C>(self, condition: C) -> impl Behaviour + IntoBehaviour<SelfMarker>
+    where
+        C: IntoSystem<Entity, bool, CMarker>,
+    {
+        SuspendIf {
+            func: IntoBehaviour::into_behaviour(self),
+            condition: IntoSystem::into_system(condition),
+        }
+    }
+
+    fn run_on_turn<C: Component>(self) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+        RunOnTurn {
+            func: IntoBehaviour::into_behaviour(self),
+            token: None,
+            _token: PhantomData::<fn() -> C>,
+        }
+    }
+
+    fn conditional<CMarker, C, M1: 'static, B1, D1, M2: 'static, B2, D2>(
+        self,
+        condition: C,
+        decorator: D1,
+        else_decorator: D2,
+    ) -> impl Behaviour + IntoBehaviour<SelfMarker>
+    where
+        C: IntoSystem<Entity, bool, CMarker>,
+        B1: IntoBehaviour<M1>,
+        D1: FnOnce(BehaviourRef<Box<dyn Behaviour>>) -> B1,
+        B2: IntoBehaviour<M2>,
+        D2: FnOnce(BehaviourRef<Box<dyn Behaviour>>) -> B2,
+    {
+        let func = (Box::new(IntoBehaviour::into_behaviour(self)) as Box<dyn Behaviour>).shared();
+
+        Conditional {
+            condition: IntoSystem::into_system(condition),
+            then: IntoBehaviour::into_behaviour(decorator(func.clone())),
+            otherwise: IntoBehaviour::into_behaviour(else_decorator(func)),
+            active: HashMap::default(),
+        }
+    }
+
+    fn track_ticks(self) -> Tracked<impl Behaviour> {
+        Tracked {
+            func: IntoBehaviour::into_behaviour(self),
+            ticks: HashMap::default(),
+        }
+    }
 }
 
 /// See [`DecoratorInput::invert`].
@@ -161,6 +465,36 @@ impl<T: Behaviour> Behaviour for Invert<T> {
         self.0.initialize(world);
     }
 
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.0.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.0.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node("invert", String::new(), vec![self.0.shape()])
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.0.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState::from_children(vec![self.0.save_state()])
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        if let Some(child) = state.children.first() {
+            self.0.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.0.reset_entity(entity);
+    }
+
     #[inline]
     fn run(&mut self, entity: Entity, world: &mut World) -> Status {
         match self.0.run(entity, world) {
@@ -171,9 +505,91 @@ impl<T: Behaviour> Behaviour for Invert<T> {
     }
 }
 
+/// Runs `behaviour` only when the condition is true.
+///
+/// **Fails** if the condition is false.
+/// **Succeeds or fails** depending on the underlying behaviour if the condition is true.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// # #[derive(Component)]
+/// # struct Target;
+/// # fn walk_to_target(_: In<Entity>) -> Status { Status::Success }
+/// let walk = when(has_component::<Target>(), walk_to_target);
+/// ```
+pub fn when<CMarker, C, Marker: 'static, B: IntoBehaviour<Marker>>(
+    condition: C,
+    behaviour: B,
+) -> impl Behaviour + IntoBehaviour<SelfMarker>
+where
+    C: IntoSystem<Entity, bool, CMarker> + Clone,
+    <C as IntoSystem<Entity, bool, CMarker>>::System: Clone,
+{
+    RunIf {
+        func: IntoBehaviour::into_behaviour(behaviour),
+        condition: IntoSystem::into_system(condition),
+        run_when: true,
+        short_circuit: Status::Failure,
+    }
+}
+
+/// The opposite of [`when`]: runs `behaviour` only when the condition is false.
+///
+/// **Fails** if the condition is true.
+pub fn unless<CMarker, C, Marker: 'static, B: IntoBehaviour<Marker>>(
+    condition: C,
+    behaviour: B,
+) -> impl Behaviour + IntoBehaviour<SelfMarker>
+where
+    C: IntoSystem<Entity, bool, CMarker> + Clone,
+    <C as IntoSystem<Entity, bool, CMarker>>::System: Clone,
+{
+    RunIf {
+        func: IntoBehaviour::into_behaviour(behaviour),
+        condition: IntoSystem::into_system(condition),
+        run_when: false,
+        short_circuit: Status::Failure,
+    }
+}
+
+/// Like [`when`], but **succeeds** if the condition is false. Same as [`Decorator::run_if`].
+pub fn try_if<CMarker, C, Marker: 'static, B: IntoBehaviour<Marker>>(
+    condition: C,
+    behaviour: B,
+) -> impl Behaviour + IntoBehaviour<SelfMarker>
+where
+    C: IntoSystem<Entity, bool, CMarker> + Clone,
+    <C as IntoSystem<Entity, bool, CMarker>>::System: Clone,
+{
+    RunIf {
+        func: IntoBehaviour::into_behaviour(behaviour),
+        condition: IntoSystem::into_system(condition),
+        run_when: true,
+        short_circuit: Status::Success,
+    }
+}
+
+/// Same as [`Decorator::invert`], for writing trees in prefix notation:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// use bevy_behaviour_tree_core::decorator::not;
+/// # fn enemy_visible(_: In<Entity>) -> Status { Status::Success }
+/// # fn run_away(_: In<Entity>) -> Status { Status::Success }
+/// let flee = (not(enemy_visible), run_away).select();
+/// ```
+/// It's not part of the prelude, since bevy's prelude has a `not` run condition.
+pub fn not<Marker: 'static>(
+    behaviour: impl IntoBehaviour<Marker>,
+) -> impl Behaviour + IntoBehaviour<SelfMarker> {
+    behaviour.invert()
+}
+
 struct RunIf<F: Behaviour, C: System<In = Entity, Out = bool> + Clone> {
     func: F,
     condition: C,
+    // what the condition needs to return for `func` to run.
+    run_when: bool,
     short_circuit: Status,
 }
 
@@ -191,9 +607,51 @@ impl<F: Behaviour, C: System<In = Entity, Out = bool> + Clone> Behaviour for Run
         self.condition.initialize(world);
     }
 
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(
+            self.func
+                .dependencies()
+                .into_iter()
+                .chain(self.condition.component_access().reads_and_writes()),
+        )
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "run_if",
+            format!(
+                "run_when: {}, short_circuit: {:?}",
+                self.run_when, self.short_circuit
+            ),
+            vec![self.func.shape()],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState::from_children(vec![self.func.save_state()])
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.func.reset_entity(entity);
+    }
+
     #[inline]
     fn run(&mut self, entity: Entity, world: &mut World) -> Status {
-        if self.condition.run(entity, world) {
+        if self.condition.run(entity, world) == self.run_when {
             self.func.run(entity, world)
         } else {
             self.short_circuit
@@ -222,6 +680,41 @@ impl<F: Behaviour, C: System<In = Entity, Out = bool> + Clone> Behaviour for Ret
         self.func.initialize(world);
     }
 
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(
+            self.func
+                .dependencies()
+                .into_iter()
+                .chain(self.condition.component_access().reads_and_writes()),
+        )
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node("retry_while", String::new(), vec![self.func.shape()])
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState::from_children(vec![self.func.save_state()])
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.func.reset_entity(entity);
+    }
+
     #[inline]
     fn run(&mut self, entity: Entity, world: &mut World) -> Status {
         if self.condition.run(entity, world) {
@@ -235,66 +728,1002 @@ impl<F: Behaviour, C: System<In = Entity, Out = bool> + Clone> Behaviour for Ret
     }
 }
 
-/// See [`DecoratorInput::retry`].
-#[derive(Clone)]
-struct Retry<T: Behaviour> {
-    max_tries: usize,
-    tries: HashMap<Entity, usize>,
+/// See [`Decorator::repeat_for_duration`].
+struct RepeatForDuration<T: Behaviour> {
     func: T,
+    duration: Duration,
+    // how long each entity's window has been going, and `Time::elapsed` when that was last updated.
+    // Only the first is saved, since the time of a previous session means nothing after restoring.
+    windows: HashMap<Entity, (Duration, Option<Duration>)>,
 }
 
-impl<T: Behaviour> Retry<T> {
-    #[inline]
-    fn reset(&mut self, entity: Entity) {
-        self.tries.insert(entity, 0);
+impl<T: Behaviour> IntoBehaviour<SelfMarker> for RepeatForDuration<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
     }
+}
 
-    #[inline]
-    fn increase(&mut self, entity: Entity) {
-        if let Some(attempt) = self.tries.get_mut(&entity) {
-            *attempt += 1;
+impl<T: Behaviour> Behaviour for RepeatForDuration<T> {
+    fn initialize(&mut self, world: &mut World) {
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.func.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "repeat_for_duration",
+            format!("duration: {:?}", self.duration),
+            vec![self.func.shape()],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        // elapsed times are stored in whole milliseconds.
+        BehaviourState {
+            entities: self
+                .windows
+                .iter()
+                .map(|(entity, (elapsed, _))| (*entity, elapsed.as_millis() as usize))
+                .collect(),
+            children: vec![self.func.save_state()],
         }
     }
 
-    #[inline]
-    fn tries(&mut self, entity: Entity) -> usize {
-        match self.tries.get(&entity) {
-            Some(attempts) => *attempts,
-            None => {
-                self.tries.insert(entity, 0);
-                0
-            }
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.windows = state
+            .entities
+            .iter()
+            .map(|(entity, millis)| (*entity, (Duration::from_millis(*millis as u64), None)))
+            .collect();
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.windows.remove(&entity);
+        self.func.reset_entity(entity);
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let Some(now) = world.get_resource::<Time>().map(Time::elapsed) else {
+            self.func.run(entity, world);
+            return Status::Running;
+        };
+
+        let (elapsed, last) = self.windows.entry(entity).or_insert((Duration::ZERO, None));
+        // restored windows pick up where they were saved, whatever the time is now.
+        *elapsed += last.map_or(Duration::ZERO, |last| now.saturating_sub(last));
+        *last = Some(now);
+
+        if *elapsed >= self.duration {
+            self.windows.remove(&entity);
+            return Status::Success;
         }
+
+        self.func.run(entity, world);
+        Status::Running
     }
 }
 
-impl<T: Behaviour> IntoBehaviour<SelfMarker> for Retry<T> {
+/// See [`Decorator::suspend_if`].
+struct SuspendIf<F: Behaviour, C: System<In = Entity, Out = bool>> {
+    func: F,
+    condition: C,
+}
+
+impl<F: Behaviour, C: System<In = Entity, Out = bool>> IntoBehaviour<SelfMarker>
+    for SuspendIf<F, C>
+{
     fn into_behaviour(self) -> impl Behaviour {
         self
     }
 }
 
-impl<T: Behaviour> Behaviour for Retry<T> {
+impl<F: Behaviour, C: System<In = Entity, Out = bool>> Behaviour for SuspendIf<F, C> {
     fn initialize(&mut self, world: &mut World) {
+        self.condition.initialize(world);
         self.func.initialize(world);
     }
 
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(
+            self.func
+                .dependencies()
+                .into_iter()
+                .chain(self.condition.component_access().reads_and_writes()),
+        )
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node("suspend_if", String::new(), vec![self.func.shape()])
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState::from_children(vec![self.func.save_state()])
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.func.reset_entity(entity);
+    }
+
+    #[inline]
     fn run(&mut self, entity: Entity, world: &mut World) -> Status {
-        match self.func.run(entity, world) {
-            Status::Failure => {
-                self.increase(entity);
-                if self.tries(entity) < self.max_tries {
-                    Status::Running
-                } else {
-                    self.reset(entity); // reset state to get ready for the next call
-                    Status::Failure
-                }
-            }
-            Status::Success => {
-                self.reset(entity); // reset state
-                Status::Success
-            }
-            Status::Running => Status::Running,
+        if self.condition.run(entity, world) {
+            // the child isn't touched, so it keeps its state.
+            Status::Running
+        } else {
+            self.func.run(entity, world)
         }
     }
 }
+
+/// See [`Decorator::run_on_turn`].
+struct RunOnTurn<F: Behaviour, T: Component> {
+    func: F,
+    // set when initialized.
+    token: Option<ComponentId>,
+    _token: PhantomData<fn() -> T>,
+}
+
+impl<F: Behaviour, T: Component> IntoBehaviour<SelfMarker> for RunOnTurn<F, T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<F: Behaviour, T: Component> Behaviour for RunOnTurn<F, T> {
+    fn initialize(&mut self, world: &mut World) {
+        self.token = Some(world.init_component::<T>());
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(self.func.dependencies().into_iter().chain(self.token))
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "run_on_turn",
+            std::any::type_name::<T>(),
+            vec![self.func.shape()],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState::from_children(vec![self.func.save_state()])
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.func.reset_entity(entity);
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        if world.get::<T>(entity).is_none() {
+            return Status::Running;
+        }
+
+        let status = self.func.run(entity, world);
+        if status != Status::Running {
+            // the behaviour may have despawned the entity.
+            if let Some(mut entity) = world.get_entity_mut(entity) {
+                entity.remove::<T>();
+            }
+        }
+        status
+    }
+}
+
+/// See [`Decorator::conditional`].
+struct Conditional<C: System<In = Entity, Out = bool>, T: Behaviour, E: Behaviour> {
+    condition: C,
+    then: T,
+    otherwise: E,
+    // whether `then` is active. Entities without an entry pick a branch on their next run.
+    active: HashMap<Entity, bool>,
+}
+
+impl<C: System<In = Entity, Out = bool>, T: Behaviour, E: Behaviour> IntoBehaviour<SelfMarker>
+    for Conditional<C, T, E>
+{
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<C: System<In = Entity, Out = bool>, T: Behaviour, E: Behaviour> Behaviour
+    for Conditional<C, T, E>
+{
+    fn initialize(&mut self, world: &mut World) {
+        self.condition.initialize(world);
+        self.then.initialize(world);
+        self.otherwise.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(
+            self.then
+                .dependencies()
+                .into_iter()
+                .chain(self.otherwise.dependencies())
+                .chain(self.condition.component_access().reads_and_writes()),
+        )
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.then.validate(trees)?;
+        self.otherwise.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "conditional",
+            String::new(),
+            vec![self.then.shape(), self.otherwise.shape()],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.then.warm_up(entity, world);
+        self.otherwise.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self
+                .active
+                .iter()
+                .map(|(entity, then)| (*entity, usize::from(*then)))
+                .collect(),
+            children: vec![self.then.save_state(), self.otherwise.save_state()],
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.active = state
+            .entities
+            .iter()
+            .map(|(entity, then)| (*entity, *then != 0))
+            .collect();
+
+        if let [then, otherwise] = state.children.as_slice() {
+            self.then.load_state(then);
+            self.otherwise.load_state(otherwise);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.active.remove(&entity);
+        self.then.reset_entity(entity);
+        self.otherwise.reset_entity(entity);
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        match self.active.get(&entity) {
+            Some(true) => "then".to_owned(),
+            Some(false) => "else".to_owned(),
+            None => String::new(),
+        }
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let then = match self.active.get(&entity) {
+            Some(then) => *then,
+            None => {
+                let then = self.condition.run(entity, world);
+                self.active.insert(entity, then);
+                then
+            }
+        };
+
+        let status = if then {
+            self.then.run(entity, world)
+        } else {
+            self.otherwise.run(entity, world)
+        };
+
+        if status != Status::Running {
+            self.active.remove(&entity);
+        }
+
+        status
+    }
+}
+
+/// See [`DecoratorInput::retry`].
+#[derive(Clone)]
+struct Retry<T: Behaviour> {
+    max_tries: usize,
+    tries: HashMap<Entity, usize>,
+    func: T,
+}
+
+impl<T: Behaviour> Retry<T> {
+    #[inline]
+    fn reset(&mut self, entity: Entity) {
+        self.tries.insert(entity, 0);
+    }
+
+    #[inline]
+    fn increase(&mut self, entity: Entity) {
+        if let Some(attempt) = self.tries.get_mut(&entity) {
+            *attempt += 1;
+        }
+    }
+
+    #[inline]
+    fn tries(&mut self, entity: Entity) -> usize {
+        match self.tries.get(&entity) {
+            Some(attempts) => *attempts,
+            None => {
+                self.tries.insert(entity, 0);
+                0
+            }
+        }
+    }
+}
+
+impl<T: Behaviour> IntoBehaviour<SelfMarker> for Retry<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour> Behaviour for Retry<T> {
+    fn initialize(&mut self, world: &mut World) {
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.func.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "retry",
+            format!("max_tries: {}", self.max_tries),
+            vec![self.func.shape()],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.tries.clone(),
+            children: vec![self.func.save_state()],
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.tries = state.entities.clone();
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.tries.remove(&entity);
+        self.func.reset_entity(entity);
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        let tries = self.tries.get(&entity).copied().unwrap_or(0);
+        format!("tries: {}/{}", tries, self.max_tries)
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        match self.func.run(entity, world) {
+            Status::Failure => {
+                self.increase(entity);
+                if self.tries(entity) < self.max_tries {
+                    Status::Running
+                } else {
+                    self.reset(entity); // reset state to get ready for the next call
+                    Status::Failure
+                }
+            }
+            Status::Success => {
+                self.reset(entity); // reset state
+                Status::Success
+            }
+            Status::Running => Status::Running,
+        }
+    }
+}
+
+/// See [`Decorator::repeat`].
+struct Repeat<T: Behaviour> {
+    repeats: usize,
+    // completed runs so far. Entities without an entry haven't completed one yet.
+    runs: HashMap<Entity, usize>,
+    func: T,
+}
+
+impl<T: Behaviour> IntoBehaviour<SelfMarker> for Repeat<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour> Behaviour for Repeat<T> {
+    fn initialize(&mut self, world: &mut World) {
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.func.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "repeat",
+            format!("repeats: {}", self.repeats),
+            vec![self.func.shape()],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.runs.clone(),
+            children: vec![self.func.save_state()],
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.runs = state.entities.clone();
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.runs.remove(&entity);
+        self.func.reset_entity(entity);
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        let runs = self.runs.get(&entity).copied().unwrap_or(0);
+        format!("runs: {}/{}", runs, self.repeats)
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        if self.repeats == 0 {
+            return Status::Success;
+        }
+
+        if self.func.run(entity, world) == Status::Running {
+            return Status::Running;
+        }
+
+        let runs = self.runs.entry(entity).or_default();
+        *runs += 1;
+        if *runs < self.repeats {
+            Status::Running
+        } else {
+            self.runs.remove(&entity); // get ready for the next call
+            Status::Success
+        }
+    }
+}
+
+/// See [`Decorator::retry_transient`].
+struct RetryTransient<T: Behaviour, C: System<In = Entity, Out = bool>> {
+    max_tries: usize,
+    // transient failures so far. Entities without an entry haven't failed yet.
+    tries: HashMap<Entity, usize>,
+    func: T,
+    is_transient: C,
+}
+
+impl<T: Behaviour, C: System<In = Entity, Out = bool>> IntoBehaviour<SelfMarker>
+    for RetryTransient<T, C>
+{
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour, C: System<In = Entity, Out = bool>> Behaviour for RetryTransient<T, C> {
+    fn initialize(&mut self, world: &mut World) {
+        self.is_transient.initialize(world);
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        union_dependencies(
+            self.func
+                .dependencies()
+                .into_iter()
+                .chain(self.is_transient.component_access().reads_and_writes()),
+        )
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "retry_transient",
+            format!("max_tries: {}", self.max_tries),
+            vec![self.func.shape()],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self.tries.clone(),
+            children: vec![self.func.save_state()],
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.tries = state.entities.clone();
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.tries.remove(&entity);
+        self.func.reset_entity(entity);
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        let tries = self.tries.get(&entity).copied().unwrap_or(0);
+        format!("tries: {}/{}", tries, self.max_tries)
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        match self.func.run(entity, world) {
+            Status::Failure if self.is_transient.run(entity, world) => {
+                let tries = self.tries.entry(entity).or_default();
+                *tries += 1;
+                if *tries < self.max_tries {
+                    Status::Running
+                } else {
+                    self.tries.remove(&entity);
+                    Status::Failure
+                }
+            }
+            Status::Failure => {
+                self.tries.remove(&entity);
+                Status::Failure
+            }
+            Status::Success => {
+                self.tries.remove(&entity);
+                Status::Success
+            }
+            Status::Running => Status::Running,
+        }
+    }
+}
+
+/// See [`Decorator::latch`].
+#[derive(Clone)]
+struct Latch<T: Behaviour> {
+    latched: HashMap<Entity, Status>,
+    func: T,
+}
+
+impl<T: Behaviour> IntoBehaviour<SelfMarker> for Latch<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour> Behaviour for Latch<T> {
+    fn initialize(&mut self, world: &mut World) {
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.func.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node("latch", String::new(), vec![self.func.shape()])
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            // statuses are stored in their `u8` encoding. `Running` is never latched.
+            entities: self
+                .latched
+                .iter()
+                .map(|(entity, status)| (*entity, usize::from(u8::from(*status))))
+                .collect(),
+            children: vec![self.func.save_state()],
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.latched = state
+            .entities
+            .iter()
+            .filter_map(|(entity, status)| {
+                let status = Status::try_from(u8::try_from(*status).ok()?).ok()?;
+                (status != Status::Running).then_some((*entity, status))
+            })
+            .collect();
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.latched.remove(&entity);
+        self.func.reset_entity(entity);
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        if let Some(status) = self.latched.get(&entity) {
+            return *status;
+        }
+
+        let status = self.func.run(entity, world);
+        if status != Status::Running {
+            self.latched.insert(entity, status);
+        }
+
+        status
+    }
+}
+
+/// See [`Decorator::map_entity`].
+struct MapEntity<T: Behaviour, F> {
+    func: T,
+    map: F,
+    // the last entity each entity was mapped to, so resets reach the right state in the child.
+    mapped: HashMap<Entity, Entity>,
+}
+
+impl<T: Behaviour, F: Fn(Entity, &World) -> Entity + Send + Sync + 'static>
+    IntoBehaviour<SelfMarker> for MapEntity<T, F>
+{
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour, F: Fn(Entity, &World) -> Entity + Send + Sync + 'static> Behaviour
+    for MapEntity<T, F>
+{
+    fn initialize(&mut self, world: &mut World) {
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.func.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node("map_entity", String::new(), vec![self.func.shape()])
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        let mapped = (self.map)(entity, world);
+        self.func.warm_up(mapped, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState::from_children(vec![self.func.save_state()])
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        let mapped = self.mapped.remove(&entity).unwrap_or(entity);
+        self.func.reset_entity(mapped);
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let mapped = (self.map)(entity, world);
+        self.mapped.insert(entity, mapped);
+
+        self.func.run(mapped, world)
+    }
+}
+
+/// Components that can count, for use with [`Decorator::count_successes`].
+pub trait Increment {
+    /// Counts one up.
+    fn increment(&mut self);
+}
+
+/// A simple counter. See [`Decorator::count_successes`].
+#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Counter(pub u32);
+
+impl Increment for Counter {
+    #[inline]
+    fn increment(&mut self) {
+        self.0 += 1;
+    }
+}
+
+/// See [`Decorator::count_successes`].
+struct CountSuccesses<T: Behaviour, C> {
+    func: T,
+    _counter: PhantomData<fn() -> C>,
+}
+
+impl<T: Behaviour, C: Component + Increment> IntoBehaviour<SelfMarker> for CountSuccesses<T, C> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour, C: Component + Increment> Behaviour for CountSuccesses<T, C> {
+    fn initialize(&mut self, world: &mut World) {
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.func.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "count_successes",
+            std::any::type_name::<C>().to_owned(),
+            vec![self.func.shape()],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState::from_children(vec![self.func.save_state()])
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.func.reset_entity(entity);
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let status = self.func.run(entity, world);
+
+        if status == Status::Success {
+            match world.get_mut::<C>(entity) {
+                Some(mut counter) => counter.increment(),
+                None => warn!(
+                    "{entity:?} doesn't have a {}, so its success wasn't counted",
+                    std::any::type_name::<C>()
+                ),
+            }
+        }
+
+        status
+    }
+}
+
+/// See [`Decorator::run_n_times_per_tick`].
+#[derive(Clone)]
+struct RunNTimesPerTick<T: Behaviour>(T, usize);
+
+impl<T: Behaviour> IntoBehaviour<SelfMarker> for RunNTimesPerTick<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour> Behaviour for RunNTimesPerTick<T> {
+    fn initialize(&mut self, world: &mut World) {
+        self.0.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.0.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.0.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node(
+            "run_n_times_per_tick",
+            format!("n: {}", self.1),
+            vec![self.0.shape()],
+        )
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.0.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState::from_children(vec![self.0.save_state()])
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        if let Some(child) = state.children.first() {
+            self.0.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.0.reset_entity(entity);
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        for _ in 0..self.1 {
+            match self.0.run(entity, world) {
+                Status::Running => continue,
+                status => return status,
+            }
+        }
+
+        Status::Running
+    }
+}
+
+/// Counts runs per entity. See [`Decorator::track_ticks`].
+pub struct Tracked<T: Behaviour> {
+    func: T,
+    ticks: HashMap<Entity, u64>,
+}
+
+impl<T: Behaviour> Tracked<T> {
+    /// How many times the behaviour ran for the entity since it was [initialized][Behaviour::initialize] or [reset][Behaviour::reset_entity].
+    pub fn tick_count(&self, entity: Entity) -> u64 {
+        self.ticks.get(&entity).copied().unwrap_or(0)
+    }
+}
+
+impl<T: Behaviour> IntoBehaviour<SelfMarker> for Tracked<T> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<T: Behaviour> Behaviour for Tracked<T> {
+    fn initialize(&mut self, world: &mut World) {
+        self.ticks.clear();
+        self.func.initialize(world);
+    }
+
+    fn dependencies(&self) -> Vec<ComponentId> {
+        self.func.dependencies()
+    }
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        self.func.validate(trees)
+    }
+
+    fn shape(&self) -> BehaviourShape {
+        BehaviourShape::node("track_ticks", String::new(), vec![self.func.shape()])
+    }
+
+    fn warm_up(&mut self, entity: Entity, world: &mut World) {
+        self.func.warm_up(entity, world);
+    }
+
+    fn save_state(&self) -> BehaviourState {
+        BehaviourState {
+            entities: self
+                .ticks
+                .iter()
+                .map(|(entity, ticks)| (*entity, *ticks as usize))
+                .collect(),
+            children: vec![self.func.save_state()],
+        }
+    }
+
+    fn load_state(&mut self, state: &BehaviourState) {
+        self.ticks = state
+            .entities
+            .iter()
+            .map(|(entity, ticks)| (*entity, *ticks as u64))
+            .collect();
+        if let Some(child) = state.children.first() {
+            self.func.load_state(child);
+        }
+    }
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.ticks.remove(&entity);
+        self.func.reset_entity(entity);
+    }
+
+    fn debug_state(&self, entity: Entity) -> String {
+        format!("ticks: {}", self.tick_count(entity))
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        *self.ticks.entry(entity).or_default() += 1;
+        self.func.run(entity, world)
+    }
+}
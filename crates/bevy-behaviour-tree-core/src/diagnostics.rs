@@ -0,0 +1,90 @@
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, DiagnosticMeasurement, DiagnosticsStore},
+    prelude::{App, Mut, Plugin, Resource, World},
+    utils::{Duration, HashMap, HashSet, Instant},
+};
+
+use crate::{plugin::BehaviourNameRegistry, prelude::BehaviourId};
+
+/// Records how long each tree takes to run per tick, as one [`Diagnostic`] per [`BehaviourId`].
+///
+/// Diagnostics are named after the tree, like `behaviour_tree/tree#0`, and measured in milliseconds.
+/// Trees with a name in the [`BehaviourNameRegistry`] when they're first measured use that instead, like `behaviour_tree/guard`.
+/// Added automatically by [`BehaviourTreePlugin::with_diagnostics`][crate::plugin::BehaviourTreePlugin::with_diagnostics].
+#[derive(Default)]
+pub struct BehaviourTreeDiagnosticsPlugin;
+
+impl BehaviourTreeDiagnosticsPlugin {
+    /// How many measurements are kept per tree.
+    pub const MAX_HISTORY_LENGTH: usize = 20;
+
+    // the lower 64 bits are reserved for the tree index.
+    const BASE_ID: u128 = 0x2bd3_c1f0_5a8e_4c47_0000_0000_0000_0000;
+
+    /// The ID of the diagnostic for the given tree.
+    pub fn diagnostic_id(id: BehaviourId) -> DiagnosticId {
+        DiagnosticId::from_u128(Self::BASE_ID | id.index as u128)
+    }
+}
+
+impl Plugin for BehaviourTreeDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<BehaviourTreeDiagnostics>();
+    }
+}
+
+/// Keeps track of which trees already have a diagnostic. Its presence enables timing in `run_ticks`.
+#[derive(Resource, Default)]
+pub(crate) struct BehaviourTreeDiagnostics {
+    registered: HashSet<BehaviourId>,
+}
+
+/// Adds one tick's worth of timings to the diagnostics store, registering new diagnostics as needed.
+pub(crate) fn record_timings(world: &mut World, timings: HashMap<BehaviourId, Duration>) {
+    world.resource_scope(
+        |world: &mut World, mut diagnostics: Mut<BehaviourTreeDiagnostics>| {
+            let names = world.get_resource::<BehaviourNameRegistry>();
+            let mut labels = timings
+                .keys()
+                .filter(|id| !diagnostics.registered.contains(*id))
+                .map(|id| {
+                    let label = match names {
+                        Some(names) => id.display_with_registry(names).to_string(),
+                        None => id.to_string(),
+                    };
+                    (*id, format!("behaviour_tree/{label}"))
+                })
+                .collect::<HashMap<_, _>>();
+
+            let Some(mut store) = world.get_resource_mut::<DiagnosticsStore>() else {
+                return;
+            };
+
+            let now = Instant::now();
+
+            for (id, time) in timings {
+                let diagnostic_id = BehaviourTreeDiagnosticsPlugin::diagnostic_id(id);
+
+                if let Some(label) = labels.remove(&id) {
+                    diagnostics.registered.insert(id);
+                    store.add(
+                        Diagnostic::new(
+                            diagnostic_id,
+                            label,
+                            BehaviourTreeDiagnosticsPlugin::MAX_HISTORY_LENGTH,
+                        )
+                        .with_suffix("ms"),
+                    );
+                }
+
+                if let Some(diagnostic) = store.get_mut(diagnostic_id) {
+                    diagnostic.add_measurement(DiagnosticMeasurement {
+                        time: now,
+                        value: time.as_secs_f64() * 1000.,
+                    });
+                }
+            }
+        },
+    );
+}
@@ -0,0 +1,122 @@
+use bevy::{
+    diagnostic::DiagnosticsStore,
+    prelude::{App, Plugin, Update, With, World},
+    window::PrimaryWindow,
+};
+use bevy_inspector_egui::{
+    bevy_egui::{EguiContext, EguiPlugin},
+    egui,
+};
+
+use crate::{
+    debug::BehaviourStatusHistory,
+    diagnostics::BehaviourTreeDiagnosticsPlugin,
+    plugin::{BehaviourNameRegistry, BehaviourTrees},
+};
+
+/// Shows all trees in an egui window, e.g. next to the `bevy_inspector_egui` world inspector.
+///
+/// Each tree is listed with its name from the [`BehaviourNameRegistry`], its [type name][crate::behaviour::Behaviour::type_name] and the entities using it.
+/// Entities show the last status they returned, if the [`BehaviourHistoryPlugin`][crate::debug::BehaviourHistoryPlugin] records them,
+/// along with their [debug state][crate::behaviour::Behaviour::debug_state].
+/// Trees also show how long they take to run, if [diagnostics][crate::plugin::BehaviourTreePlugin::with_diagnostics] are enabled.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, inspector::BehaviourTreeInspectorPlugin};
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(BehaviourTreePlugin::default().with_diagnostics())
+///     .add_plugins(BehaviourTreeInspectorPlugin)
+///     .run();
+/// ```
+///
+/// The panel is read-only; trees can't be edited through it.
+#[derive(Default)]
+pub struct BehaviourTreeInspectorPlugin;
+
+impl Plugin for BehaviourTreeInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+
+        app.add_systems(Update, inspector_ui);
+    }
+}
+
+fn inspector_ui(world: &mut World) {
+    let Ok(context) = world
+        .query_filtered::<&mut EguiContext, With<PrimaryWindow>>()
+        .get_single(world)
+    else {
+        return;
+    };
+    let mut context = context.clone();
+
+    egui::Window::new("Behaviour trees")
+        .default_size((320., 240.))
+        .show(context.get_mut(), |ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| trees_ui(world, ui));
+        });
+}
+
+fn trees_ui(world: &World, ui: &mut egui::Ui) {
+    let Some(trees) = world.get_resource::<BehaviourTrees>() else {
+        ui.label("No BehaviourTrees resource.");
+        return;
+    };
+    let names = world.get_resource::<BehaviourNameRegistry>();
+    let diagnostics = world.get_resource::<DiagnosticsStore>();
+
+    if trees.is_empty() {
+        ui.label("No trees.");
+    }
+
+    for (id, tree) in trees.stored() {
+        let title = match names.and_then(|names| names.name(id)) {
+            Some(name) => format!("{id} {name}"),
+            None => id.to_string(),
+        };
+
+        egui::CollapsingHeader::new(title)
+            .id_source(id)
+            .show(ui, |ui| {
+                ui.label(tree.type_name());
+
+                let entities = trees.entities_for(id, world);
+                let mut info = format!("{} entities", entities.len());
+                if let Some(time) = diagnostics
+                    .and_then(|store| store.get(BehaviourTreeDiagnosticsPlugin::diagnostic_id(id)))
+                    .and_then(|diagnostic| diagnostic.average())
+                {
+                    info.push_str(&format!(", {time:.3} ms"));
+                }
+                if trees.is_paused(id) {
+                    info.push_str(", paused");
+                }
+                ui.label(info);
+
+                for entity in entities {
+                    let status = world
+                        .get::<BehaviourStatusHistory>(entity)
+                        .and_then(|history| {
+                            history
+                                .0
+                                .iter()
+                                .rev()
+                                .find(|(history_id, ..)| *history_id == id)
+                        })
+                        .map(|(_, status, _)| format!("{status:?}"))
+                        .unwrap_or_else(|| String::from("-"));
+
+                    let mut line = format!("{entity:?}: {status}");
+                    let state = tree.debug_state(entity);
+                    if !state.is_empty() {
+                        line.push_str(&format!(" [{state}]"));
+                    }
+                    ui.label(line);
+                }
+            });
+    }
+}
@@ -0,0 +1,218 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::event::ManualEventReader,
+    prelude::{Bundle, Entity, Event, Events, World},
+    utils::HashMap,
+};
+
+use crate::{
+    behaviour::{IntoBehaviour, SelfMarker, ValidationError},
+    plugin::{tick, BehaviourTrees},
+    prelude::{Behaviour, BehaviourId, Status},
+};
+
+/// Inserts a clone of the bundle into the entity.
+///
+/// **Succeeds** immediately. **Fails** if the entity doesn't exist.
+#[derive(Clone)]
+pub struct Insert<B: Bundle + Clone>(pub B);
+
+impl<B: Bundle + Clone> IntoBehaviour<SelfMarker> for Insert<B> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<B: Bundle + Clone> Behaviour for Insert<B> {
+    fn initialize(&mut self, _: &mut World) {}
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let Some(mut entity) = world.get_entity_mut(entity) else {
+            return Status::Failure;
+        };
+
+        entity.insert(self.0.clone());
+        Status::Success
+    }
+}
+
+/// Removes the bundle `B` from the entity.
+///
+/// **Succeeds** immediately, even if the entity didn't have `B`. **Fails** if the entity doesn't exist.
+pub struct Remove<B: Bundle>(PhantomData<fn() -> B>);
+
+impl<B: Bundle> Remove<B> {
+    /// Removes `B`.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<B: Bundle> Default for Remove<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Bundle> Clone for Remove<B> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Bundle> IntoBehaviour<SelfMarker> for Remove<B> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<B: Bundle> Behaviour for Remove<B> {
+    fn initialize(&mut self, _: &mut World) {}
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let Some(mut entity) = world.get_entity_mut(entity) else {
+            return Status::Failure;
+        };
+
+        entity.remove::<B>();
+        Status::Success
+    }
+}
+
+/// Despawns the entity.
+///
+/// **Succeeds** immediately. **Fails** if the entity doesn't exist.
+#[derive(Clone, Copy, Default)]
+pub struct Despawn;
+
+impl IntoBehaviour<SelfMarker> for Despawn {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for Despawn {
+    fn initialize(&mut self, _: &mut World) {}
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        world.despawn(entity).into()
+    }
+}
+
+/// Sends a clone of the event.
+///
+/// **Succeeds** immediately. **Fails** if the event type hasn't been [added][bevy::prelude::App::add_event].
+#[derive(Clone)]
+pub struct SendEvent<E: Event + Clone>(pub E);
+
+impl<E: Event + Clone> IntoBehaviour<SelfMarker> for SendEvent<E> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<E: Event + Clone> Behaviour for SendEvent<E> {
+    fn initialize(&mut self, _: &mut World) {}
+
+    #[inline]
+    fn run(&mut self, _: Entity, world: &mut World) -> Status {
+        let Some(mut events) = world.get_resource_mut::<Events<E>>() else {
+            return Status::Failure;
+        };
+
+        events.send(self.0.clone());
+        Status::Success
+    }
+}
+
+/// Waits for an `E` event sent after the entity started waiting.
+///
+/// **Succeeds** once such an event is received. **Fails** if the event type hasn't been [added][bevy::prelude::App::add_event].
+pub struct WaitForEvent<E: Event> {
+    readers: HashMap<Entity, ManualEventReader<E>>,
+}
+
+impl<E: Event> WaitForEvent<E> {
+    /// Waits for `E`.
+    pub fn new() -> Self {
+        Self {
+            readers: HashMap::default(),
+        }
+    }
+}
+
+impl<E: Event> Default for WaitForEvent<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Event> Clone for WaitForEvent<E> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Event> IntoBehaviour<SelfMarker> for WaitForEvent<E> {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl<E: Event> Behaviour for WaitForEvent<E> {
+    fn initialize(&mut self, _: &mut World) {}
+
+    fn reset_entity(&mut self, entity: Entity) {
+        self.readers.remove(&entity);
+    }
+
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        let Some(events) = world.get_resource::<Events<E>>() else {
+            return Status::Failure;
+        };
+
+        let Some(reader) = self.readers.get_mut(&entity) else {
+            // only events sent from now on count.
+            self.readers.insert(entity, events.get_reader_current());
+            return Status::Running;
+        };
+
+        if reader.iter(events).next().is_some() {
+            self.readers.remove(&entity);
+            Status::Success
+        } else {
+            Status::Running
+        }
+    }
+}
+
+/// Runs another tree from [`BehaviourTrees`][crate::plugin::BehaviourTrees], so trees can be reused as parts of others.
+///
+/// **Succeeds or fails** depending on the other tree.
+/// **Fails** if the other tree doesn't exist, is already running (like when a tree contains itself), or is nested too deeply (see [`TreeDepthGuard`][crate::plugin::TreeDepthGuard]).
+#[derive(Clone, Copy, Debug)]
+pub struct SubtreeBehaviour(pub BehaviourId);
+
+impl IntoBehaviour<SelfMarker> for SubtreeBehaviour {
+    fn into_behaviour(self) -> impl Behaviour {
+        self
+    }
+}
+
+impl Behaviour for SubtreeBehaviour {
+    // the other tree is initialized the first time it runs.
+    fn initialize(&mut self, _: &mut World) {}
+
+    fn validate(&self, trees: &BehaviourTrees) -> Result<(), ValidationError> {
+        trees.validate_subtree(self.0)
+    }
+
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        tick(world, entity, self.0).unwrap_or(Status::Failure)
+    }
+}
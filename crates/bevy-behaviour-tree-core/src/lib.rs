@@ -1,15 +1,31 @@
 //! bevy-behaviour-tree is a crate for defining simple, composable, and extensible behaviour trees for [bevy].
 #![warn(missing_docs)]
 #![allow(clippy::type_complexity)]
-#![feature(return_position_impl_trait_in_trait)] // this may be avoidable.
-#![feature(associated_type_bounds)]
 
+/// Loading trees from asset files. Requires the `assets` feature.
+#[cfg(feature = "assets")]
+pub mod assets;
 /// Basic [`Behaviour`][behaviour::Behaviour] trait and impls.
 pub mod behaviour;
+/// State shared between trees.
+pub mod blackboard;
 /// Compositor behaviour impls.
 pub mod compositor;
+/// Common conditions, for use with decorators like [`retry_while`][decorator::Decorator::retry_while].
+pub mod conditions;
+/// Tools for debugging trees.
+pub mod debug;
 /// Decorator behaviour impls.
 pub mod decorator;
+/// Per-tree timing diagnostics.
+pub mod diagnostics;
+/// A read-only inspector panel for trees. Requires the `inspector` feature.
+#[cfg(feature = "inspector")]
+pub mod inspector;
+/// Common leaf behaviours.
+pub mod leaves;
+/// Trees for behaviours that aren't `Send + Sync`.
+pub mod local;
 /// The actual plugin and related stuff.
 pub mod plugin;
 
@@ -17,10 +33,28 @@ pub mod plugin;
 ///
 /// Best used as `use bevy_behaviour_tree::prelude::*`.
 pub mod prelude {
-    pub use super::behaviour::{Behaviour, Status};
-    pub use super::compositor::Compositor;
-    pub use super::decorator::Decorator;
-    pub use super::plugin::{BehaviourId, BehaviourTreePlugin, BehaviourTrees, Skip};
+    pub use super::behaviour::{
+        always_fail, always_succeed, stateful, Behaviour, BehaviourError, BehaviourErrors, Status,
+    };
+    pub use super::blackboard::{
+        GlobalBlackboard, GlobalBlackboardCheck, GlobalBlackboardSet, SharedState, TypedBlackboard,
+    };
+    pub use super::compositor::{
+        if_then, parallel_all, parallel_any, repeat_sequence_until, scoped, Compositor,
+        UtilityCompositor, WeightedCompositor,
+    };
+    // `resource_exists` is left out on purpose; it'd clash with bevy's prelude.
+    pub use super::conditions::{
+        children_all_have, children_any_have, event_received, has_component, predicate,
+        world_condition,
+    };
+    // `not` is left out as well, for the same reason.
+    pub use super::decorator::{try_if, unless, when, Decorator};
+    pub use super::leaves::{Despawn, Insert, Remove, SendEvent, SubtreeBehaviour, WaitForEvent};
+    pub use super::plugin::{
+        BehaviourId, BehaviourIds, BehaviourTreePlugin, BehaviourTreeSet, BehaviourTrees,
+        EntityBehaviourTree, Skip,
+    };
 }
 
 /// For debug purposes only. Panics if used in any way.
@@ -45,9 +79,14 @@ impl behaviour::Behaviour for TodoBehaviour {
 }
 #[cfg(test)]
 mod tests {
-    use bevy::prelude::{Component, Entity, In, Query, World};
+    use bevy::prelude::{Component, Entity, In, Query, With, World};
 
-    use crate::prelude::*;
+    use crate::{
+        behaviour::{BehaviourState, Constant},
+        plugin::{tick, BehaviourTypeRegistry},
+        prelude::*,
+        TodoBehaviour,
+    };
 
     fn succeed(In(_): In<Entity>) -> Status {
         Status::Success
@@ -61,6 +100,27 @@ mod tests {
         panic!(":(");
     }
 
+    #[derive(Component)]
+    struct Runs(usize);
+
+    // succeeds, counting its runs in the entity's `Runs`.
+    fn count(In(entity): In<Entity>, mut runs: Query<&mut Runs>) -> Status {
+        runs.get_mut(entity).unwrap().0 += 1;
+        Status::Success
+    }
+
+    #[derive(Component, Default)]
+    struct BranchRuns([usize; 3]);
+
+    // like `count`, for telling up to three branches apart.
+    fn count_branch<const N: usize>(
+        In(entity): In<Entity>,
+        mut runs: Query<&mut BranchRuns>,
+    ) -> Status {
+        runs.get_mut(entity).unwrap().0[N] += 1;
+        Status::Success
+    }
+
     #[test]
     fn test_invert() {
         let mut world = World::default();
@@ -107,6 +167,27 @@ mod tests {
         assert_eq!(counter.0, 3);
     }
 
+    #[test]
+    fn test_repeat() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        // failures count as runs, too.
+        let mut repeat = fail.repeat(3);
+        repeat.initialize(&mut world);
+        for _ in 0..2 {
+            assert_eq!(repeat.run(entity, &mut world), Status::Running);
+        }
+        assert_eq!(repeat.run(entity, &mut world), Status::Success);
+
+        // and it starts over afterwards.
+        assert_eq!(repeat.run(entity, &mut world), Status::Running);
+
+        let mut never = panic_if_run.repeat(0);
+        never.initialize(&mut world);
+        assert_eq!(never.run(entity, &mut world), Status::Success);
+    }
+
     #[test]
     fn test_retry_while() {
         let mut world = World::default();
@@ -212,4 +293,2287 @@ mod tests {
         let has_run = world.query::<&HasRun>().get(&world, entity).unwrap();
         assert!(has_run.0, "select system did not run");
     }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut world = World::default();
+        let mut trees = BehaviourTrees::default();
+
+        let id = trees.create(fail.retry(5));
+        let entity = world.spawn(id).id();
+
+        let tick = |trees: &mut BehaviourTrees, world: &mut World| {
+            let mut status = Status::Running;
+            trees.behaviour_scope(id, |_, behaviour| {
+                behaviour.initialize(world);
+                status = behaviour.run(entity, world);
+            });
+            status
+        };
+
+        tick(&mut trees, &mut world);
+        tick(&mut trees, &mut world);
+
+        let snapshot = trees.snapshot();
+        let tries = snapshot.get(id).unwrap().entities[&entity];
+
+        tick(&mut trees, &mut world);
+        assert_ne!(trees.snapshot(), snapshot);
+
+        trees.restore(&snapshot);
+        assert_eq!(trees.snapshot(), snapshot);
+
+        // the retry counter picks up where the snapshot left off.
+        for _ in tries + 1..5 {
+            assert_eq!(tick(&mut trees, &mut world), Status::Running);
+        }
+        assert_eq!(tick(&mut trees, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_restore_with_registry() {
+        let mut trees = BehaviourTrees::default();
+        let id = trees.create(TodoBehaviour);
+        let snapshot = trees.snapshot();
+
+        let mut registry = BehaviourTypeRegistry::default();
+        registry.register_type::<TodoBehaviour>(|_| Box::new(TodoBehaviour));
+
+        let mut restored = BehaviourTrees::default();
+        restored.restore_with_registry(&snapshot, &registry);
+
+        assert_eq!(restored.snapshot(), snapshot);
+        assert!(restored.snapshot().get(id).is_some());
+    }
+
+    #[test]
+    fn test_restore_built_ins() {
+        let mut trees = BehaviourTrees::default();
+        let despawn = trees.create(Despawn);
+        let paused = trees.create(TodoBehaviour);
+        trees.pause(paused);
+        let snapshot = trees.snapshot();
+
+        let mut registry = BehaviourTypeRegistry::default();
+        registry.register_type::<TodoBehaviour>(|state| {
+            assert_eq!(*state, BehaviourState::default());
+            Box::new(TodoBehaviour)
+        });
+
+        let mut restored = BehaviourTrees::default();
+        restored.restore_with_registry(&snapshot, &registry);
+
+        assert!(restored.contains(despawn));
+        assert!(restored.is_paused(paused));
+        assert!(!restored.is_paused(despawn));
+    }
+
+    #[test]
+    fn test_snapshot_versions() {
+        let mut registry = BehaviourTypeRegistry::default();
+        registry.register_type::<TodoBehaviour>(|_| Box::new(TodoBehaviour));
+
+        let mut trees = BehaviourTrees::default();
+        let removed = trees.create(TodoBehaviour);
+        trees.remove(removed);
+        let id = trees.create(TodoBehaviour);
+        let snapshot = trees.snapshot();
+
+        // reused slots keep their version.
+        let mut restored = BehaviourTrees::default();
+        restored.restore_with_registry(&snapshot, &registry);
+        assert!(restored.is_valid(id));
+        assert!(!restored.is_valid(removed));
+
+        // slots emptied after the snapshot aren't rolled back.
+        trees.remove(id);
+        trees.restore_with_registry(&snapshot, &registry);
+        assert!(!trees.contains(id));
+
+        // a tree created in the slot after the snapshot doesn't get the old tree's state.
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut trees = BehaviourTrees::default();
+        let id = trees.create(fail.retry(5));
+        trees.tick_entity(entity, id, &mut world);
+        let snapshot = trees.snapshot();
+
+        trees.remove(id);
+        let newer = trees.create(fail.retry(5));
+        assert!(snapshot.get(newer).is_none());
+
+        trees.restore(&snapshot);
+        assert!(trees.snapshot().get(newer).unwrap().entities.is_empty());
+
+        // nor does a tree of another type that replaced it under the same ID.
+        trees.tick_entity(entity, newer, &mut world);
+        let snapshot = trees.snapshot();
+
+        trees.replace(newer, (fail, succeed).sequence());
+        trees.restore(&snapshot);
+        assert!(trees.snapshot().get(newer).unwrap().entities.is_empty());
+    }
+
+    #[test]
+    fn test_replace_stale_id() {
+        let mut trees = BehaviourTrees::default();
+        let old = trees.create(TodoBehaviour);
+        trees.remove(old);
+        let new = trees.create(TodoBehaviour);
+
+        // the stale ID doesn't take over the slot again.
+        assert!(trees.replace(old, TodoBehaviour).is_some());
+        assert!(!trees.is_valid(old));
+        assert!(trees.is_valid(new));
+
+        let moved = trees.create(TodoBehaviour);
+        trees.move_to(moved, old);
+        assert!(trees.contains(moved));
+        assert!(trees.is_valid(new));
+    }
+
+    #[test]
+    fn test_select_new_entities() {
+        let mut world = World::default();
+
+        let mut selected = Compositor::select((fail, fail));
+        selected.initialize(&mut world);
+
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+
+        assert_eq!(selected.run(first, &mut world), Status::Running);
+        assert_eq!(selected.save_state().entities.get(&first), Some(&1));
+        assert!(
+            !selected.save_state().entities.contains_key(&second),
+            "untouched entity has state"
+        );
+
+        assert_eq!(selected.run(first, &mut world), Status::Running);
+        assert_eq!(selected.run(first, &mut world), Status::Failure);
+        assert_eq!(selected.save_state().entities.get(&first), Some(&0));
+
+        // a second entity encountering the select for the first time starts from the beginning.
+        assert_eq!(selected.run(second, &mut world), Status::Running);
+        assert_eq!(selected.run(second, &mut world), Status::Running);
+        assert_eq!(selected.run(second, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_select_new_entity_succeeds() {
+        let mut world = World::default();
+
+        let mut selected = Compositor::select((succeed, panic_if_run));
+        selected.initialize(&mut world);
+
+        let entity = world.spawn_empty().id();
+
+        assert_eq!(selected.run(entity, &mut world), Status::Success);
+        assert_eq!(selected.save_state().entities.get(&entity), Some(&0));
+    }
+
+    #[test]
+    fn test_weight_by_component() {
+        use crate::compositor::weight_by_component;
+
+        let mut world = World::default();
+
+        #[derive(Component)]
+        struct Urge(f32);
+
+        #[derive(Component, Default)]
+        struct Picked([u32; 2]);
+
+        let mut selected = (
+            (
+                weight_by_component::<Urge, _>(|urge: &Urge| urge.0),
+                |In(entity): In<Entity>, mut picked: Query<&mut Picked>| {
+                    picked.get_mut(entity).unwrap().0[0] += 1;
+                    Status::Success
+                },
+            ),
+            (
+                weight_by_component::<Urge, _>(|urge: &Urge| 1. - urge.0),
+                |In(entity): In<Entity>, mut picked: Query<&mut Picked>| {
+                    picked.get_mut(entity).unwrap().0[1] += 1;
+                    Status::Success
+                },
+            ),
+        )
+            .weighted_select();
+
+        selected.initialize(&mut world);
+
+        let entity = world.spawn((Urge(1.), Picked::default())).id();
+
+        for _ in 0..20 {
+            assert_eq!(selected.run(entity, &mut world), Status::Success);
+        }
+        assert_eq!(world.get::<Picked>(entity).unwrap().0, [20, 0]);
+
+        world.get_mut::<Urge>(entity).unwrap().0 = 0.;
+
+        for _ in 0..20 {
+            assert_eq!(selected.run(entity, &mut world), Status::Success);
+        }
+        assert_eq!(world.get::<Picked>(entity).unwrap().0, [20, 20]);
+
+        // without the component, no branch can be picked.
+        world.entity_mut(entity).remove::<Urge>();
+        assert_eq!(selected.run(entity, &mut world), Status::Failure);
+
+        // branches past the last one are dropped instead of panicking.
+        selected.load_state(&BehaviourState {
+            entities: [(entity, 2)].into_iter().collect(),
+            children: Vec::new(),
+        });
+        assert!(selected.save_state().entities.is_empty());
+        assert_eq!(selected.run(entity, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_into_system() {
+        use crate::behaviour::into_system;
+        use bevy::prelude::{IntoSystem, ResMut, Resource, System};
+
+        #[derive(Resource, Default)]
+        struct StatusLog(Vec<Status>);
+
+        let mut world = World::default();
+        world.init_resource::<StatusLog>();
+
+        let mut system = into_system((fail, succeed).select().invert()).pipe(
+            |In(status): In<Status>, mut log: ResMut<StatusLog>| {
+                log.0.push(status);
+            },
+        );
+
+        system.initialize(&mut world);
+
+        let entity = world.spawn_empty().id();
+
+        system.run(entity, &mut world);
+        system.run(entity, &mut world);
+        system.apply_deferred(&mut world);
+
+        assert_eq!(
+            world.resource::<StatusLog>().0,
+            vec![Status::Running, Status::Failure]
+        );
+    }
+
+    #[test]
+    fn test_constants() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut sequence = (always_succeed(), always_fail(), panic_if_run).sequence();
+        sequence.initialize(&mut world);
+
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.run(entity, &mut world), Status::Failure);
+
+        let mut select = (always_fail(), always_succeed(), panic_if_run).select();
+        select.initialize(&mut world);
+
+        assert_eq!(select.run(entity, &mut world), Status::Running);
+        assert_eq!(select.run(entity, &mut world), Status::Success);
+    }
+
+    #[test]
+    fn test_single_element_tuples() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        for status in [Status::Success, Status::Failure, Status::Running] {
+            let mut sequence = Compositor::sequence((Constant(status),));
+            sequence.initialize(&mut world);
+            assert_eq!(sequence.run(entity, &mut world), status);
+
+            let mut select = Compositor::select((Constant(status),));
+            select.initialize(&mut world);
+            assert_eq!(select.run(entity, &mut world), status);
+        }
+    }
+
+    #[test]
+    fn test_compositor_introspection() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut sequence = (succeed, succeed, succeed).sequence();
+        sequence.initialize(&mut world);
+
+        assert_eq!(sequence.len(), 3);
+        assert_eq!(sequence.current_index(entity), 0);
+
+        sequence.run(entity, &mut world);
+        assert_eq!(sequence.current_index(entity), 1);
+
+        let mut select = (fail, fail).select();
+        select.initialize(&mut world);
+
+        assert_eq!(select.len(), 2);
+        assert_eq!(select.current_index(entity), 0);
+
+        select.run(entity, &mut world);
+        assert_eq!(select.current_index(entity), 1);
+    }
+
+    #[test]
+    fn test_result_errors() {
+        use crate::behaviour::IntoBehaviour;
+
+        let mut world = World::default();
+
+        let mut behaviour =
+            IntoBehaviour::into_behaviour(|In(_): In<Entity>| -> Result<Status, BehaviourError> {
+                Err(BehaviourError::Custom("test".into()))
+            });
+
+        behaviour.initialize(&mut world);
+
+        let entity = world.spawn_empty().id();
+
+        assert_eq!(behaviour.run(entity, &mut world), Status::Failure);
+
+        let errors = world.resource::<BehaviourErrors>();
+        assert_eq!(
+            errors.iter().collect::<Vec<_>>(),
+            vec![&(entity, BehaviourError::Custom("test".into()))]
+        );
+
+        let mut succeeding =
+            IntoBehaviour::into_behaviour(|In(_): In<Entity>| Ok::<_, &str>(Status::Success));
+        succeeding.initialize(&mut world);
+
+        assert_eq!(succeeding.run(entity, &mut world), Status::Success);
+        assert_eq!(world.resource::<BehaviourErrors>().len(), 1);
+    }
+
+    #[test]
+    fn test_latch() {
+        let mut world = World::default();
+        let mut trees = BehaviourTrees::default();
+
+        let id = trees.create(count.latch());
+        world.insert_resource(trees);
+        let entity = world.spawn(Runs(0)).id();
+
+        for _ in 0..3 {
+            assert_eq!(tick(&mut world, entity, id), Some(Status::Success));
+        }
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 1);
+
+        world
+            .resource_mut::<BehaviourTrees>()
+            .reset_for_entity(id, entity);
+
+        assert_eq!(tick(&mut world, entity, id), Some(Status::Success));
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 2);
+
+        // failures latch too, and `Running` never does.
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut running = Constant(Status::Running).latch();
+        running.initialize(&mut world);
+        assert_eq!(running.run(entity, &mut world), Status::Running);
+
+        let mut failing = (fail, fail).select().latch();
+        failing.initialize(&mut world);
+        assert_eq!(failing.run(entity, &mut world), Status::Running);
+        assert_eq!(failing.run(entity, &mut world), Status::Running);
+        assert_eq!(failing.run(entity, &mut world), Status::Failure);
+        assert_eq!(failing.run(entity, &mut world), Status::Failure);
+
+        // restored copies stay latched.
+        let mut restored = (fail, fail).select().latch();
+        restored.initialize(&mut world);
+        restored.load_state(&failing.save_state());
+        assert_eq!(restored.run(entity, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_named() {
+        use crate::behaviour::IntoBehaviour;
+
+        let named = always_succeed().with_name("succeed");
+        assert_eq!(named.type_name(), "succeed");
+        assert_eq!(named.clone().type_name(), "succeed");
+
+        let renamed = IntoBehaviour::into_behaviour(fail)
+            .with_name("fail")
+            .retry(3)
+            .with_name("fail_with_retry");
+        assert_eq!(renamed.type_name(), "fail_with_retry");
+
+        let mut trees = BehaviourTrees::default();
+        let id = trees.create(named);
+        assert_eq!(trees.type_name(id), Some("succeed"));
+    }
+
+    #[test]
+    fn test_map_entity() {
+        use crate::behaviour::{BehaviourShape, IntoBehaviour};
+
+        #[derive(Component)]
+        struct Subordinate(Entity);
+
+        #[derive(Component)]
+        struct Ordered;
+
+        fn order(In(entity): In<Entity>, query: Query<(), With<Ordered>>) -> Status {
+            query.contains(entity).into()
+        }
+
+        let mut world = World::default();
+
+        let subordinate = world.spawn(Ordered).id();
+        let commander = world.spawn(Subordinate(subordinate)).id();
+
+        let mut behaviour = order.map_entity(|entity, world| {
+            world
+                .get::<Subordinate>(entity)
+                .map_or(entity, |subordinate| subordinate.0)
+        });
+        behaviour.initialize(&mut world);
+
+        // the commander itself isn't `Ordered`, so this only succeeds if the subordinate was checked.
+        assert_eq!(behaviour.run(commander, &mut world), Status::Success);
+
+        let mut unmapped = order.map_entity(|entity, _| entity);
+        unmapped.initialize(&mut world);
+        assert_eq!(unmapped.run(commander, &mut world), Status::Failure);
+
+        // everything but the entity is forwarded to the child.
+        fn read_subordinate(In(_): In<Entity>, _: Query<&Subordinate>) -> Status {
+            Status::Success
+        }
+
+        let mut mapped = read_subordinate.map_entity(|entity, _| entity);
+        let mut child = IntoBehaviour::into_behaviour(read_subordinate);
+        mapped.initialize(&mut world);
+        child.initialize(&mut world);
+        assert!(!mapped.dependencies().is_empty());
+        assert_eq!(mapped.dependencies(), child.dependencies());
+        assert_eq!(
+            mapped.shape(),
+            BehaviourShape::node("map_entity", "", vec![child.shape()])
+        );
+
+        let missing = SubtreeBehaviour(BehaviourId::null()).map_entity(|entity, _| entity);
+        assert!(missing.validate(&BehaviourTrees::default()).is_err());
+    }
+
+    #[test]
+    fn test_compositor_conversion() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut sequence = (succeed, succeed, fail).sequence();
+        sequence.initialize(&mut world);
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+
+        let mut select = sequence.into_select();
+        assert_eq!(select.current_index(entity), 1);
+        assert_eq!(select.run(entity, &mut world), Status::Success);
+
+        let mut sequence = select.into_sequence();
+        assert_eq!(sequence.current_index(entity), 0);
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.current_index(entity), 1);
+    }
+
+    #[test]
+    fn test_status_ordering() {
+        use std::collections::BTreeMap;
+
+        let statuses = [Status::Success, Status::Running, Status::Failure]
+            .into_iter()
+            .map(|status| (status, ()))
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(
+            statuses.into_keys().collect::<Vec<_>>(),
+            vec![Status::Failure, Status::Running, Status::Success]
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut trees = BehaviourTrees::default();
+        let ids = (0..10)
+            .map(|index| trees.create(Constant(Status::from(index % 2 == 0))))
+            .collect::<Vec<_>>();
+
+        // removes every tree that fails, leaving only ones that succeed.
+        for id in ids.iter().skip(1).step_by(2) {
+            assert!(trees.remove(*id).is_some());
+        }
+        assert!(trees.remove(ids[1]).is_none());
+
+        let moved = trees.compact();
+        assert_eq!(moved.len(), 4);
+
+        let remaining = ids
+            .iter()
+            .step_by(2)
+            .map(|id| moved.get(id).copied().unwrap_or(*id))
+            .collect::<Vec<_>>();
+
+        assert!(!trees.contains(ids[5]));
+        world.insert_resource(trees);
+
+        for id in &remaining {
+            assert!(world.resource::<BehaviourTrees>().contains(*id));
+            assert_eq!(tick(&mut world, entity, *id), Some(Status::Success));
+        }
+
+        let mut trees = world.resource_mut::<BehaviourTrees>();
+        trees.resize(100);
+        assert!(trees.capacity() >= 100);
+        trees.resize(0);
+        assert!(remaining.iter().all(|id| trees.contains(*id)));
+    }
+
+    #[test]
+    fn test_parallel() {
+        use crate::behaviour::IntoBehaviour;
+
+        fn running(_: In<Entity>) -> Status {
+            Status::Running
+        }
+
+        fn boxed<M>(behaviour: impl IntoBehaviour<M>) -> Box<dyn Behaviour> {
+            Box::new(behaviour.into_behaviour())
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut all_running = parallel_all(vec![boxed(running), boxed(running)]);
+        let mut any_running = parallel_any(vec![boxed(running), boxed(running)]);
+        all_running.initialize(&mut world);
+        any_running.initialize(&mut world);
+        assert_eq!(all_running.run(entity, &mut world), Status::Running);
+        assert_eq!(any_running.run(entity, &mut world), Status::Running);
+
+        let mut all = parallel_all(vec![boxed(succeed), boxed(running), boxed(fail)]);
+        all.initialize(&mut world);
+        assert_eq!(all.run(entity, &mut world), Status::Failure);
+
+        let mut all = parallel_all(vec![boxed(succeed), boxed(succeed)]);
+        all.initialize(&mut world);
+        assert_eq!(all.run(entity, &mut world), Status::Success);
+
+        let mut any = parallel_any(vec![boxed(fail), boxed(running), boxed(succeed)]);
+        any.initialize(&mut world);
+        assert_eq!(any.run(entity, &mut world), Status::Success);
+
+        // finished children aren't run again.
+        let fail_once = |_: In<Entity>, mut ran: bevy::prelude::Local<bool>| {
+            assert!(!*ran, "finished child ran again");
+            *ran = true;
+            Status::Failure
+        };
+        let mut any = (fail_once, running).parallel(crate::compositor::ParallelPolicy::RequireOne);
+        any.initialize(&mut world);
+        assert_eq!(any.run(entity, &mut world), Status::Running);
+        assert_eq!(any.run(entity, &mut world), Status::Running);
+    }
+
+    #[test]
+    fn test_count_successes() {
+        use crate::decorator::Counter;
+
+        let mut world = World::default();
+        let entity = world.spawn(Counter(0)).id();
+        let uncounted = world.spawn_empty().id();
+
+        let mut behaviour = (succeed, fail).select().count_successes::<Counter>();
+        behaviour.initialize(&mut world);
+
+        for _ in 0..3 {
+            assert_eq!(behaviour.run(entity, &mut world), Status::Success);
+        }
+        assert_eq!(behaviour.run(uncounted, &mut world), Status::Success);
+
+        let mut failing = fail.count_successes::<Counter>();
+        failing.initialize(&mut world);
+        assert_eq!(failing.run(entity, &mut world), Status::Failure);
+
+        assert_eq!(world.get::<Counter>(entity), Some(&Counter(3)));
+    }
+
+    #[test]
+    fn test_run_n_times_per_tick() {
+        fn count_to_ten(In(entity): In<Entity>, mut query: Query<&mut Runs>) -> Status {
+            let mut runs = query.get_mut(entity).unwrap();
+            runs.0 += 1;
+            if runs.0 < 10 {
+                Status::Running
+            } else {
+                Status::Success
+            }
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn(Runs(0)).id();
+
+        let mut behaviour = count_to_ten.run_n_times_per_tick(4);
+        behaviour.initialize(&mut world);
+
+        assert_eq!(behaviour.run(entity, &mut world), Status::Running);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 4);
+
+        assert_eq!(behaviour.run(entity, &mut world), Status::Running);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 8);
+
+        // stops as soon as the child finishes.
+        assert_eq!(behaviour.run(entity, &mut world), Status::Success);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 10);
+    }
+
+    #[test]
+    fn test_stateful() {
+        let mut world = World::default();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+
+        let mut behaviour = stateful(|_: Entity, _: &mut World, ticks: &mut u32| {
+            *ticks += 1;
+            *ticks == 2
+        });
+        behaviour.initialize(&mut world);
+
+        assert_eq!(behaviour.run(first, &mut world), Status::Failure);
+        assert_eq!(behaviour.run(first, &mut world), Status::Success);
+        assert_eq!(behaviour.run(second, &mut world), Status::Failure);
+        assert_eq!(behaviour.run(first, &mut world), Status::Failure);
+        assert_eq!(behaviour.run(second, &mut world), Status::Success);
+
+        behaviour.reset_entity(first);
+        assert_eq!(behaviour.run(first, &mut world), Status::Failure);
+        assert_eq!(behaviour.run(first, &mut world), Status::Success);
+    }
+
+    #[test]
+    fn test_children_conditions() {
+        use bevy::prelude::BuildWorldChildren;
+
+        use crate::behaviour::IntoBehaviour;
+
+        #[derive(Component)]
+        struct InPosition;
+
+        let mut world = World::default();
+
+        let ready = world.spawn(InPosition).id();
+        let not_ready = world.spawn_empty().id();
+        let squad = world.spawn_empty().push_children(&[ready, not_ready]).id();
+        let also_ready = world.spawn(InPosition).id();
+        let ready_squad = world.spawn_empty().push_children(&[also_ready]).id();
+        let empty_squad = world.spawn_empty().id();
+
+        let mut all = IntoBehaviour::into_behaviour(children_all_have::<InPosition>());
+        let mut any = IntoBehaviour::into_behaviour(children_any_have::<InPosition>());
+        all.initialize(&mut world);
+        any.initialize(&mut world);
+
+        assert_eq!(all.run(squad, &mut world), Status::Failure);
+        assert_eq!(any.run(squad, &mut world), Status::Success);
+
+        assert_eq!(all.run(ready_squad, &mut world), Status::Success);
+
+        assert_eq!(all.run(empty_squad, &mut world), Status::Failure);
+        assert_eq!(any.run(empty_squad, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_subtree_depth() {
+        use crate::plugin::{BehaviourTreesSnapshot, TreeDepthGuard};
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut trees = BehaviourTrees::default();
+
+        // a tree containing itself fails instead of recursing.
+        let recursive = trees.create(always_succeed());
+        trees.replace(recursive, SubtreeBehaviour(recursive));
+
+        // each tree runs the one before it, so the last one is nested 5 deep.
+        let mut chain = trees.create(always_succeed());
+        for _ in 0..4 {
+            chain = trees.create(SubtreeBehaviour(chain));
+        }
+
+        world.insert_resource(trees);
+        world.insert_resource(TreeDepthGuard::new(5));
+
+        assert_eq!(tick(&mut world, entity, recursive), Some(Status::Failure));
+        assert_eq!(tick(&mut world, entity, chain), Some(Status::Success));
+
+        world.resource_mut::<TreeDepthGuard>().max_depth = 4;
+        assert_eq!(tick(&mut world, entity, chain), Some(Status::Failure));
+        assert_eq!(world.resource::<TreeDepthGuard>().depth(), 0);
+
+        // nothing got lost along the way.
+        let snapshot: BehaviourTreesSnapshot = world.resource::<BehaviourTrees>().snapshot();
+        assert!(snapshot.get(recursive).is_some() && snapshot.get(chain).is_some());
+    }
+
+    #[test]
+    fn test_warm_up_too_deep() {
+        use crate::plugin::TreeDepthGuard;
+
+        #[derive(Component)]
+        struct Warm;
+
+        struct WarmUp;
+
+        impl Behaviour for WarmUp {
+            fn initialize(&mut self, _: &mut World) {}
+
+            fn warm_up(&mut self, entity: Entity, world: &mut World) {
+                world.entity_mut(entity).insert(Warm);
+            }
+
+            fn run(&mut self, _: Entity, _: &mut World) -> Status {
+                Status::Success
+            }
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut trees = BehaviourTrees::default();
+        let id = trees.create(WarmUp);
+        world.insert_resource(trees);
+        world.insert_resource(TreeDepthGuard::new(0));
+
+        assert_eq!(tick(&mut world, entity, id), Some(Status::Failure));
+        assert!(!world.entity(entity).contains::<Warm>());
+
+        // the tree didn't run, so it's warmed up the first time it does.
+        world.resource_mut::<TreeDepthGuard>().max_depth = 1;
+        assert_eq!(tick(&mut world, entity, id), Some(Status::Success));
+        assert!(world.entity(entity).contains::<Warm>());
+    }
+
+    #[test]
+    fn test_debug_state() {
+        use crate::behaviour::IntoBehaviour;
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut retry = fail.retry(5);
+        retry.initialize(&mut world);
+        assert_eq!(retry.debug_state(entity), "tries: 0/5");
+
+        retry.load_state(&BehaviourState {
+            entities: [(entity, 2)].into_iter().collect(),
+            children: vec![BehaviourState::default()],
+        });
+        assert_eq!(retry.debug_state(entity), "tries: 2/5");
+
+        let mut sequence = (succeed, panic_if_run).sequence();
+        sequence.initialize(&mut world);
+        sequence.run(entity, &mut world);
+        assert_eq!(sequence.debug_state(entity), "index: 1");
+
+        // plain systems have no state to show.
+        assert_eq!(
+            IntoBehaviour::into_behaviour(succeed).debug_state(entity),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_drain_completed() {
+        use crate::{behaviour::IntoBehaviour, compositor::Sequence};
+
+        #[derive(Component)]
+        struct Step(u32);
+
+        fn advance(In(entity): In<Entity>, mut query: Query<&mut Step>) -> Status {
+            query.get_mut(entity).unwrap().0 += 1;
+            Status::Success
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn(Step(0)).id();
+
+        let mut sequence = Sequence::new(vec![
+            Box::new(IntoBehaviour::into_behaviour(advance)),
+            Box::new(IntoBehaviour::into_behaviour(advance)),
+            Box::new(IntoBehaviour::into_behaviour(|_: In<Entity>| {
+                Status::Running
+            })),
+        ]);
+        sequence.initialize(&mut world);
+
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.current_index(entity), 2);
+
+        assert_eq!(sequence.drain_completed(entity), 2);
+        assert_eq!(sequence.len(), 1);
+        assert_eq!(sequence.current_index(entity), 0);
+
+        // the finished steps aren't run again.
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(world.get::<Step>(entity).unwrap().0, 2);
+
+        assert_eq!(sequence.drain_completed(entity), 0);
+    }
+
+    #[test]
+    fn test_shared() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut first = fail.retry(2).shared();
+        let mut second = first.clone();
+        first.initialize(&mut world);
+        second.initialize(&mut world);
+
+        assert_eq!(first.run(entity, &mut world), Status::Running);
+        assert_eq!(second.run(entity, &mut world), Status::Running);
+        assert_eq!(first.debug_state(entity), "tries: 1/2");
+
+        // separate instances would both still be retrying.
+        assert_eq!(first.run(entity, &mut world), Status::Failure);
+        assert_eq!(second.debug_state(entity), "tries: 0/2");
+
+        #[derive(Component)]
+        struct Steps(u32);
+
+        fn step(In(entity): In<Entity>, mut steps: Query<&mut Steps>) -> Status {
+            steps.get_mut(entity).unwrap().0 += 1;
+            Status::Success
+        }
+
+        // initializing the system again for the second parent would conflict with its own mutable access.
+        let step = crate::behaviour::IntoBehaviour::into_behaviour(step).shared();
+        let mut walk = (step.clone(), step).sequence();
+        walk.initialize(&mut world);
+
+        let walker = world.spawn(Steps(0)).id();
+        while walk.run(walker, &mut world) == Status::Running {}
+        assert_eq!(world.get::<Steps>(walker).unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_when_unless() {
+        #[derive(Component)]
+        struct Ready;
+
+        let mut world = World::default();
+        let ready = world.spawn(Ready).id();
+        let waiting = world.spawn_empty().id();
+
+        let mut when = when(has_component::<Ready>(), succeed);
+        let mut unless = unless(has_component::<Ready>(), succeed);
+        let mut try_if = try_if(has_component::<Ready>(), fail);
+        when.initialize(&mut world);
+        unless.initialize(&mut world);
+        try_if.initialize(&mut world);
+
+        assert_eq!(when.run(ready, &mut world), Status::Success);
+        assert_eq!(when.run(waiting, &mut world), Status::Failure);
+
+        assert_eq!(unless.run(ready, &mut world), Status::Failure);
+        assert_eq!(unless.run(waiting, &mut world), Status::Success);
+
+        assert_eq!(try_if.run(ready, &mut world), Status::Failure);
+        assert_eq!(try_if.run(waiting, &mut world), Status::Success);
+    }
+
+    #[test]
+    fn test_tick_entity() {
+        let mut world = World::default();
+        let mut trees = BehaviourTrees::default();
+
+        let id = trees.create((succeed, fail).sequence());
+        let entity = world.spawn(id).id();
+
+        assert_eq!(
+            trees.tick_entity(entity, id, &mut world),
+            Some(Status::Running)
+        );
+        assert_eq!(
+            trees.tick_entity(entity, id, &mut world),
+            Some(Status::Failure)
+        );
+
+        trees.remove(id);
+        assert_eq!(trees.tick_entity(entity, id, &mut world), None);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_chain_alias() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut chained = (succeed, fail, panic_if_run).chain();
+        chained.initialize(&mut world);
+
+        assert_eq!(chained.len(), 3);
+        assert_eq!(chained.run(entity, &mut world), Status::Running);
+        assert_eq!(chained.run(entity, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_scoped() {
+        #[derive(Component, Default)]
+        struct Calls {
+            enter: u32,
+            body: u32,
+            exit: u32,
+        }
+
+        fn enter(In(entity): In<Entity>, mut query: Query<&mut Calls>) -> Status {
+            query.get_mut(entity).unwrap().enter += 1;
+            Status::Success
+        }
+
+        // runs once, then fails.
+        fn body(In(entity): In<Entity>, mut query: Query<&mut Calls>) -> Status {
+            let mut calls = query.get_mut(entity).unwrap();
+            calls.body += 1;
+            if calls.body < 2 {
+                Status::Running
+            } else {
+                Status::Failure
+            }
+        }
+
+        fn exit(In(entity): In<Entity>, mut query: Query<&mut Calls>) -> Status {
+            query.get_mut(entity).unwrap().exit += 1;
+            Status::Success
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn(Calls::default()).id();
+
+        let mut scope = scoped(enter, body, exit);
+        scope.initialize(&mut world);
+
+        assert_eq!(scope.run(entity, &mut world), Status::Running);
+        let calls = world.get::<Calls>(entity).unwrap();
+        assert_eq!((calls.enter, calls.body, calls.exit), (1, 1, 0));
+
+        assert_eq!(scope.run(entity, &mut world), Status::Failure);
+        let calls = world.get::<Calls>(entity).unwrap();
+        assert_eq!((calls.enter, calls.body, calls.exit), (1, 2, 1));
+
+        // the next run enters the scope again.
+        scope.run(entity, &mut world);
+        assert_eq!(world.get::<Calls>(entity).unwrap().enter, 2);
+    }
+
+    #[test]
+    fn test_closure_behaviour() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::behaviour::IntoBehaviour;
+
+        let mut world = World::default();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut record = IntoBehaviour::into_behaviour({
+            let seen = seen.clone();
+            move |entity: Entity, _: &mut World| {
+                seen.lock().unwrap().push(entity);
+                Status::Success
+            }
+        });
+        record.initialize(&mut world);
+
+        assert_eq!(record.run(first, &mut world), Status::Success);
+        assert_eq!(record.run(second, &mut world), Status::Success);
+        assert_eq!(*seen.lock().unwrap(), vec![first, second]);
+
+        // closures work with decorators like any other behaviour.
+        let mut despawned =
+            (|entity: Entity, world: &mut World| world.get_entity(entity).is_none()).invert();
+        despawned.initialize(&mut world);
+        assert_eq!(despawned.run(first, &mut world), Status::Success);
+    }
+
+    #[test]
+    fn test_memory() {
+        #[derive(Component)]
+        struct Attempts(u32);
+
+        // fails the first time, succeeds after.
+        fn flaky(In(entity): In<Entity>, mut query: Query<&mut Attempts>) -> Status {
+            let mut attempts = query.get_mut(entity).unwrap();
+            attempts.0 += 1;
+            (attempts.0 > 1).into()
+        }
+
+        let mut world = World::default();
+
+        let mut sequences = [
+            (succeed, flaky).sequence_memory(),
+            (succeed, flaky).sequence().with_memory(),
+        ];
+
+        for sequence in &mut sequences {
+            let entity = world.spawn(Attempts(0)).id();
+
+            assert!(sequence.has_memory());
+            sequence.initialize(&mut world);
+
+            assert_eq!(sequence.run(entity, &mut world), Status::Running);
+            assert_eq!(sequence.run(entity, &mut world), Status::Failure);
+            // picks up at `flaky` instead of starting over.
+            assert_eq!(sequence.current_index(entity), 1);
+            assert_eq!(sequence.run(entity, &mut world), Status::Running);
+            assert_eq!(sequence.run(entity, &mut world), Status::Success);
+        }
+
+        let mut select = (fail, succeed).select_memory();
+        select.initialize(&mut world);
+        let entity = world.spawn_empty().id();
+
+        assert_eq!(select.run(entity, &mut world), Status::Running);
+        assert_eq!(select.run(entity, &mut world), Status::Success);
+        assert_eq!(select.current_index(entity), 1);
+
+        assert!(!(succeed, fail).sequence().has_memory());
+    }
+
+    #[test]
+    fn test_predicate() {
+        #[derive(Component)]
+        struct Target;
+
+        let mut world = World::default();
+        let with_target = world.spawn(Target).id();
+        let without_target = world.spawn_empty().id();
+
+        let mut has_target = predicate(has_component::<Target>());
+        has_target.initialize(&mut world);
+
+        assert_eq!(has_target.run(with_target, &mut world), Status::Success);
+        assert_eq!(has_target.run(without_target, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_interleave() {
+        #[derive(Component, Default)]
+        struct Log(Vec<u32>);
+
+        fn log<const N: u32>(In(entity): In<Entity>, mut query: Query<&mut Log>) -> Status {
+            let mut log = query.get_mut(entity).unwrap();
+            log.0.push(N);
+
+            // every child finishes on its second run.
+            if log.0.iter().filter(|n| **n == N).count() < 2 {
+                Status::Running
+            } else {
+                Status::Success
+            }
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn(Log::default()).id();
+
+        let mut interleave = (log::<0>, log::<1>, log::<2>).interleave(1);
+        interleave.initialize(&mut world);
+
+        assert_eq!(interleave.run(entity, &mut world), Status::Running);
+        assert_eq!(world.get::<Log>(entity).unwrap().0, vec![0, 1, 2]);
+
+        assert_eq!(interleave.run(entity, &mut world), Status::Success);
+        assert_eq!(world.get::<Log>(entity).unwrap().0, vec![0, 1, 2, 0, 1, 2]);
+
+        // with a bigger step, each child gets its runs in one go.
+        let entity = world.spawn(Log::default()).id();
+        let mut interleave = (log::<0>, log::<1>).interleave(3);
+        interleave.initialize(&mut world);
+
+        assert_eq!(interleave.run(entity, &mut world), Status::Success);
+        assert_eq!(world.get::<Log>(entity).unwrap().0, vec![0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_run_all_for_entity() {
+        let mut world = World::default();
+        let mut trees = BehaviourTrees::default();
+
+        let main = trees.create((succeed, fail).sequence());
+        let first = trees.create(always_succeed());
+        let second = trees.create(always_fail());
+        let removed = trees.create(always_succeed());
+        trees.remove(removed);
+
+        let entity = world
+            .spawn((main, BehaviourIds(vec![first, second, removed])))
+            .id();
+
+        assert_eq!(
+            trees.run_all_for_entity(entity, &mut world),
+            vec![
+                (main, Status::Running),
+                (first, Status::Success),
+                (second, Status::Failure)
+            ]
+        );
+        assert_eq!(
+            trees.run_all_for_entity(entity, &mut world)[0],
+            (main, Status::Failure)
+        );
+    }
+
+    #[test]
+    fn test_reduce() {
+        use crate::behaviour::ReducePolicy;
+        use Status::*;
+
+        assert_eq!(
+            Status::reduce_sequence([Running, Failure, Running]),
+            Failure
+        );
+        assert_eq!(Status::reduce_sequence([Success, Running]), Running);
+        assert_eq!(Status::reduce_sequence([]), Success);
+
+        assert_eq!(Status::reduce_select([Failure, Failure, Success]), Success);
+        assert_eq!(Status::reduce_select([Failure, Failure]), Failure);
+        assert_eq!(Status::reduce_select([]), Failure);
+
+        assert_eq!(
+            Status::reduce([Failure, Running], ReducePolicy::AllSuccess),
+            Running
+        );
+        assert_eq!(
+            Status::reduce([Failure, Success], ReducePolicy::AllSuccess),
+            Failure
+        );
+        assert_eq!(
+            Status::reduce([Running, Success, Failure], ReducePolicy::FirstTerminal),
+            Success
+        );
+        assert_eq!(
+            Status::reduce([Running, Running], ReducePolicy::FirstTerminal),
+            Running
+        );
+
+        assert_eq!(
+            Status::reduce([Success, Success, Failure], ReducePolicy::Majority),
+            Success
+        );
+        assert_eq!(
+            Status::reduce([Failure, Failure, Running], ReducePolicy::Majority),
+            Failure
+        );
+        assert_eq!(
+            Status::reduce([Success, Failure, Running], ReducePolicy::Majority),
+            Running
+        );
+        assert_eq!(
+            Status::reduce([Success, Failure], ReducePolicy::Majority),
+            Failure
+        );
+    }
+
+    #[test]
+    fn test_track_ticks() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+
+        let mut tracked = (fail, succeed).select().track_ticks();
+        tracked.initialize(&mut world);
+
+        for ticks in 1..=3 {
+            tracked.run(entity, &mut world);
+            assert_eq!(tracked.tick_count(entity), ticks);
+        }
+        assert_eq!(tracked.tick_count(other), 0);
+
+        tracked.initialize(&mut world);
+        assert_eq!(tracked.tick_count(entity), 0);
+    }
+
+    #[test]
+    fn test_suspend_if() {
+        #[derive(Component)]
+        struct Paused;
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        // fails on the fourth run.
+        let mut suspendable = fail.retry(3).suspend_if(has_component::<Paused>());
+        suspendable.initialize(&mut world);
+
+        assert_eq!(suspendable.run(entity, &mut world), Status::Running);
+        assert_eq!(suspendable.run(entity, &mut world), Status::Running);
+
+        world.entity_mut(entity).insert(Paused);
+        for _ in 0..5 {
+            assert_eq!(suspendable.run(entity, &mut world), Status::Running);
+        }
+        world.entity_mut(entity).remove::<Paused>();
+
+        // the retry count carried over.
+        assert_eq!(suspendable.run(entity, &mut world), Status::Running);
+        assert_eq!(suspendable.run(entity, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_homogeneous_groups() {
+        use crate::behaviour::IntoBehaviour;
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut sequence = Compositor::sequence([succeed, succeed, fail]);
+        sequence.initialize(&mut world);
+
+        assert_eq!(sequence.len(), 3);
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.run(entity, &mut world), Status::Failure);
+
+        let children: Vec<Box<dyn Behaviour>> = vec![
+            Box::new(always_fail()),
+            Box::new(always_succeed()),
+            Box::new(panic_if_run.into_behaviour()),
+        ];
+        let mut select = children.select();
+        select.initialize(&mut world);
+
+        assert_eq!(select.run(entity, &mut world), Status::Running);
+        assert_eq!(select.run(entity, &mut world), Status::Success);
+    }
+
+    #[test]
+    fn test_repeat_for_duration() {
+        use bevy::{prelude::Time, utils::Instant};
+        use std::time::Duration;
+
+        fn count_and_fail(In(entity): In<Entity>, mut runs: Query<&mut Runs>) -> Status {
+            runs.get_mut(entity).unwrap().0 += 1;
+            Status::Failure
+        }
+
+        let mut world = World::default();
+        let start = Instant::now();
+        world.insert_resource(Time::new(start));
+
+        let entity = world.spawn(Runs(0)).id();
+
+        let mut repeat = count_and_fail.repeat_for_duration(Duration::from_secs(3));
+        repeat.initialize(&mut world);
+
+        for second in 0..3 {
+            world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs(second));
+            assert_eq!(repeat.run(entity, &mut world), Status::Running);
+        }
+
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 3);
+
+        world
+            .resource_mut::<Time>()
+            .update_with_instant(start + Duration::from_secs(3));
+        assert_eq!(repeat.run(entity, &mut world), Status::Success);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 3);
+
+        // the next run starts a new window.
+        assert_eq!(repeat.run(entity, &mut world), Status::Running);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 4);
+
+        // a restored copy keeps the time that already passed, even if the clock starts over in between.
+        world
+            .resource_mut::<Time>()
+            .update_with_instant(start + Duration::from_secs(5));
+        assert_eq!(repeat.run(entity, &mut world), Status::Running);
+        let state = repeat.save_state();
+
+        let mut restored = count_and_fail.repeat_for_duration(Duration::from_secs(3));
+        restored.load_state(&state);
+        // trees are initialized on their first tick, after restoring.
+        restored.initialize(&mut world);
+
+        let start = Instant::now();
+        world.insert_resource(Time::new(start));
+        assert_eq!(restored.run(entity, &mut world), Status::Running);
+
+        world
+            .resource_mut::<Time>()
+            .update_with_instant(start + Duration::from_secs(1));
+        assert_eq!(restored.run(entity, &mut world), Status::Success);
+    }
+
+    #[test]
+    fn test_race() {
+        #[derive(Component)]
+        struct Progress(usize);
+
+        // succeeds on its third run.
+        fn slow_success(In(entity): In<Entity>, mut progress: Query<&mut Progress>) -> Status {
+            let mut progress = progress.get_mut(entity).unwrap();
+            progress.0 += 1;
+            if progress.0 >= 3 {
+                Status::Success
+            } else {
+                Status::Running
+            }
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn(Progress(0)).id();
+
+        let mut race = (fail, slow_success).race_first_success();
+        race.initialize(&mut world);
+
+        assert_eq!(race.run(entity, &mut world), Status::Running);
+        assert_eq!(race.run(entity, &mut world), Status::Running);
+        assert_eq!(race.run(entity, &mut world), Status::Success);
+
+        let mut race = (fail, fail).race_first_success();
+        race.initialize(&mut world);
+        assert_eq!(race.run(entity, &mut world), Status::Failure);
+
+        world.get_mut::<Progress>(entity).unwrap().0 = 0;
+
+        let mut race = (succeed, slow_success, fail).race_last_success();
+        race.initialize(&mut world);
+
+        assert_eq!(race.run(entity, &mut world), Status::Running);
+        assert_eq!(race.run(entity, &mut world), Status::Running);
+        assert_eq!(race.run(entity, &mut world), Status::Success);
+
+        let mut race = (fail, fail).race_last_success();
+        race.initialize(&mut world);
+        assert_eq!(race.run(entity, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_backoff_select() {
+        use crate::compositor::BackoffSelect;
+        use bevy::{prelude::Time, utils::Instant};
+        use std::time::Duration;
+
+        let mut world = World::default();
+        let start = Instant::now();
+        world.insert_resource(Time::new(start));
+
+        let entity = world.spawn_empty().id();
+
+        let mut backoff = (fail, fail, succeed).backoff_select(2, Duration::from_secs(5));
+        backoff.initialize(&mut world);
+        let mut per_frame = (fail, fail).backoff_select(2, Duration::from_secs(5));
+        per_frame.initialize(&mut world);
+        let mut slow = (fail, fail).backoff_select(2, Duration::from_secs(5));
+        slow.initialize(&mut world);
+
+        let mut run_at = |backoff: &mut BackoffSelect, secs: u64| {
+            world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_secs(secs));
+            backoff.run(entity, &mut world)
+        };
+
+        assert_eq!(run_at(&mut backoff, 0), Status::Failure);
+        assert_eq!(backoff.level(entity), 0);
+        assert_eq!(run_at(&mut backoff, 1), Status::Failure);
+        assert_eq!(backoff.level(entity), 1);
+        assert_eq!(run_at(&mut backoff, 2), Status::Failure);
+        assert_eq!(run_at(&mut backoff, 3), Status::Failure);
+        assert_eq!(backoff.level(entity), 2);
+
+        // the most expensive level is the last one.
+        assert_eq!(run_at(&mut backoff, 4), Status::Success);
+        assert_eq!(backoff.level(entity), 0);
+
+        assert_eq!(run_at(&mut backoff, 5), Status::Failure);
+        assert_eq!(run_at(&mut backoff, 6), Status::Failure);
+        assert_eq!(backoff.level(entity), 1);
+
+        // the cooldown is up, so it starts from the cheapest again.
+        assert_eq!(run_at(&mut backoff, 11), Status::Failure);
+        assert_eq!(backoff.level(entity), 0);
+
+        // ticking every frame still backs off once the cooldown is up.
+        for secs in 20..=21 {
+            run_at(&mut per_frame, secs);
+        }
+        for secs in 22..=25 {
+            assert_eq!(run_at(&mut per_frame, secs), Status::Failure);
+            assert_eq!(per_frame.level(entity), 1);
+        }
+        run_at(&mut per_frame, 26);
+        assert_eq!(per_frame.level(entity), 0);
+
+        // restored copies keep the level, failures and time waited, even if the clock starts over in between.
+        assert_eq!(run_at(&mut backoff, 31), Status::Failure);
+        assert_eq!(run_at(&mut backoff, 33), Status::Failure);
+        assert_eq!(backoff.level(entity), 1);
+        assert_eq!(run_at(&mut slow, 40), Status::Failure);
+        assert_eq!(run_at(&mut slow, 41), Status::Failure);
+        assert_eq!(run_at(&mut slow, 43), Status::Failure);
+        assert_eq!(slow.level(entity), 1);
+
+        let saved = backoff.save_state();
+        let slow_saved = slow.save_state();
+
+        let start = Instant::now();
+        world.insert_resource(Time::new(start));
+
+        let mut restored = (fail, fail, succeed).backoff_select(2, Duration::from_secs(5));
+        restored.load_state(&saved);
+        restored.initialize(&mut world);
+        assert_eq!(restored.run(entity, &mut world), Status::Failure);
+        // one more failure escalates, since the one at 33 was kept.
+        assert_eq!(restored.level(entity), 2);
+
+        let mut restored = (fail, fail).backoff_select(2, Duration::from_secs(5));
+        restored.load_state(&slow_saved);
+        restored.initialize(&mut world);
+        world
+            .resource_mut::<Time>()
+            .update_with_instant(start + Duration::from_secs(1));
+        restored.run(entity, &mut world);
+        assert_eq!(restored.level(entity), 1);
+        // 2 seconds before saving, 3 after.
+        world
+            .resource_mut::<Time>()
+            .update_with_instant(start + Duration::from_secs(4));
+        restored.run(entity, &mut world);
+        assert_eq!(restored.level(entity), 0);
+
+        // levels past the last child are dropped instead of panicking.
+        let mut shorter = (fail,).backoff_select(2, Duration::from_secs(5));
+        shorter.initialize(&mut world);
+        shorter.load_state(&BehaviourState {
+            entities: [(entity, usize::MAX)].into_iter().collect(),
+            children: Vec::new(),
+        });
+        assert_eq!(shorter.level(entity), 0);
+        assert_eq!(shorter.run(entity, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_world_condition() {
+        use bevy::prelude::{IntoSystem, Resource};
+
+        #[derive(Resource)]
+        struct Alarm;
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let alarm = world_condition(|world: &World| world.contains_resource::<Alarm>());
+
+        let mut flee =
+            succeed.run_if_with_return(IntoSystem::into_system(alarm.clone()), Status::Failure);
+        flee.initialize(&mut world);
+        assert_eq!(flee.run(entity, &mut world), Status::Failure);
+
+        world.insert_resource(Alarm);
+        assert_eq!(flee.run(entity, &mut world), Status::Success);
+
+        let mut idle = fail.retry_while(alarm);
+        idle.initialize(&mut world);
+        assert_eq!(idle.run(entity, &mut world), Status::Running);
+    }
+
+    #[test]
+    fn test_utility_select() {
+        fn count_runs(In(entity): In<Entity>, mut runs: Query<&mut Runs>) -> Status {
+            runs.get_mut(entity).unwrap().0 += 1;
+            Status::Running
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn(Runs(0)).id();
+
+        let mut select = (
+            (|_: In<Entity>| 0.5, panic_if_run),
+            (|_: In<Entity>| 0.9, count_runs),
+            (|_: In<Entity>| 0.3, panic_if_run),
+        )
+            .utility_select();
+        select.initialize(&mut world);
+
+        assert_eq!(select.run(entity, &mut world), Status::Running);
+        assert_eq!(select.run(entity, &mut world), Status::Running);
+
+        assert_eq!(select.active(entity), Some(1));
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 2);
+
+        // branches past the last one are dropped, so the entity picks again.
+        select.load_state(&BehaviourState {
+            entities: [(entity, 3)].into_iter().collect(),
+            children: Vec::new(),
+        });
+        assert_eq!(select.active(entity), None);
+        assert_eq!(select.run(entity, &mut world), Status::Running);
+        assert_eq!(select.active(entity), Some(1));
+    }
+
+    #[test]
+    fn test_if_then() {
+        #[derive(Component)]
+        struct Wounded;
+
+        let mut world = World::default();
+        let wounded = world.spawn(Wounded).id();
+        let healthy = world.spawn_empty().id();
+
+        let mut skipped = if_then(has_component::<Wounded>(), panic_if_run);
+        skipped.initialize(&mut world);
+        assert_eq!(skipped.run(healthy, &mut world), Status::Success);
+
+        let mut heal = if_then(has_component::<Wounded>(), fail);
+        heal.initialize(&mut world);
+        assert_eq!(heal.run(wounded, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_typed_blackboard() {
+        use bevy::prelude::Vec2;
+
+        let mut blackboard = TypedBlackboard::default();
+        blackboard.set("target", Vec2::new(1., 2.));
+        blackboard.set("target", 3_f32);
+
+        assert_eq!(blackboard.get::<Vec2>("target"), Some(&Vec2::new(1., 2.)));
+        assert_eq!(blackboard.get::<f32>("target"), Some(&3.));
+        assert_eq!(blackboard.get::<Vec2>("home"), None);
+
+        blackboard.get_mut::<Vec2>("target").unwrap().x = 5.;
+        assert_eq!(blackboard.remove::<Vec2>("target"), Some(Vec2::new(5., 2.)));
+        assert_eq!(blackboard.get::<Vec2>("target"), None);
+        assert_eq!(blackboard.get::<f32>("target"), Some(&3.));
+    }
+
+    #[test]
+    fn test_shapes() {
+        let tree = || -> Box<dyn Behaviour> {
+            Box::new((succeed.retry(3), fail.invert()).sequence().with_memory())
+        };
+
+        assert!(*tree() == *tree());
+        // leaves aren't compared.
+        let other_leaves: Box<dyn Behaviour> =
+            Box::new((fail.retry(3), succeed.invert()).sequence().with_memory());
+        assert!(*tree() == *other_leaves);
+
+        let different: [Box<dyn Behaviour>; 3] = [
+            Box::new((succeed.retry(4), fail.invert()).sequence().with_memory()),
+            Box::new((succeed.retry(3), fail.invert()).sequence()),
+            Box::new((succeed.retry(3), fail.invert()).select().with_memory()),
+        ];
+        for different in different {
+            assert!(*tree() != *different);
+        }
+    }
+
+    #[test]
+    fn test_aliases() {
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut repeat = fail.repeat(3);
+        let mut times = fail.times(3);
+        assert_eq!(repeat.shape(), times.shape());
+
+        repeat.initialize(&mut world);
+        times.initialize(&mut world);
+        for _ in 0..4 {
+            assert_eq!(
+                repeat.run(entity, &mut world),
+                times.run(entity, &mut world)
+            );
+        }
+
+        let mut retry = fail.retry(3);
+        let mut try_n_times = fail.try_n_times(3);
+        assert_eq!(retry.shape(), try_n_times.shape());
+
+        retry.initialize(&mut world);
+        try_n_times.initialize(&mut world);
+        for _ in 0..4 {
+            assert_eq!(
+                retry.run(entity, &mut world),
+                try_n_times.run(entity, &mut world)
+            );
+        }
+    }
+
+    #[test]
+    fn test_display_id() {
+        use crate::plugin::{BehaviourId, BehaviourNameRegistry};
+
+        let id = BehaviourId {
+            index: 42,
+            version: 0,
+        };
+        assert_eq!(format!("{id}"), "tree#42");
+        assert_eq!(format!("{id:?}"), "BehaviourId { index: 42, version: 0 }");
+        assert_eq!(format!("{}", BehaviourId { version: 3, ..id }), "tree#42v3");
+        assert_eq!(format!("{}", BehaviourId::null()), "tree#null");
+
+        let mut registry = BehaviourNameRegistry::default();
+        assert_eq!(id.display_with_registry(&registry).to_string(), "tree#42");
+
+        registry.set_name(id, "guard");
+        assert_eq!(id.display_with_registry(&registry).to_string(), "guard");
+    }
+
+    #[test]
+    fn test_repeat_sequence() {
+        use crate::compositor::repeat_sequence_until;
+
+        let mut world = World::default();
+        let entity = world.spawn(BranchRuns::default()).id();
+
+        let mut repeat =
+            (count_branch::<0>, count_branch::<1>, count_branch::<2>).repeat_sequence();
+        repeat.initialize(&mut world);
+
+        for _ in 0..9 {
+            assert_eq!(repeat.run(entity, &mut world), Status::Running);
+        }
+        assert_eq!(world.get::<BranchRuns>(entity).unwrap().0, [3, 3, 3]);
+
+        fn second_pass(In(entity): In<Entity>, runs: Query<&BranchRuns>) -> bool {
+            runs.get(entity).unwrap().0[2] >= 5
+        }
+
+        let mut until = repeat_sequence_until(
+            (count_branch::<0>, count_branch::<1>, count_branch::<2>),
+            second_pass,
+        );
+        until.initialize(&mut world);
+
+        let statuses = (0..6)
+            .map(|_| until.run(entity, &mut world))
+            .collect::<Vec<_>>();
+        assert_eq!(statuses[..5], [Status::Running; 5]);
+        assert_eq!(statuses[5], Status::Success);
+    }
+
+    #[test]
+    fn test_prepend_append() {
+        use crate::compositor::Sequence;
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut sequence = (succeed, panic_if_run).sequence();
+        sequence.initialize(&mut world);
+
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.current_index(entity), 1);
+
+        let mut checked = Sequence::new(vec![]);
+        checked.prepend(succeed);
+        checked.append(fail);
+        assert_eq!(checked.len(), 2);
+
+        sequence.prepend(checked);
+        sequence.append(fail);
+        assert_eq!(sequence.len(), 4);
+        // still pointing at the old second child.
+        assert_eq!(sequence.current_index(entity), 2);
+    }
+
+    #[test]
+    fn test_prepend_running() {
+        #[derive(Component)]
+        struct Ready;
+
+        fn wait(In(entity): In<Entity>, ready: Query<&Ready>) -> Status {
+            match ready.contains(entity) {
+                true => Status::Success,
+                false => Status::Running,
+            }
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn(Runs(0)).id();
+
+        let mut sequence = (wait, succeed).sequence();
+        sequence.initialize(&mut world);
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+
+        // the entity is still waiting on the old first child.
+        sequence.prepend(count);
+        assert_eq!(sequence.current_index(entity), 1);
+
+        world.entity_mut(entity).insert(Ready);
+        for _ in 0..2 {
+            assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        }
+        assert_eq!(sequence.run(entity, &mut world), Status::Success);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 0);
+
+        // starting over runs the new child, which was initialized on the fly.
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 1);
+
+        // same with a single child, which is run without moving on.
+        world.entity_mut(entity).remove::<Ready>();
+        for mut single in [(wait,).sequence(), (wait,).select().into_sequence()] {
+            single.initialize(&mut world);
+            assert_eq!(single.run(entity, &mut world), Status::Running);
+
+            single.prepend(count);
+            assert_eq!(single.current_index(entity), 1);
+            assert_eq!(single.run(entity, &mut world), Status::Running);
+            assert_eq!(world.get::<Runs>(entity).unwrap().0, 1);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "smallvec")]
+    fn test_inline_children() {
+        assert!((succeed, fail).sequence().is_inline());
+        assert!((succeed, fail).select().is_inline());
+        assert!(!(succeed, fail, succeed, fail, succeed)
+            .sequence()
+            .is_inline());
+    }
+
+    #[test]
+    fn test_fenced_sequence() {
+        let mut world = World::default();
+        let entity = world.spawn(Runs(0)).id();
+
+        let mut fence = (count, count, count).fenced_sequence();
+        fence.initialize(&mut world);
+
+        for tick in 1..=3 {
+            assert_eq!(fence.run(entity, &mut world), Status::Running);
+            assert_eq!(world.get::<Runs>(entity).unwrap().0, tick);
+        }
+
+        assert_eq!(fence.run(entity, &mut world), Status::Success);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 3);
+        assert_eq!(fence.current_index(entity), 0);
+
+        let mut failing = (count, fail, panic_if_run).fenced_sequence();
+        failing.initialize(&mut world);
+
+        assert_eq!(failing.run(entity, &mut world), Status::Running);
+        assert_eq!(failing.run(entity, &mut world), Status::Failure);
+        assert_eq!(failing.current_index(entity), 0);
+    }
+
+    #[test]
+    fn test_register_type() {
+        use crate::behaviour::{IntoBehaviour, SelfMarker};
+
+        struct Wander;
+
+        impl IntoBehaviour<SelfMarker> for Wander {
+            fn into_behaviour(self) -> impl Behaviour {
+                self
+            }
+        }
+
+        impl Behaviour for Wander {
+            fn initialize(&mut self, _: &mut World) {}
+
+            fn run(&mut self, _: Entity, _: &mut World) -> Status {
+                Status::Running
+            }
+        }
+
+        let mut trees = BehaviourTrees::default();
+        trees.register_type::<Wander>("wander", |_| Box::new(Wander));
+
+        let name = trees.build_type("wander").unwrap().type_name().to_owned();
+        assert_eq!(name, std::any::type_name::<Wander>());
+        assert_eq!(trees.build_type(&name).unwrap().type_name(), name);
+        assert!(trees.build_type("idle").is_none());
+
+        let id = trees.create(Wander);
+        let snapshot = trees.snapshot();
+
+        let mut restored = BehaviourTrees::default();
+        restored.register_type::<Wander>("wander", |_| Box::new(Wander));
+        restored.restore_registered(&snapshot);
+
+        assert!(restored.contains(id));
+        assert_eq!(restored.snapshot(), snapshot);
+    }
+
+    #[test]
+    fn test_weighted_sequence() {
+        use crate::compositor::WeightedCompositor;
+
+        let mut world = World::default();
+        let entity = world.spawn(BranchRuns::default()).id();
+
+        let mut sequence = (
+            (1., count_branch::<0>),
+            (0., panic_if_run),
+            (1., count_branch::<2>),
+        )
+            .weighted_sequence()
+            .with_seed(0);
+        sequence.initialize(&mut world);
+
+        for _ in 0..3 {
+            assert_eq!(sequence.run(entity, &mut world), Status::Running);
+            assert_eq!(sequence.run(entity, &mut world), Status::Success);
+        }
+        assert_eq!(world.get::<BranchRuns>(entity).unwrap().0, [3, 0, 3]);
+
+        // all skipped.
+        let mut skipped = ((0., panic_if_run), (0., panic_if_run)).weighted_sequence();
+        skipped.initialize(&mut world);
+        assert_eq!(skipped.run(entity, &mut world), Status::Success);
+
+        // the same seed skips the same branches.
+        let runs = |seed| {
+            let mut world = World::default();
+            let entity = world.spawn(BranchRuns::default()).id();
+
+            let mut sequence = (
+                (0.5, count_branch::<0>),
+                (0.5, count_branch::<1>),
+                (0.5, count_branch::<2>),
+            )
+                .weighted_sequence()
+                .with_seed(seed);
+            sequence.initialize(&mut world);
+
+            for _ in 0..30 {
+                sequence.run(entity, &mut world);
+            }
+            world.get::<BranchRuns>(entity).unwrap().0
+        };
+        assert_eq!(runs(7), runs(7));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize_progress() {
+        use crate::compositor::Sequence;
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut sequence = (succeed, succeed, fail).sequence();
+        sequence.initialize(&mut world);
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+
+        let saved = ron::to_string(&sequence).unwrap();
+
+        let mut loaded: Sequence = ron::from_str(&saved).unwrap();
+        assert!(loaded.is_empty());
+        assert_eq!(loaded.current_index(entity), 2);
+
+        let mut fresh = (panic_if_run, panic_if_run, fail).sequence();
+        fresh.initialize(&mut world);
+        loaded.reattach(fresh);
+
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded.run(entity, &mut world), Status::Failure);
+    }
+
+    #[test]
+    fn test_conditional() {
+        use bevy::prelude::{Res, Resource};
+
+        #[derive(Resource)]
+        struct Combat(bool);
+
+        // running every other run, failing otherwise.
+        fn alternate(In(entity): In<Entity>, mut runs: Query<&mut Runs>) -> Status {
+            let mut runs = runs.get_mut(entity).unwrap();
+            runs.0 += 1;
+            if runs.0 % 2 == 1 {
+                Status::Running
+            } else {
+                Status::Failure
+            }
+        }
+
+        fn in_combat(_: In<Entity>, combat: Res<Combat>) -> bool {
+            combat.0
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Combat(true));
+        let entity = world.spawn(Runs(0)).id();
+
+        let mut conditional = alternate.conditional(in_combat, |func| func.invert(), |func| func);
+        conditional.initialize(&mut world);
+
+        assert_eq!(conditional.run(entity, &mut world), Status::Running);
+        assert_eq!(conditional.debug_state(entity), "then");
+        // not checked again until the behaviour finished.
+        world.resource_mut::<Combat>().0 = false;
+        assert_eq!(conditional.run(entity, &mut world), Status::Success);
+
+        assert_eq!(conditional.run(entity, &mut world), Status::Running);
+        assert_eq!(conditional.debug_state(entity), "else");
+        world.resource_mut::<Combat>().0 = true;
+        assert_eq!(conditional.run(entity, &mut world), Status::Failure);
+
+        assert_eq!(conditional.run(entity, &mut world), Status::Running);
+        assert_eq!(conditional.debug_state(entity), "then");
+    }
+
+    #[test]
+    fn test_validate() {
+        use crate::{
+            behaviour::ValidationError,
+            compositor::{Select, Sequence},
+            leaves::SubtreeBehaviour,
+        };
+
+        let mut trees = BehaviourTrees::default();
+
+        assert_eq!(
+            trees.try_create(Sequence::new(vec![])),
+            Err(ValidationError::EmptyCompositor)
+        );
+        // nested compositors are checked too.
+        assert_eq!(
+            trees.try_create((succeed, Select::new(vec![]).invert()).sequence()),
+            Err(ValidationError::EmptyCompositor)
+        );
+        assert!(trees.is_empty());
+
+        let id = trees.try_create((succeed, fail).sequence()).unwrap();
+        assert!(trees.try_create(SubtreeBehaviour(id).retry(3)).is_ok());
+
+        assert_eq!(
+            trees.try_create((succeed, SubtreeBehaviour(BehaviourId::null())).select()),
+            Err(ValidationError::InvalidSubtreeId(BehaviourId::null()))
+        );
+
+        let next = trees.next_id();
+        assert_eq!(
+            trees.try_create((succeed, SubtreeBehaviour(next)).sequence()),
+            Err(ValidationError::CircularReference)
+        );
+
+        // replacing `a` would make it contain itself through `b`.
+        let a = trees.create(always_succeed());
+        let b = trees.create(SubtreeBehaviour(a).invert());
+        assert_eq!(
+            trees.validate_as(a, &SubtreeBehaviour(b)),
+            Err(ValidationError::CircularReference)
+        );
+        assert!(trees.validate_as(a, &SubtreeBehaviour(id)).is_ok());
+
+        // existing loops end the walk instead of recursing forever.
+        trees.replace(a, SubtreeBehaviour(b));
+        assert_eq!(
+            trees.try_create(SubtreeBehaviour(a)),
+            Err(ValidationError::CircularReference)
+        );
+        assert!(trees.try_create(SubtreeBehaviour(id)).is_ok());
+    }
+
+    #[test]
+    fn test_status_u8() {
+        use crate::behaviour::InvalidStatus;
+
+        for status in [Status::Success, Status::Failure, Status::Running] {
+            assert_eq!(Status::try_from(u8::from(status)), Ok(status));
+        }
+        assert_eq!(u8::from(Status::Success), 0);
+        assert_eq!(u8::from(Status::Failure), 1);
+        assert_eq!(u8::from(Status::Running), 2);
+
+        for value in 3..=u8::MAX {
+            assert_eq!(Status::try_from(value), Err(InvalidStatus(value)));
+        }
+    }
+
+    #[test]
+    fn test_all_or_nothing() {
+        #[derive(Component)]
+        struct Progress(usize);
+
+        // succeeds on its third run.
+        fn slow_success(In(entity): In<Entity>, mut progress: Query<&mut Progress>) -> Status {
+            let mut progress = progress.get_mut(entity).unwrap();
+            progress.0 += 1;
+            if progress.0 >= 3 {
+                Status::Success
+            } else {
+                Status::Running
+            }
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn(Progress(0)).id();
+
+        let mut all = (succeed, slow_success, fail).all_or_nothing();
+        all.initialize(&mut world);
+
+        assert_eq!(all.run(entity, &mut world), Status::Running);
+        assert_eq!(all.run(entity, &mut world), Status::Running);
+        assert_eq!(all.run(entity, &mut world), Status::Failure);
+        assert_eq!(world.get::<Progress>(entity).unwrap().0, 3);
+
+        world.get_mut::<Progress>(entity).unwrap().0 = 0;
+
+        let mut all = (succeed, slow_success).all_or_nothing();
+        all.initialize(&mut world);
+
+        assert_eq!(all.run(entity, &mut world), Status::Running);
+        assert_eq!(all.run(entity, &mut world), Status::Running);
+        assert_eq!(all.run(entity, &mut world), Status::Success);
+    }
+
+    #[test]
+    fn test_not() {
+        use crate::decorator::not;
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let pairs: [(Box<dyn Behaviour>, Box<dyn Behaviour>); 2] = [
+            (Box::new(not(succeed)), Box::new(succeed.invert())),
+            (Box::new(not(fail)), Box::new(fail.invert())),
+        ];
+
+        for (mut prefix, mut method) in pairs {
+            prefix.initialize(&mut world);
+            method.initialize(&mut world);
+            assert_eq!(
+                prefix.run(entity, &mut world),
+                method.run(entity, &mut world)
+            );
+        }
+
+        let mut nested = not(not(succeed));
+        nested.initialize(&mut world);
+        assert_eq!(nested.run(entity, &mut world), Status::Success);
+    }
+
+    #[test]
+    fn test_to_boxed() {
+        use crate::{
+            behaviour::IntoBehaviour,
+            compositor::{Select, Sequence},
+        };
+
+        let mut world = World::default();
+        let entity = world.spawn_empty().id();
+
+        let mut tree = Sequence::new(vec![
+            (succeed, succeed).sequence().to_boxed(),
+            (fail, succeed).select().to_boxed(),
+            succeed.into_behaviour().to_boxed(),
+        ])
+        .to_boxed();
+        tree.initialize(&mut world);
+
+        // sequences and selects run one child per tick.
+        let status = std::iter::repeat_with(|| tree.run(entity, &mut world))
+            .take(10)
+            .find(|status| *status != Status::Running);
+        assert_eq!(status, Some(Status::Success));
+
+        let mut tree = Select::new(vec![fail.into_behaviour().to_boxed()]).to_boxed();
+        tree.initialize(&mut world);
+        assert_eq!(tree.run(entity, &mut world), Status::Failure);
+
+        // already boxed, so this hands back the same box instead of adding another layer.
+        let inner: *const dyn Behaviour = &*tree;
+        let boxed = tree.to_boxed();
+        assert!(std::ptr::addr_eq(&*boxed, inner));
+    }
+
+    #[test]
+    fn test_run_on_turn() {
+        #[derive(Component)]
+        struct TurnToken;
+
+        // takes two ticks per turn.
+        fn act(In(entity): In<Entity>, mut runs: Query<&mut Runs>) -> Status {
+            let mut runs = runs.get_mut(entity).unwrap();
+            runs.0 += 1;
+            if runs.0 % 2 == 0 {
+                Status::Success
+            } else {
+                Status::Running
+            }
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn(Runs(0)).id();
+
+        let mut turn = act.run_on_turn::<TurnToken>();
+        turn.initialize(&mut world);
+
+        assert_eq!(turn.run(entity, &mut world), Status::Running);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 0);
+
+        world.entity_mut(entity).insert(TurnToken);
+        assert_eq!(turn.run(entity, &mut world), Status::Running);
+        assert!(world.get::<TurnToken>(entity).is_some());
+        assert_eq!(turn.run(entity, &mut world), Status::Success);
+        assert!(world.get::<TurnToken>(entity).is_none());
+
+        assert_eq!(turn.run(entity, &mut world), Status::Running);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_sorted_sequence() {
+        #[derive(Component)]
+        struct Urgency(f32);
+
+        #[derive(Component, Default)]
+        struct Log(Vec<char>);
+
+        fn urgency(In(entity): In<Entity>, urgency: Query<&Urgency>) -> f32 {
+            urgency.get(entity).unwrap().0
+        }
+
+        fn eat(In(entity): In<Entity>, mut log: Query<&mut Log>) -> Status {
+            log.get_mut(entity).unwrap().0.push('e');
+            Status::Success
+        }
+
+        fn sleep(In(entity): In<Entity>, mut log: Query<&mut Log>) -> Status {
+            log.get_mut(entity).unwrap().0.push('s');
+            Status::Success
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn((Urgency(1.), Log::default())).id();
+
+        let mut sequence = ((urgency, eat), (|_: In<Entity>| 0.5, sleep)).sorted_sequence();
+        sequence.initialize(&mut world);
+
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.order(entity), Some(&[0, 1][..]));
+
+        // the order is kept until the sequence finishes.
+        world.get_mut::<Urgency>(entity).unwrap().0 = 0.;
+        assert_eq!(sequence.run(entity, &mut world), Status::Success);
+        assert_eq!(sequence.order(entity), None);
+
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        assert_eq!(sequence.run(entity, &mut world), Status::Success);
+
+        assert_eq!(world.get::<Log>(entity).unwrap().0, ['e', 's', 's', 'e']);
+
+        // a restored copy continues in the saved order, even though the scores changed.
+        assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        let state = sequence.save_state();
+        world.get_mut::<Urgency>(entity).unwrap().0 = 1.;
+
+        let mut restored = ((urgency, eat), (|_: In<Entity>| 0.5, sleep)).sorted_sequence();
+        restored.initialize(&mut world);
+        restored.load_state(&state);
+        assert_eq!(restored.order(entity), Some(&[1, 0][..]));
+        assert_eq!(restored.run(entity, &mut world), Status::Success);
+        assert_eq!(
+            world.get::<Log>(entity).unwrap().0,
+            ['e', 's', 's', 'e', 's', 'e']
+        );
+    }
+
+    #[test]
+    fn test_behaviour_type_id() {
+        use crate::{
+            behaviour::{behaviour_type_id, IntoBehaviour},
+            compositor::Sequence,
+        };
+
+        let invert = succeed.invert().into_behaviour();
+        let latch = succeed.latch().into_behaviour();
+        assert_ne!(invert.behaviour_type_id(), latch.behaviour_type_id());
+        assert_eq!(
+            invert.behaviour_type_id(),
+            succeed.invert().into_behaviour().behaviour_type_id()
+        );
+
+        let sequence = (succeed, fail).sequence();
+        assert_eq!(
+            sequence.behaviour_type_id(),
+            behaviour_type_id::<Sequence>()
+        );
+
+        let shared = (Box::new(sequence) as Box<dyn Behaviour>).shared();
+        assert_eq!(shared.behaviour_type_id(), behaviour_type_id::<Sequence>());
+    }
+
+    #[test]
+    fn test_optional_behaviour() {
+        use crate::{behaviour::IntoBehaviour, compositor::Sequence};
+
+        let mut world = World::default();
+        let entity = world.spawn(Runs(0)).id();
+
+        let empty: Option<Box<dyn Behaviour>> = None;
+        let mut sequence = Sequence::new(vec![
+            count.into_behaviour().to_boxed(),
+            empty.to_boxed(),
+            Some(count.into_behaviour()).to_boxed(),
+        ]);
+        sequence.initialize(&mut world);
+
+        for _ in 0..3 {
+            assert_eq!(sequence.run(entity, &mut world), Status::Running);
+        }
+        assert_eq!(sequence.run(entity, &mut world), Status::Success);
+        assert_eq!(world.get::<Runs>(entity).unwrap().0, 2);
+    }
+
+    #[test]
+    fn test_retry_transient() {
+        #[derive(Component)]
+        struct Transient;
+
+        #[derive(Component)]
+        struct Attempts(usize);
+
+        fn attempt(In(entity): In<Entity>, mut attempts: Query<&mut Attempts>) -> Status {
+            attempts.get_mut(entity).unwrap().0 += 1;
+            Status::Failure
+        }
+
+        let mut world = World::default();
+        let entity = world.spawn((Attempts(0), Transient)).id();
+
+        let mut retry = attempt.retry_transient(3, has_component::<Transient>());
+        retry.initialize(&mut world);
+
+        assert_eq!(retry.run(entity, &mut world), Status::Running);
+        assert_eq!(retry.debug_state(entity), "tries: 1/3");
+
+        // permanent failures fail right away, and don't count as a try.
+        world.entity_mut(entity).remove::<Transient>();
+        assert_eq!(retry.run(entity, &mut world), Status::Failure);
+        assert_eq!(retry.debug_state(entity), "tries: 0/3");
+
+        world.entity_mut(entity).insert(Transient);
+        assert_eq!(retry.run(entity, &mut world), Status::Running);
+        assert_eq!(retry.run(entity, &mut world), Status::Running);
+        assert_eq!(retry.run(entity, &mut world), Status::Failure);
+        assert_eq!(world.get::<Attempts>(entity).unwrap().0, 5);
+    }
 }
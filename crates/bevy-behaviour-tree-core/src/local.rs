@@ -0,0 +1,162 @@
+use bevy::{
+    ecs::schedule::ScheduleLabel,
+    prelude::{App, Component, Entity, Plugin, ReflectComponent, Update, Without, World},
+    reflect::Reflect,
+    utils::HashSet,
+};
+
+use crate::prelude::{Behaviour, Skip, Status};
+
+/// Like [`Behaviour`], but without the `Send + Sync` requirement.
+///
+/// Useful for behaviours that hold on to thread-bound state, like `Rc` handles into C libraries.
+/// Every [`Behaviour`] is also a `LocalBehaviour`, so the built-in compositors and decorators can be used for local trees as well. Their children still need to be `Send + Sync`, though.
+///
+/// Local behaviours are stored in [`LocalBehaviourTrees`] and always run on the main thread.
+pub trait LocalBehaviour: 'static {
+    /// See [`Behaviour::run`].
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status;
+
+    /// See [`Behaviour::initialize`].
+    fn initialize(&mut self, world: &mut World);
+}
+
+impl<T: Behaviour> LocalBehaviour for T {
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        Behaviour::run(self, entity, world)
+    }
+
+    #[inline]
+    fn initialize(&mut self, world: &mut World) {
+        Behaviour::initialize(self, world)
+    }
+}
+
+/// Turns a closure into a [`LocalBehaviour`]. The closure doesn't need to be `Send` or `Sync`.
+///
+/// ```
+/// # use std::{cell::Cell, rc::Rc};
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, local::{local_fn, LocalBehaviourTrees}};
+/// let calls = Rc::new(Cell::new(0));
+///
+/// let mut trees = LocalBehaviourTrees::default();
+/// trees.create(local_fn(move |_: Entity, _: &mut World| {
+///     calls.set(calls.get() + 1);
+///     Status::Running
+/// }));
+/// ```
+pub fn local_fn<F, S>(func: F) -> impl LocalBehaviour
+where
+    F: FnMut(Entity, &mut World) -> S + 'static,
+    S: Into<Status>,
+{
+    LocalFn(func)
+}
+
+struct LocalFn<F>(F);
+
+impl<F, S> LocalBehaviour for LocalFn<F>
+where
+    F: FnMut(Entity, &mut World) -> S + 'static,
+    S: Into<Status>,
+{
+    #[inline]
+    fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+        (self.0)(entity, world).into()
+    }
+
+    fn initialize(&mut self, _: &mut World) {}
+}
+
+/// Non-send resource storing [`LocalBehaviour`] trees. Counterpart to [`BehaviourTrees`][crate::plugin::BehaviourTrees].
+#[derive(Default)]
+pub struct LocalBehaviourTrees {
+    // see `BehaviourTrees::trees`.
+    trees: Vec<Option<Box<dyn LocalBehaviour>>>,
+    initialized: HashSet<LocalBehaviourId>,
+}
+
+impl LocalBehaviourTrees {
+    /// Create a new local behaviour tree. See [`BehaviourTrees::create`][crate::plugin::BehaviourTrees::create].
+    pub fn create<T: LocalBehaviour>(&mut self, behaviour: T) -> LocalBehaviourId {
+        self.trees.push(Some(Box::new(behaviour)));
+        LocalBehaviourId(self.trees.len() - 1)
+    }
+
+    /// See `BehaviourTrees::behaviour_scope`.
+    fn behaviour_scope<F>(&mut self, id: LocalBehaviourId, mut scope: F)
+    where
+        F: FnMut(&mut Self, &mut Box<dyn LocalBehaviour>),
+    {
+        let Some(behaviour_borrow) = self.trees.get_mut(id.0) else {
+            return;
+        };
+
+        let mut behaviour = std::mem::take(behaviour_borrow).unwrap();
+
+        scope(self, &mut behaviour);
+
+        self.trees[id.0] = Some(behaviour);
+    }
+}
+
+/// An ID for a local behaviour tree. Counterpart to [`BehaviourId`][crate::plugin::BehaviourId].
+#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Reflect, Default)]
+#[reflect(Component)]
+pub struct LocalBehaviourId(usize);
+
+/// Runs [`LocalBehaviourTrees`]. Can be used alongside [`BehaviourTreePlugin`][crate::plugin::BehaviourTreePlugin].
+pub struct LocalBehaviourTreePlugin<Label: ScheduleLabel + Clone = Update> {
+    label: Label,
+}
+
+impl<Label: ScheduleLabel + Clone> LocalBehaviourTreePlugin<Label> {
+    /// Executes the local tree runner in the given schedule.
+    /// Defaults to [`Update`].
+    pub fn in_schedule(label: Label) -> Self {
+        Self { label }
+    }
+}
+
+impl Default for LocalBehaviourTreePlugin {
+    fn default() -> Self {
+        Self::in_schedule(Update)
+    }
+}
+
+impl<Label: ScheduleLabel + Clone> Plugin for LocalBehaviourTreePlugin<Label> {
+    fn build(&self, app: &mut App) {
+        app.init_non_send_resource::<LocalBehaviourTrees>()
+            .add_systems(self.label.clone(), run_local_ticks);
+    }
+}
+
+// exclusive systems always run on the main thread, so we're free to move the non-send resource around.
+fn run_local_ticks(world: &mut World) {
+    let Some(mut trees) = world.remove_non_send_resource::<LocalBehaviourTrees>() else {
+        return;
+    };
+
+    let mut query = world
+        .query_filtered::<(Entity, &LocalBehaviourId), Without<Skip>>()
+        .iter(world)
+        .map(|(entity, id)| (entity, *id))
+        .collect::<Vec<_>>();
+
+    query.sort_by_key(|(_, id)| *id);
+
+    for (entity, id) in query {
+        trees.behaviour_scope(id, |trees, behaviour| {
+            if !trees.initialized.contains(&id) {
+                behaviour.initialize(world);
+                trees.initialized.insert(id);
+            }
+
+            behaviour.run(entity, world);
+        });
+    }
+
+    world.insert_non_send_resource(trees);
+}
@@ -1,48 +1,539 @@
+use std::{
+    any::Any,
+    fmt::Display,
+    marker::PhantomData,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Mutex,
+    time::Duration,
+};
+
 use bevy::{
-    ecs::schedule::ScheduleLabel,
+    ecs::{
+        change_detection::DetectChangesMut, component::ComponentId, query::ReadOnlyWorldQuery,
+        schedule::ScheduleLabel,
+    },
+    log::warn,
     prelude::{
-        App, Component, Entity, Mut, Plugin, ReflectComponent, Resource, Update, Without, World,
+        App, Bundle, Changed, Commands, Component, Entity, IntoSystem, IntoSystemConfigs,
+        IntoSystemSetConfigs, Or, Plugin, Query, ReflectComponent, ReflectResource,
+        RemovedComponents, Res, ResMut, Resource, SystemSet, Time, Update, With, Without, World,
     },
     reflect::Reflect,
-    utils::HashSet,
+    utils::{HashMap, HashSet, Instant},
 };
 
-use crate::prelude::Behaviour;
+use crate::{
+    behaviour::{
+        BehaviourErrors, BehaviourShape, BehaviourState, ClosureBehaviour, IntoBehaviour,
+        SelfMarker, ValidationError,
+    },
+    debug::{advance_on_key, record_status, step, EntityStepper},
+    decorator::try_if,
+    diagnostics::{record_timings, BehaviourTreeDiagnostics, BehaviourTreeDiagnosticsPlugin},
+    leaves::Despawn,
+    prelude::{Behaviour, Status},
+};
 
 /// Plugin for all core functionality.
-pub struct BehaviourTreePlugin<Label: ScheduleLabel + Clone = Update> {
+pub struct BehaviourTreePlugin<Label: ScheduleLabel + Clone = Update, Filter = ()> {
     label: Label,
+    diagnostics: bool,
+    entity_stepper: bool,
+    panic_handler: Option<BehaviourPanicHandler>,
+    max_depth: usize,
+    frame_budget: Option<Duration>,
+    cleanup: CleanupPolicy,
+    sort: Option<TickSorter>,
+    _filter: PhantomData<fn() -> Filter>,
 }
 
 impl<Label: ScheduleLabel + Clone> BehaviourTreePlugin<Label> {
     /// Executes the tree runner in the given schedule.
     /// Defaults to [`Update`].
     pub fn in_schedule(label: Label) -> Self {
-        Self { label }
+        Self {
+            label,
+            diagnostics: false,
+            entity_stepper: false,
+            panic_handler: None,
+            max_depth: TreeDepthGuard::DEFAULT_MAX_DEPTH,
+            frame_budget: None,
+            cleanup: CleanupPolicy::default(),
+            sort: None,
+            _filter: PhantomData,
+        }
+    }
+}
+
+impl<Label: ScheduleLabel + Clone, Filter> BehaviourTreePlugin<Label, Filter> {
+    /// Only ticks entities matching the given query filter.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// #[derive(Component)]
+    /// struct ActiveAI;
+    ///
+    /// App::new().add_plugins(BehaviourTreePlugin::default().with_entity_filter::<With<ActiveAI>>());
+    /// ```
+    pub fn with_entity_filter<F: ReadOnlyWorldQuery + 'static>(
+        self,
+    ) -> BehaviourTreePlugin<Label, F> {
+        BehaviourTreePlugin {
+            label: self.label,
+            diagnostics: self.diagnostics,
+            entity_stepper: self.entity_stepper,
+            panic_handler: self.panic_handler,
+            max_depth: self.max_depth,
+            frame_budget: self.frame_budget,
+            cleanup: self.cleanup,
+            sort: self.sort,
+            _filter: PhantomData,
+        }
+    }
+
+    /// Measures how long each tree takes to run. See [`BehaviourTreeDiagnosticsPlugin`].
+    pub fn with_diagnostics(mut self) -> Self {
+        self.diagnostics = true;
+        self
+    }
+
+    /// Steps through trees one entity at a time, at the press of a key. See [`EntityStepper`].
+    pub fn with_entity_stepper(mut self) -> Self {
+        self.entity_stepper = true;
+        self
+    }
+
+    /// Catches panics in behaviours instead of crashing, and passes them to `handler`.
+    ///
+    /// The panicking tree counts as failed for that entity and tick. The handler can then log the panic, remove the entity's [`BehaviourId`], despawn it, etc.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// App::new().add_plugins(BehaviourTreePlugin::default().with_error_handler(|world, entity, _, _| {
+    ///     error!("{entity:?}'s behaviour panicked");
+    ///     world.entity_mut(entity).remove::<BehaviourId>();
+    /// }));
+    /// ```
+    /// Note that the world may be left in an unexpected state by the panicking behaviour.
+    pub fn with_error_handler(mut self, handler: BehaviourPanicHandler) -> Self {
+        self.panic_handler = Some(handler);
+        self
+    }
+
+    /// How deeply trees may be nested through [`SubtreeBehaviour`][crate::leaves::SubtreeBehaviour]s. See [`TreeDepthGuard`].
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Skips ticking trees on frames that already took longer than `budget`, so slow frames don't get even slower. See [`TickLimiter`].
+    pub fn with_frame_budget_limit(mut self, budget: Duration) -> Self {
+        self.frame_budget = Some(budget);
+        self
+    }
+
+    /// When trees forget the state of entities that lost their [`BehaviourId`], e.g. because they were despawned.
+    /// Defaults to [`CleanupPolicy::Deferred(1)`][CleanupPolicy::Deferred].
+    pub fn with_deferred_cleanup(mut self, policy: CleanupPolicy) -> Self {
+        self.cleanup = policy;
+        self
+    }
+
+    /// Ticks entities in the order of their `C` component, e.g. to have them act by turn order.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// #[derive(Component, PartialEq, Eq, PartialOrd, Ord)]
+    /// struct TurnOrder(i32);
+    ///
+    /// App::new().add_plugins(BehaviourTreePlugin::default().with_sort_key::<TurnOrder>());
+    /// ```
+    /// The sort is stable, so entities with equal keys keep their usual order. Entities without `C` are ticked last.
+    pub fn with_sort_key<C: Component + Ord>(mut self) -> Self {
+        self.sort = Some(TickSorter(sort_by_component::<C>));
+        self
     }
 }
 
 impl Default for BehaviourTreePlugin {
     fn default() -> Self {
-        Self { label: Update }
+        Self::in_schedule(Update)
     }
 }
 
-impl Plugin for BehaviourTreePlugin {
+impl<Label: ScheduleLabel + Clone, Filter: ReadOnlyWorldQuery + 'static> Plugin
+    for BehaviourTreePlugin<Label, Filter>
+{
     fn build(&self, app: &mut App) {
         app.init_resource::<BehaviourTrees>()
-            .add_systems(self.label.clone(), run_ticks);
+            .register_type::<BehaviourTrees>()
+            .init_resource::<BehaviourTypeRegistry>()
+            .init_resource::<BehaviourNameRegistry>()
+            .init_resource::<BehaviourErrors>()
+            .insert_resource(TreeDepthGuard::new(self.max_depth))
+            .configure_sets(
+                self.label.clone(),
+                (
+                    BehaviourTreeSet::PreTick,
+                    BehaviourTreeSet::Tick,
+                    BehaviourTreeSet::PostTick,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                self.label.clone(),
+                (run_ticks::<Filter>, forget_removed_names)
+                    .chain()
+                    .in_set(BehaviourTreeSet::Tick),
+            );
+
+        if self.diagnostics && !app.is_plugin_added::<BehaviourTreeDiagnosticsPlugin>() {
+            app.add_plugins(BehaviourTreeDiagnosticsPlugin);
+        }
+
+        if let Some(handler) = self.panic_handler {
+            app.insert_resource(PanicHandler(handler));
+        }
+
+        if let Some(sort) = self.sort {
+            app.insert_resource(sort);
+        }
+
+        if let Some(budget) = self.frame_budget {
+            app.insert_resource(TickLimiter::new(budget));
+        }
+
+        if self.cleanup != CleanupPolicy::Manual {
+            app.insert_resource(EntityCleanup {
+                policy: self.cleanup,
+                assigned: HashMap::default(),
+                pending: Vec::new(),
+            })
+            .add_systems(
+                self.label.clone(),
+                cleanup_entities
+                    .after(run_ticks::<Filter>)
+                    .in_set(BehaviourTreeSet::Tick),
+            );
+        }
+
+        if self.entity_stepper {
+            app.init_resource::<EntityStepper>().add_systems(
+                self.label.clone(),
+                advance_on_key.in_set(BehaviourTreeSet::PreTick),
+            );
+        }
+    }
+}
+
+/// The phases trees are ticked in, all in the plugin's [schedule][BehaviourTreePlugin::in_schedule] and run in order.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// # fn reset_threat_levels() {}
+/// # fn apply_decisions() {}
+/// App::new()
+///     .add_plugins(BehaviourTreePlugin::default())
+///     .add_systems(Update, (
+///         reset_threat_levels.in_set(BehaviourTreeSet::PreTick),
+///         apply_decisions.in_set(BehaviourTreeSet::PostTick),
+///     ));
+/// ```
+#[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BehaviourTreeSet {
+    /// Before any tree is ticked, e.g. to reset per-frame state.
+    PreTick,
+    /// Where the trees are ticked.
+    Tick,
+    /// After all trees have been ticked, e.g. to read their results.
+    PostTick,
+}
+
+/// Called with the panic payload when a behaviour panics. See [`BehaviourTreePlugin::with_error_handler`].
+pub type BehaviourPanicHandler = fn(&mut World, Entity, BehaviourId, Box<dyn Any + Send>);
+
+#[derive(Resource)]
+pub(crate) struct PanicHandler(BehaviourPanicHandler);
+
+/// When trees forget the state of entities that lost their [`BehaviourId`] or [`BehaviourIds`]. See [`BehaviourTreePlugin::with_deferred_cleanup`].
+///
+/// Cleaning up [resets][Behaviour::reset_entity] the entity only for the trees it lost, and only if it didn't get them back in the meantime, see [`BehaviourTrees::forget_entity_tree`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CleanupPolicy {
+    /// Clean up in the same tick the removal is noticed.
+    Immediate,
+    /// Clean up this many ticks after the removal is noticed.
+    Deferred(u64),
+    /// Never clean up automatically.
+    Manual,
+}
+
+impl Default for CleanupPolicy {
+    fn default() -> Self {
+        // so systems running after the trees in the same frame can still look at the state of just despawned entities.
+        Self::Deferred(1)
+    }
+}
+
+#[derive(Resource)]
+struct EntityCleanup {
+    policy: CleanupPolicy,
+    // the trees each entity had when last checked, to tell which ones it lost.
+    assigned: HashMap<Entity, Vec<BehaviourId>>,
+    // entities, the trees they lost, and how many more ticks until they're cleaned up.
+    pending: Vec<(Entity, Vec<BehaviourId>, u64)>,
+}
+
+/// The trees assigned to an entity through its [`BehaviourId`] and [`BehaviourIds`].
+fn assigned_trees(id: Option<&BehaviourId>, ids: Option<&BehaviourIds>) -> Vec<BehaviourId> {
+    id.into_iter()
+        .chain(ids.into_iter().flat_map(|ids| ids.0.iter()))
+        .copied()
+        .collect()
+}
+
+/// Each entity with a [`BehaviourId`] or [`BehaviourIds`], along with its [assigned trees][assigned_trees].
+///
+/// Only looks at archetypes with one of the components, instead of every entity in the world.
+fn assigned_entities(world: &World) -> impl Iterator<Item = (Entity, Vec<BehaviourId>)> + '_ {
+    let components = [
+        world.component_id::<BehaviourId>(),
+        world.component_id::<BehaviourIds>(),
+    ];
+
+    world
+        .archetypes()
+        .iter()
+        .filter(move |archetype| {
+            components
+                .into_iter()
+                .flatten()
+                .any(|component| archetype.contains(component))
+        })
+        .flat_map(|archetype| archetype.entities())
+        .map(|entity| {
+            let entity = world.entity(entity.entity());
+            (entity.id(), assigned_trees(entity.get(), entity.get()))
+        })
+}
+
+/// Forgets the entity's state in trees it lost, unless it got them back since.
+fn forget_lost_trees(
+    trees: &mut BehaviourTrees,
+    entity: Entity,
+    lost: &[BehaviourId],
+    assigned: &Query<(Option<&BehaviourId>, Option<&BehaviourIds>)>,
+) {
+    let Ok((id, ids)) = assigned.get(entity) else {
+        // despawned, so nobody can observe it anymore either.
+        for id in lost {
+            trees.forget_entity_tree(entity, *id);
+            trees.entity_observers.remove(&(*id, entity));
+        }
+        return;
+    };
+
+    let current = assigned_trees(id, ids);
+    for id in lost.iter().filter(|id| !current.contains(id)) {
+        trees.forget_entity_tree(entity, *id);
+    }
+}
+
+fn cleanup_entities(
+    mut removed: RemovedComponents<BehaviourId>,
+    mut removed_many: RemovedComponents<BehaviourIds>,
+    changed: Query<
+        (Entity, Option<&BehaviourId>, Option<&BehaviourIds>),
+        Or<(Changed<BehaviourId>, Changed<BehaviourIds>)>,
+    >,
+    assigned: Query<(Option<&BehaviourId>, Option<&BehaviourIds>)>,
+    mut cleanup: ResMut<EntityCleanup>,
+    mut trees: ResMut<BehaviourTrees>,
+) {
+    let delay = match cleanup.policy {
+        CleanupPolicy::Immediate => 0,
+        CleanupPolicy::Deferred(ticks) => ticks,
+        CleanupPolicy::Manual => return,
+    };
+
+    // entities from earlier ticks count down before new ones are added, so they don't lose a tick.
+    cleanup.pending.retain_mut(|(entity, lost, ticks)| {
+        *ticks -= 1;
+        if *ticks == 0 {
+            forget_lost_trees(&mut trees, *entity, lost, &assigned);
+        }
+        *ticks > 0
+    });
+
+    let removed = removed
+        .iter()
+        .chain(removed_many.iter())
+        .map(|entity| {
+            let current = assigned
+                .get(entity)
+                .map_or_else(|_| Vec::new(), |(id, ids)| assigned_trees(id, ids));
+            (entity, current)
+        })
+        .collect::<Vec<_>>();
+    let changed = changed
+        .iter()
+        .map(|(entity, id, ids)| (entity, assigned_trees(id, ids)));
+
+    for (entity, current) in removed.into_iter().chain(changed) {
+        let before = match current.is_empty() {
+            true => cleanup.assigned.remove(&entity),
+            false => cleanup.assigned.insert(entity, current.clone()),
+        };
+        let lost = before
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|id| !current.contains(id))
+            .collect::<Vec<_>>();
+
+        if lost.is_empty() {
+            continue;
+        }
+
+        if delay == 0 {
+            forget_lost_trees(&mut trees, entity, &lost, &assigned);
+        } else {
+            cleanup.pending.push((entity, lost, delay));
+        }
+    }
+}
+
+/// Skips ticking trees for a frame if the last frame took longer than [`frame_budget`][TickLimiter::frame_budget], according to [`Time::delta`].
+///
+/// Inserted by [`BehaviourTreePlugin::with_frame_budget_limit`]. Does nothing without a [`Time`] resource.
+#[derive(Resource, Clone, Debug)]
+pub struct TickLimiter {
+    /// The longest a frame may take for trees to still be ticked.
+    pub frame_budget: Duration,
+    /// Tick trees anyway, even if the frame is over budget. Turns the limiter off without removing it.
+    pub tick_if_frame_over_budget: bool,
+}
+
+impl TickLimiter {
+    /// A limiter with the given budget.
+    pub fn new(frame_budget: Duration) -> Self {
+        Self {
+            frame_budget,
+            tick_if_frame_over_budget: false,
+        }
+    }
+
+    fn should_skip(&self, time: &Time) -> bool {
+        !self.tick_if_frame_over_budget && time.delta() > self.frame_budget
+    }
+}
+
+/// Keeps track of how deeply trees are nested while they run, so runaway [subtrees][crate::leaves::SubtreeBehaviour] fail instead of overflowing the stack.
+///
+/// Trees nested deeper than [`max_depth`][TreeDepthGuard::max_depth] aren't run, and fail with a warning instead.
+/// Inserted by [`BehaviourTreePlugin`], see [`with_max_depth`][BehaviourTreePlugin::with_max_depth].
+#[derive(Resource, Debug)]
+pub struct TreeDepthGuard {
+    /// The maximum nesting depth. Top-level trees have a depth of 1.
+    pub max_depth: usize,
+    depth: usize,
+}
+
+impl TreeDepthGuard {
+    /// The default maximum depth.
+    pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+    /// A guard allowing trees to be nested up to `max_depth` deep.
+    pub fn new(max_depth: usize) -> Self {
+        Self {
+            max_depth,
+            depth: 0,
+        }
+    }
+
+    /// How many trees are currently running inside each other.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+impl Default for TreeDepthGuard {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_DEPTH)
     }
 }
 
 /// Resource required for creating trees.
-#[derive(Resource, Default)]
+///
+/// Behaviours themselves can't be reflected, so through [`Reflect`] (e.g. in a world inspector) this only shows a [summary][BehaviourTreesSummary].
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource, from_reflect = false)]
 pub struct BehaviourTrees {
     // We use Option<T> here so we can temporarily move behaviours out of the resource without shifting indices with `std::mem::take`.
+    #[reflect(ignore)]
     trees: Vec<Option<Box<dyn Behaviour>>>,
+    #[reflect(ignore)]
     initialized: HashSet<BehaviourId>,
+    // updated once per tick, so `entity_count` doesn't need world access.
+    #[reflect(ignore)]
+    entity_counts: HashMap<BehaviourId, usize>,
+    #[reflect(ignore)]
+    max_runs: HashMap<BehaviourId, u64>,
+    // only tracked for trees with a limit.
+    #[reflect(ignore)]
+    run_counts: HashMap<(BehaviourId, Entity), u64>,
+    #[reflect(ignore)]
+    observers: HashMap<BehaviourId, Vec<(ObserverHandle, TreeObserver)>>,
+    #[reflect(ignore)]
+    entity_observers: HashMap<(BehaviourId, Entity), Vec<(ObserverHandle, TreeObserver)>>,
+    #[reflect(ignore)]
+    next_observer: u64,
+    // recorded when a tree is initialized.
+    #[reflect(ignore)]
+    dependencies: HashMap<BehaviourId, Vec<ComponentId>>,
+    #[reflect(ignore)]
+    warmed_up: HashSet<(BehaviourId, Entity)>,
+    #[reflect(ignore)]
+    types: BehaviourTypeRegistry,
+    #[reflect(ignore)]
+    total_ticks: u64,
+    #[reflect(ignore)]
+    tick_counts: HashMap<BehaviourId, u64>,
+    #[reflect(ignore)]
+    paused: HashSet<BehaviourId>,
+    // bumped whenever a slot is emptied, so IDs of removed trees don't match the slot's next tree. May be longer than `trees`.
+    #[reflect(ignore)]
+    versions: Vec<u32>,
+    // slots emptied by `remove`, reused by `create`.
+    #[reflect(ignore)]
+    free: Vec<usize>,
+    // set whenever a slot changes, so the summary's list of trees is only rebuilt when needed.
+    #[reflect(ignore)]
+    summary_outdated: bool,
+    // the tree being validated, followed by the subtrees entered to get here.
+    #[reflect(ignore)]
+    validating: Mutex<Vec<BehaviourId>>,
+    summary: BehaviourTreesSummary,
+}
+
+/// What [`BehaviourTrees`] looks like through [`Reflect`]. Refreshed at the start of a tick if anything changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Reflect)]
+pub struct BehaviourTreesSummary {
+    /// See [`BehaviourTrees::len`].
+    pub len: usize,
+    /// See [`BehaviourTrees::active_count`].
+    pub active_count: usize,
+    /// Every tree with its [type name][Behaviour::type_name].
+    pub trees: Vec<(BehaviourId, String)>,
 }
 
+type TreeObserver = Box<dyn Fn(Entity, Status) + Send + Sync>;
+
+/// Identifies an observer added with [`BehaviourTrees::observe`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ObserverHandle(u64);
+
 impl BehaviourTrees {
     /// Create a new behaviour tree.
     ///
@@ -104,61 +595,1689 @@ impl BehaviourTrees {
     ///
     /// ```
     /// You can return any [`Into<Status>`] from a behaviour, by the way! By default, this is only implemented for `Option<Status>` and `bool` (and, y'know, `Status` itself).
+    ///
+    /// Behaviours can also return `Result<Status, E>` for any `E: Into<BehaviourError>`. Errors count as failures, and end up in the [`BehaviourErrors`] resource.
     pub fn create<T: Behaviour + 'static>(&mut self, behaviour: T) -> BehaviourId {
-        self.trees.push(Some(Box::new(behaviour)));
-        BehaviourId(self.trees.len() - 1)
+        self.create_boxed(Box::new(behaviour))
     }
 
-    /// Temporarily moves the behaviour belonging to `id` out of the internal storage.
-    /// Used for behaviour initialization logic.
+    /// Shorthand for creating a tree wrapped in [`run_if`][crate::prelude::Decorator::run_if]:
+    /// entities only run it while the condition is true, and **succeed** right away otherwise.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// #[derive(Component)]
+    /// struct Hungry;
     ///
-    /// `scope` is not ran if the behaviour doesn't exist.
-    pub(crate) fn behaviour_scope<F>(&mut self, id: BehaviourId, mut scope: F)
+    /// # fn find_food(_: In<Entity>) -> Status { Status::Success }
+    /// let mut trees = BehaviourTrees::default();
+    /// let id = trees.create_conditional(find_food, has_component::<Hungry>());
+    /// ```
+    pub fn create_conditional<Marker: 'static, CMarker, B, C>(
+        &mut self,
+        behaviour: B,
+        condition: C,
+    ) -> BehaviourId
     where
-        F: FnMut(&mut Self, &mut Box<dyn Behaviour>),
+        B: IntoBehaviour<Marker>,
+        C: IntoSystem<Entity, bool, CMarker> + Clone,
+        <C as IntoSystem<Entity, bool, CMarker>>::System: Clone,
     {
-        let Some(behaviour_borrow) = self.trees.get_mut(id.0) else {
-            return;
+        self.create(try_if(condition, behaviour))
+    }
+
+    /// Turns a closure with direct world access into a behaviour, so no `In<Entity>` or type annotations are needed:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// let mut trees = BehaviourTrees::default();
+    /// let despawn_self = BehaviourTrees::from_fn(|entity, world| {
+    ///     world.despawn(entity);
+    ///     Status::Success
+    /// });
+    /// trees.create(despawn_self.invert());
+    /// ```
+    pub fn from_fn<F>(func: F) -> impl Behaviour + IntoBehaviour<SelfMarker>
+    where
+        F: FnMut(Entity, &mut World) -> Status + Send + Sync + 'static,
+    {
+        ClosureBehaviour(func)
+    }
+
+    /// Creates a tree from a closure, see [`from_fn`][BehaviourTrees::from_fn].
+    pub fn create_fn<F>(&mut self, func: F) -> BehaviourId
+    where
+        F: FnMut(Entity, &mut World) -> Status + Send + Sync + 'static,
+    {
+        self.create(Self::from_fn(func))
+    }
+
+    /// Like [`create`][BehaviourTrees::create], for trees that are already boxed.
+    pub fn create_boxed(&mut self, behaviour: Box<dyn Behaviour>) -> BehaviourId {
+        if let Err(error) = self.validate_as(self.next_id(), behaviour.as_ref()) {
+            warn!(
+                "created an invalid tree ({}): {error}",
+                behaviour.type_name()
+            );
+        }
+
+        self.store(behaviour)
+    }
+
+    /// Puts the tree into a free slot, without validating it.
+    fn store(&mut self, behaviour: Box<dyn Behaviour>) -> BehaviourId {
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.trees[index] = Some(behaviour);
+                index
+            }
+            None => {
+                self.trees.push(Some(behaviour));
+                self.trees.len() - 1
+            }
         };
+        self.summary_outdated = true;
 
-        let mut behaviour = std::mem::take(behaviour_borrow).unwrap();
+        self.id_at(index)
+    }
 
-        scope(self, &mut behaviour);
+    /// Like [`create`][BehaviourTrees::create], but [validates][Behaviour::validate] the tree first, instead of only warning about mistakes.
+    /// ```
+    /// # use bevy_behaviour_tree_core::{prelude::*, behaviour::ValidationError, compositor::Sequence, leaves::SubtreeBehaviour};
+    /// let mut trees = BehaviourTrees::default();
+    ///
+    /// let empty = trees.try_create(Sequence::new(vec![]));
+    /// assert_eq!(empty.unwrap_err(), ValidationError::EmptyCompositor);
+    ///
+    /// let id = trees.try_create(always_succeed()).unwrap();
+    /// assert!(trees.try_create(SubtreeBehaviour(id)).is_ok());
+    /// ```
+    /// Invalid trees aren't stored.
+    pub fn try_create<T: Behaviour + 'static>(
+        &mut self,
+        behaviour: T,
+    ) -> Result<BehaviourId, ValidationError> {
+        self.validate_as(self.next_id(), &behaviour)?;
+        Ok(self.store(Box::new(behaviour)))
+    }
 
-        self.trees[id.0] = Some(behaviour);
+    /// [Validates][Behaviour::validate] `behaviour` as the tree stored at `id`, so subtrees referring back to `id` count as [circular][ValidationError::CircularReference].
+    pub(crate) fn validate_as(
+        &self,
+        id: BehaviourId,
+        behaviour: &dyn Behaviour,
+    ) -> Result<(), ValidationError> {
+        *self.validating.lock().unwrap() = vec![id];
+        let result = behaviour.validate(self);
+        self.validating.lock().unwrap().clear();
+        result
     }
-}
 
-/// Skips processing the behaviour tree for this entity.
-#[derive(Component, PartialEq, Eq, Debug, Default)]
-pub struct Skip;
+    /// Validates a [subtree][crate::leaves::SubtreeBehaviour] running the tree at `id`.
+    ///
+    /// Follows the subtrees inside that tree too, so a tree containing itself through other trees is caught.
+    /// Other mistakes in those trees were already reported when they were created.
+    pub(crate) fn validate_subtree(&self, id: BehaviourId) -> Result<(), ValidationError> {
+        let mut path = self.validating.lock().unwrap();
+        // validated directly, rather than through `create`.
+        let outside = path.is_empty();
+        if outside {
+            path.push(self.next_id());
+        }
 
-/// An ID for a behaviour tree.
-/// This is a component type. If this is on an entity, that entity is ticked for the given tree.
-#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Reflect, Default)]
-#[reflect(Component)]
-pub struct BehaviourId(usize);
+        let result = if path.contains(&id) {
+            Err(ValidationError::CircularReference)
+        } else if let Some(Some(tree)) = self.slot(id) {
+            path.push(id);
+            drop(path);
+            let nested = tree.validate(self);
+            path = self.validating.lock().unwrap();
+            path.pop();
 
-fn run_ticks(world: &mut World) {
-    world.resource_scope(|world: &mut World, mut trees: Mut<BehaviourTrees>| {
-        let mut query = world
-            .query_filtered::<(Entity, &BehaviourId), Without<Skip>>()
-            .iter(world)
-            .map(|(entity, id)| (entity, *id))
-            .collect::<Vec<_>>(); // collect so we can reborrow world for initialization/running.
+            match nested {
+                Err(ValidationError::CircularReference) => Err(ValidationError::CircularReference),
+                _ => Ok(()),
+            }
+        } else {
+            Err(ValidationError::InvalidSubtreeId(id))
+        };
+
+        if outside {
+            path.clear();
+        }
+        result
+    }
 
-        // sort to *hopefully* squeeze out some performance.
-        query.sort_by(|(_, id1), (_, id2)| id1.cmp(id2));
+    /// The ID the next [created][BehaviourTrees::create] tree gets.
+    pub(crate) fn next_id(&self) -> BehaviourId {
+        self.id_at(self.free.last().copied().unwrap_or(self.trees.len()))
+    }
 
-        for (entity, id) in query {
-            trees.behaviour_scope(id, |trees, behaviour| {
-                if !trees.initialized.contains(&id) {
-                    behaviour.initialize(world);
-                    trees.initialized.insert(id);
-                }
+    /// The current ID of the slot at `index`.
+    fn id_at(&self, index: usize) -> BehaviourId {
+        BehaviourId {
+            index,
+            version: self.versions.get(index).copied().unwrap_or(0),
+        }
+    }
 
-                behaviour.run(entity, world);
-            });
+    /// Invalidates all IDs handed out for the slot at `index`.
+    fn bump_version(&mut self, index: usize) {
+        if self.versions.len() <= index {
+            self.versions.resize(index + 1, 0);
         }
-    });
+        self.versions[index] += 1;
+    }
+
+    /// The slot `id` points to, unless the ID is stale.
+    fn slot(&self, id: BehaviourId) -> Option<&Option<Box<dyn Behaviour>>> {
+        self.is_valid(id).then(|| &self.trees[id.index])
+    }
+
+    fn slot_mut(&mut self, id: BehaviourId) -> Option<&mut Option<Box<dyn Behaviour>>> {
+        self.is_valid(id).then(|| &mut self.trees[id.index])
+    }
+
+    /// Replaces the tree with the given ID, returning the old one if there was one.
+    ///
+    /// The new tree is initialized the next time it runs. Entities using the ID stay assigned to it, but start over with the new tree.
+    /// Like with [`create`][BehaviourTrees::create], mistakes in the new tree are [validated][Behaviour::validate] and warned about.
+    ///
+    /// Stale IDs, i.e. IDs of [removed][BehaviourTrees::remove] trees, are rejected so they can't take over their slot again. Nothing is replaced, and `behaviour` is handed back.
+    pub fn replace<T: Behaviour + 'static>(
+        &mut self,
+        id: BehaviourId,
+        behaviour: T,
+    ) -> Option<Box<dyn Behaviour>> {
+        self.replace_boxed(id, Box::new(behaviour))
+    }
+
+    /// Like [`replace`][BehaviourTrees::replace], for trees that are already boxed.
+    pub fn replace_boxed(
+        &mut self,
+        id: BehaviourId,
+        behaviour: Box<dyn Behaviour>,
+    ) -> Option<Box<dyn Behaviour>> {
+        if !self.can_replace(id) {
+            return Some(behaviour);
+        }
+
+        if let Err(error) = self.validate_as(id, behaviour.as_ref()) {
+            warn!(
+                "replaced {id} with an invalid tree ({}): {error}",
+                behaviour.type_name()
+            );
+        }
+
+        if self.trees.len() <= id.index {
+            self.trees.resize_with(id.index + 1, || None);
+        }
+        if self.versions.len() <= id.index {
+            self.versions.resize(id.index + 1, 0);
+        }
+        self.versions[id.index] = id.version;
+        self.free.retain(|index| *index != id.index);
+
+        self.initialized.remove(&id);
+        self.dependencies.remove(&id);
+        self.warmed_up.retain(|(warmed_id, _)| *warmed_id != id);
+        self.summary_outdated = true;
+        self.trees[id.index].replace(behaviour)
+    }
+
+    /// Whether `id` may be [replaced][BehaviourTrees::replace]: it's either valid, or points past all slots at a version that wasn't handed out yet.
+    fn can_replace(&self, id: BehaviourId) -> bool {
+        self.is_valid(id)
+            || (id.index >= self.trees.len() && id.version >= self.id_at(id.index).version)
+    }
+
+    /// Removes the tree with the given ID, returning it if it existed.
+    ///
+    /// IDs of other trees stay valid. Entities still using the removed ID are no longer ticked,
+    /// even once a new tree is [created][BehaviourTrees::create] in the same slot.
+    pub fn remove(&mut self, id: BehaviourId) -> Option<Box<dyn Behaviour>> {
+        self.initialized.remove(&id);
+        self.entity_counts.remove(&id);
+        self.tick_counts.remove(&id);
+        self.max_runs.remove(&id);
+        self.run_counts.retain(|(run_id, _), _| *run_id != id);
+        self.observers.remove(&id);
+        self.entity_observers
+            .retain(|(observed_id, _), _| *observed_id != id);
+        self.dependencies.remove(&id);
+        self.warmed_up.retain(|(warmed_id, _)| *warmed_id != id);
+        self.paused.remove(&id);
+
+        if !self.is_valid(id) {
+            return None;
+        }
+
+        self.bump_version(id.index);
+        self.free.push(id.index);
+        self.summary_outdated = true;
+        self.trees[id.index].take()
+    }
+
+    /// Moves the tree at `id` into the slot of `new_id`, dropping the tree that was there before.
+    ///
+    /// Entities using `new_id` run the moved tree from now on, after it has been initialized again. `id` is left empty, like after [`remove`][BehaviourTrees::remove].
+    /// Useful to build a tree under a fresh ID and then swap it in for one that entities already use:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::{prelude::*, behaviour::IntoBehaviour};
+    /// # fn idle(_: In<Entity>) -> Status { Status::Running }
+    /// # fn patrol(_: In<Entity>) -> Status { Status::Running }
+    /// let mut trees = BehaviourTrees::default();
+    /// let guard = trees.create(idle.into_behaviour());
+    ///
+    /// let loaded = trees.create(patrol.into_behaviour());
+    /// trees.move_to(loaded, guard);
+    ///
+    /// assert!(trees.contains(guard));
+    /// assert!(!trees.contains(loaded));
+    /// ```
+    /// Does nothing if there's no tree at `id`, or if `new_id` is stale.
+    pub fn move_to(&mut self, id: BehaviourId, new_id: BehaviourId) {
+        if id == new_id || !self.can_replace(new_id) {
+            return;
+        }
+
+        if let Some(tree) = self.remove(id) {
+            self.replace_boxed(new_id, tree);
+        }
+    }
+
+    /// Whether a tree with the given ID exists.
+    pub fn contains(&self, id: BehaviourId) -> bool {
+        matches!(self.slot(id), Some(Some(_)))
+    }
+
+    /// Whether the ID still refers to the tree it was created for, i.e. that tree hasn't been [removed][BehaviourTrees::remove] since.
+    ///
+    /// Slots of removed trees are reused, but get a new [version][BehaviourId::version]:
+    /// ```
+    /// # use bevy_behaviour_tree_core::{prelude::*, behaviour::IntoBehaviour};
+    /// let mut trees = BehaviourTrees::default();
+    /// let old = trees.create(always_succeed().into_behaviour());
+    /// trees.remove(old);
+    ///
+    /// let new = trees.create(always_fail().into_behaviour());
+    /// assert_eq!(new.index(), old.index());
+    /// assert!(!trees.is_valid(old));
+    /// assert!(trees.is_valid(new));
+    /// ```
+    pub fn is_valid(&self, id: BehaviourId) -> bool {
+        id.index < self.trees.len() && self.id_at(id.index) == id
+    }
+
+    /// Moves all trees to the front, filling the slots left behind by [`remove`][BehaviourTrees::remove].
+    ///
+    /// Returns the new ID of every tree that was moved. `BehaviourId` components aren't updated automatically:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// fn compact(mut trees: ResMut<BehaviourTrees>, mut query: Query<&mut BehaviourId>) {
+    ///     let moved = trees.compact();
+    ///     for mut id in &mut query {
+    ///         if let Some(new_id) = moved.get(&*id) {
+    ///             *id = *new_id;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn compact(&mut self) -> HashMap<BehaviourId, BehaviourId> {
+        let mut moved = HashMap::default();
+        let mut trees = Vec::with_capacity(self.trees.len());
+
+        for (index, tree) in std::mem::take(&mut self.trees).into_iter().enumerate() {
+            let Some(tree) = tree else {
+                continue;
+            };
+
+            if index != trees.len() {
+                moved.insert(self.id_at(index), self.id_at(trees.len()));
+                self.bump_version(index);
+            }
+            trees.push(Some(tree));
+        }
+
+        self.trees = trees;
+        self.free.clear();
+        self.summary_outdated = true;
+
+        let remap = |id: BehaviourId| moved.get(&id).copied().unwrap_or(id);
+        self.initialized = self.initialized.drain().map(remap).collect();
+        self.entity_counts = self
+            .entity_counts
+            .drain()
+            .map(|(id, count)| (remap(id), count))
+            .collect();
+        self.tick_counts = self
+            .tick_counts
+            .drain()
+            .map(|(id, ticks)| (remap(id), ticks))
+            .collect();
+        self.max_runs = self
+            .max_runs
+            .drain()
+            .map(|(id, max)| (remap(id), max))
+            .collect();
+        self.run_counts = self
+            .run_counts
+            .drain()
+            .map(|((id, entity), count)| ((remap(id), entity), count))
+            .collect();
+        self.observers = self
+            .observers
+            .drain()
+            .map(|(id, observers)| (remap(id), observers))
+            .collect();
+        self.entity_observers = self
+            .entity_observers
+            .drain()
+            .map(|((id, entity), observers)| ((remap(id), entity), observers))
+            .collect();
+        self.dependencies = self
+            .dependencies
+            .drain()
+            .map(|(id, dependencies)| (remap(id), dependencies))
+            .collect();
+        self.warmed_up = self
+            .warmed_up
+            .drain()
+            .map(|(id, entity)| (remap(id), entity))
+            .collect();
+        self.paused = self.paused.drain().map(remap).collect();
+
+        moved
+    }
+
+    /// Reserves space for `capacity` trees in total, or frees unused space down to `capacity`.
+    ///
+    /// Never removes trees; only slots left empty by [`remove`][BehaviourTrees::remove] at the end are dropped.
+    pub fn resize(&mut self, capacity: usize) {
+        while self.trees.len() > capacity && matches!(self.trees.last(), Some(None)) {
+            self.trees.pop();
+        }
+        let len = self.trees.len();
+        self.free.retain(|index| *index < len);
+
+        if capacity > self.trees.len() {
+            self.trees.reserve_exact(capacity - self.trees.len());
+        } else {
+            self.trees.shrink_to(capacity);
+        }
+    }
+
+    /// The number of trees this can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.trees.capacity()
+    }
+
+    /// All trees and their IDs, in order. Trees that are currently running are left out.
+    pub(crate) fn stored(&self) -> impl Iterator<Item = (BehaviourId, &dyn Behaviour)> {
+        self.trees
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tree)| Some((self.id_at(index), tree.as_deref()?)))
+    }
+
+    /// How many trees there are, not counting removed ones.
+    pub fn len(&self) -> usize {
+        self.trees.iter().filter(|tree| tree.is_some()).count()
+    }
+
+    /// Whether there are no trees.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// How many trees were run by at least one entity in the last tick.
+    pub fn active_count(&self) -> usize {
+        self.entity_counts
+            .values()
+            .filter(|count| **count > 0)
+            .count()
+    }
+
+    /// How many times the plugin ticked a tree for an entity, across all trees, since the last [`reset_counters`][BehaviourTrees::reset_counters].
+    ///
+    /// A cheap health check that doesn't need the [diagnostics][BehaviourTreePlugin::with_diagnostics].
+    pub fn total_ticks(&self) -> u64 {
+        self.total_ticks
+    }
+
+    /// Like [`total_ticks`][BehaviourTrees::total_ticks], for a single tree.
+    pub fn ticks_for(&self, id: BehaviourId) -> u64 {
+        self.tick_counts.get(&id).copied().unwrap_or(0)
+    }
+
+    /// Sets [`total_ticks`][BehaviourTrees::total_ticks] and [`ticks_for`][BehaviourTrees::ticks_for] back to 0.
+    pub fn reset_counters(&mut self) {
+        self.total_ticks = 0;
+        self.tick_counts.clear();
+    }
+
+    /// The summary shown through [`Reflect`], built from the current trees.
+    ///
+    /// [Active counts][BehaviourTrees::active_count] are still from the last tick.
+    pub fn summary(&self) -> BehaviourTreesSummary {
+        BehaviourTreesSummary {
+            len: self.len(),
+            active_count: self.active_count(),
+            trees: self
+                .stored()
+                .map(|(id, tree)| (id, tree.type_name().to_owned()))
+                .collect(),
+        }
+    }
+
+    /// The components the tree with the given ID accesses, see [`Behaviour::dependencies`].
+    ///
+    /// Recorded when the tree is first initialized, so this is empty for trees that haven't run yet.
+    pub fn dependencies(&self, id: BehaviourId) -> &[ComponentId] {
+        self.dependencies.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// How many entities ran the tree with the given ID in the last tick.
+    ///
+    /// Entities that were [skipped][Skip] or excluded by the [entity filter][BehaviourTreePlugin::with_entity_filter] aren't counted.
+    pub fn entity_count(&self, id: BehaviourId) -> usize {
+        self.entity_counts.get(&id).copied().unwrap_or(0)
+    }
+
+    /// All entities using the tree with the given ID, through their [`BehaviourId`] or [`BehaviourIds`].
+    ///
+    /// Unlike [`entity_count`][BehaviourTrees::entity_count], this includes [skipped][Skip] entities, e.g. to pause or resume everything using a tree:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// fn pause_tree(world: &mut World, id: BehaviourId) {
+    ///     let entities = world.resource::<BehaviourTrees>().entities_for(id, world);
+    ///     for entity in entities {
+    ///         world.entity_mut(entity).insert(Skip);
+    ///     }
+    /// }
+    /// ```
+    pub fn entities_for(&self, id: BehaviourId, world: &World) -> Vec<Entity> {
+        assigned_entities(world)
+            .filter(|(_, ids)| ids.contains(&id))
+            .map(|(entity, _)| entity)
+            .collect()
+    }
+
+    /// A text summary of all trees, e.g. for bug reports. Trees are listed in order, each with its [shape][Behaviour::shape]:
+    ///
+    /// ```text
+    /// tree#0 guard: bevy_behaviour_tree_core::compositor::Sequence, 2 entities, initialized
+    ///   sequence
+    ///     leaf
+    ///     retry (max_tries: 3)
+    ///       leaf
+    /// ```
+    ///
+    /// Names come from the [`BehaviourNameRegistry`], if there is one. Trees that are currently running are left out.
+    pub fn debug_dump(&self, world: &World) -> String {
+        let registry = world.get_resource::<BehaviourNameRegistry>();
+        let mut dump = String::new();
+
+        let mut entity_counts = HashMap::<BehaviourId, usize>::default();
+        for (_, ids) in assigned_entities(world) {
+            for id in ids.into_iter().collect::<HashSet<_>>() {
+                *entity_counts.entry(id).or_default() += 1;
+            }
+        }
+
+        for (id, tree) in self.stored() {
+            dump.push_str(&id.to_string());
+            if let Some(name) = registry.and_then(|registry| registry.name(id)) {
+                dump.push(' ');
+                dump.push_str(name);
+            }
+
+            let entities = entity_counts.get(&id).copied().unwrap_or(0);
+            let initialized = match self.initialized.contains(&id) {
+                true => "initialized",
+                false => "not initialized",
+            };
+            dump.push_str(&format!(
+                ": {}, {entities} entities, {initialized}\n",
+                tree.type_name()
+            ));
+
+            dump_shape(&mut dump, &tree.shape(), 1);
+        }
+
+        dump
+    }
+
+    /// Stops the plugin from ticking the tree with the given ID, for every entity, until it's [resumed][BehaviourTrees::resume].
+    ///
+    /// Like [skipping][Skip] all entities using it, but kept in [snapshots][BehaviourTrees::snapshot]:
+    /// ```
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// let mut trees = BehaviourTrees::default();
+    /// let cutscene = trees.create(always_succeed());
+    /// trees.pause(cutscene);
+    ///
+    /// let mut restored = BehaviourTrees::default();
+    /// restored.create(always_succeed());
+    /// restored.restore(&trees.snapshot());
+    /// assert!(restored.is_paused(cutscene));
+    /// ```
+    pub fn pause(&mut self, id: BehaviourId) {
+        self.paused.insert(id);
+    }
+
+    /// Lets the plugin tick a [paused][BehaviourTrees::pause] tree again.
+    pub fn resume(&mut self, id: BehaviourId) {
+        self.paused.remove(&id);
+    }
+
+    /// Whether the tree with the given ID is [paused][BehaviourTrees::pause].
+    pub fn is_paused(&self, id: BehaviourId) -> bool {
+        self.paused.contains(&id)
+    }
+
+    /// Lets the plugin run the tree with the given ID at most `max` times per entity.
+    /// Once an entity has reached the limit, it's treated as if it was [skipped][Skip] for this tree.
+    ///
+    /// Useful for trees that should only ever play once per entity, like an intro:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::{prelude::*, behaviour::IntoBehaviour};
+    /// # fn greet(_: In<Entity>) -> Status { Status::Success }
+    /// let mut trees = BehaviourTrees::default();
+    /// let intro = trees.create(greet.into_behaviour());
+    /// trees.set_max_runs_per_entity(intro, 1);
+    /// ```
+    /// Only ticks done by the plugin are counted, not [`tick_entity`][BehaviourTrees::tick_entity].
+    pub fn set_max_runs_per_entity(&mut self, id: BehaviourId, max: u64) {
+        self.max_runs.insert(id, max);
+    }
+
+    /// Removes the limit set with [`set_max_runs_per_entity`][BehaviourTrees::set_max_runs_per_entity].
+    /// Run counts are kept, in case a limit is set again.
+    pub fn clear_max_runs_per_entity(&mut self, id: BehaviourId) {
+        self.max_runs.remove(&id);
+    }
+
+    /// How many times the plugin ran the tree with the given ID for the entity.
+    ///
+    /// Always 0 for trees without a [limit][BehaviourTrees::set_max_runs_per_entity].
+    pub fn run_count(&self, id: BehaviourId, entity: Entity) -> u64 {
+        self.run_counts.get(&(id, entity)).copied().unwrap_or(0)
+    }
+
+    /// Resets the run count of the entity for the tree with the given ID, so it can run again.
+    pub fn clear_run_count(&mut self, id: BehaviourId, entity: Entity) {
+        self.run_counts.remove(&(id, entity));
+    }
+
+    fn run_limit_reached(&self, id: BehaviourId, entity: Entity) -> bool {
+        self.max_runs
+            .get(&id)
+            .is_some_and(|max| self.run_count(id, entity) >= *max)
+    }
+
+    /// Calls `observer` every time the tree with the given ID succeeds or fails for any entity.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// let mut trees = BehaviourTrees::default();
+    /// let id = trees.create(always_succeed());
+    ///
+    /// let handle = trees.observe(id, |entity, status| info!("{entity:?} finished with {status:?}"));
+    /// // ...
+    /// trees.unobserve(id, handle);
+    /// ```
+    /// Like [run limits][BehaviourTrees::set_max_runs_per_entity], this only applies to ticks done by the plugin.
+    pub fn observe(
+        &mut self,
+        id: BehaviourId,
+        observer: impl Fn(Entity, Status) + Send + Sync + 'static,
+    ) -> ObserverHandle {
+        let handle = ObserverHandle(self.next_observer);
+        self.next_observer += 1;
+
+        self.observers
+            .entry(id)
+            .or_default()
+            .push((handle, Box::new(observer)));
+
+        handle
+    }
+
+    /// Like [`observe`][BehaviourTrees::observe], but only called when the tree finishes for one entity.
+    /// Other entities sharing the tree don't trigger it:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// # let mut world = World::default();
+    /// let mut trees = BehaviourTrees::default();
+    /// let id = trees.create(always_succeed());
+    ///
+    /// let boss = world.spawn(id).id();
+    /// trees.observe_entity(id, boss, |_, status| info!("the boss finished with {status:?}"));
+    /// ```
+    /// The observer is removed when the entity is [forgotten][BehaviourTrees::forget_entity].
+    pub fn observe_entity(
+        &mut self,
+        id: BehaviourId,
+        entity: Entity,
+        observer: impl Fn(Entity, Status) + Send + Sync + 'static,
+    ) -> ObserverHandle {
+        let handle = ObserverHandle(self.next_observer);
+        self.next_observer += 1;
+
+        self.entity_observers
+            .entry((id, entity))
+            .or_default()
+            .push((handle, Box::new(observer)));
+
+        handle
+    }
+
+    /// Removes an observer added with [`observe`][BehaviourTrees::observe] or [`observe_entity`][BehaviourTrees::observe_entity]. Returns whether it existed.
+    pub fn unobserve(&mut self, id: BehaviourId, handle: ObserverHandle) -> bool {
+        let entity_observers = self
+            .entity_observers
+            .iter_mut()
+            .filter(|((observed_id, _), _)| *observed_id == id)
+            .map(|(_, observers)| observers);
+
+        let mut removed = false;
+        for observers in self
+            .observers
+            .get_mut(&id)
+            .into_iter()
+            .chain(entity_observers)
+        {
+            let count = observers.len();
+            observers.retain(|(observer, _)| *observer != handle);
+            removed |= observers.len() != count;
+        }
+
+        removed
+    }
+
+    /// Counts a run towards the entity's [limit][BehaviourTrees::set_max_runs_per_entity], and notifies observers if the tree finished.
+    pub(crate) fn finish_run(&mut self, id: BehaviourId, entity: Entity, status: Status) {
+        self.total_ticks += 1;
+        *self.tick_counts.entry(id).or_default() += 1;
+
+        if self.max_runs.contains_key(&id) {
+            *self.run_counts.entry((id, entity)).or_default() += 1;
+        }
+
+        if status == Status::Running {
+            return;
+        }
+
+        let observers = self.observers.get(&id).into_iter().flatten();
+        let entity_observers = self
+            .entity_observers
+            .get(&(id, entity))
+            .into_iter()
+            .flatten();
+
+        for (_, observer) in observers.chain(entity_observers) {
+            observer(entity, status);
+        }
+    }
+
+    /// Recounts the entities per tree at the start of a tick.
+    pub(crate) fn count_entities(&mut self, queued: &[(Entity, BehaviourId)]) {
+        self.entity_counts.clear();
+        // entity trees are queued under the null ID, but aren't stored here. Neither are removed trees entities still point to.
+        for (_, id) in queued {
+            if self.is_valid(*id) {
+                *self.entity_counts.entry(*id).or_default() += 1;
+            }
+        }
+
+        // rebuilding the list of trees allocates, so only do it when it changed.
+        if self.summary_outdated {
+            self.summary = self.summary();
+            self.summary_outdated = false;
+        } else {
+            self.summary.active_count = self.active_count();
+        }
+    }
+
+    /// Temporarily moves the behaviour belonging to `id` out of the internal storage.
+    /// Used for behaviour initialization logic.
+    ///
+    /// `scope` is not ran if the behaviour doesn't exist.
+    pub(crate) fn behaviour_scope<F>(&mut self, id: BehaviourId, mut scope: F)
+    where
+        F: FnMut(&mut Self, &mut Box<dyn Behaviour>),
+    {
+        let Some(behaviour_borrow) = self.slot_mut(id) else {
+            return;
+        };
+
+        // removed trees leave an empty slot behind.
+        let Some(mut behaviour) = behaviour_borrow.take() else {
+            return;
+        };
+
+        scope(self, &mut behaviour);
+
+        self.trees[id.index] = Some(behaviour);
+    }
+
+    /// Runs the tree with the given ID once for the entity, initializing it first if needed.
+    /// Useful for running trees outside of the schedule, e.g. in tests or cutscenes.
+    ///
+    /// Returns `None` if the tree doesn't exist.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// let mut world = World::default();
+    /// let mut trees = BehaviourTrees::default();
+    ///
+    /// let id = trees.create(always_succeed());
+    /// let entity = world.spawn(id).id();
+    ///
+    /// assert_eq!(trees.tick_entity(entity, id, &mut world), Some(Status::Success));
+    /// ```
+    /// Like regular ticks, panics are passed to the [error handler][BehaviourTreePlugin::with_error_handler] and count as [`Status::Failure`],
+    /// and trees nested deeper than the [`TreeDepthGuard`] allows fail, if `world` has them.
+    /// [Subtrees][crate::leaves::SubtreeBehaviour] can't be run unless `self` is the world's [`BehaviourTrees`] resource, though.
+    pub fn tick_entity(
+        &mut self,
+        entity: Entity,
+        id: BehaviourId,
+        world: &mut World,
+    ) -> Option<Status> {
+        let mut status = None;
+
+        self.behaviour_scope(id, |trees, behaviour| {
+            let initialized = trees.initialized.contains(&id);
+            let warmed_up = trees.warmed_up.contains(&(id, entity));
+
+            let (ran, result) = run_guarded(world, entity, id, behaviour, initialized, warmed_up);
+            if ran {
+                trees.mark_ran(id, entity, behaviour.as_ref());
+            }
+
+            status = Some(handle_panic(world, entity, id, result));
+        });
+
+        status
+    }
+
+    /// Marks the tree as initialized and warmed up for the entity after it ran, recording its [dependencies][Behaviour::dependencies] the first time.
+    fn mark_ran(&mut self, id: BehaviourId, entity: Entity, behaviour: &dyn Behaviour) {
+        if self.initialized.insert(id) {
+            self.dependencies.insert(id, behaviour.dependencies());
+        }
+        self.warmed_up.insert((id, entity));
+    }
+
+    /// Runs every tree assigned to the entity once, through its [`BehaviourId`] and [`BehaviourIds`], with [`tick_entity`][BehaviourTrees::tick_entity].
+    ///
+    /// Returns the status of each tree that exists.
+    pub fn run_all_for_entity(
+        &mut self,
+        entity: Entity,
+        world: &mut World,
+    ) -> Vec<(BehaviourId, Status)> {
+        let Some(entity_ref) = world.get_entity(entity) else {
+            return Vec::new();
+        };
+
+        let ids = entity_ref
+            .get::<BehaviourId>()
+            .into_iter()
+            .chain(
+                entity_ref
+                    .get::<BehaviourIds>()
+                    .into_iter()
+                    .flat_map(|ids| &ids.0),
+            )
+            .copied()
+            .collect::<Vec<_>>();
+
+        ids.into_iter()
+            .filter_map(|id| Some((id, self.tick_entity(entity, id, world)?)))
+            .collect()
+    }
+
+    /// Like [`run_all_for_entity`][BehaviourTrees::run_all_for_entity], but also runs the entity's [`EntityBehaviourTree`] if it has one.
+    ///
+    /// Returns the statuses of the shared trees first, in the same order, followed by the status of the entity's own tree.
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// let mut world = World::default();
+    /// let mut trees = BehaviourTrees::default();
+    ///
+    /// let id = trees.create(always_succeed());
+    /// let entity = world.spawn((id, EntityBehaviourTree::new(always_fail()))).id();
+    ///
+    /// assert_eq!(trees.tick_entity_all(entity, &mut world), [Status::Success, Status::Failure]);
+    /// ```
+    pub fn tick_entity_all(&mut self, entity: Entity, world: &mut World) -> Vec<Status> {
+        let mut statuses = self
+            .run_all_for_entity(entity, world)
+            .into_iter()
+            .map(|(_, status)| status)
+            .collect::<Vec<_>>();
+
+        statuses.extend(tick_entity_tree(world, entity));
+        statuses
+    }
+
+    /// The [type name][Behaviour::type_name] of the tree with the given ID.
+    pub(crate) fn type_name(&self, id: BehaviourId) -> Option<&'static str> {
+        Some(self.slot(id)?.as_ref()?.type_name())
+    }
+
+    /// The [debug state][Behaviour::debug_state] of the tree with the given ID for the entity.
+    pub(crate) fn debug_state(&self, id: BehaviourId, entity: Entity) -> Option<String> {
+        Some(self.slot(id)?.as_ref()?.debug_state(entity))
+    }
+
+    /// Forgets all progress the tree made for the entity, like the current child of a sequence or a [latched][crate::prelude::Decorator::latch] result.
+    ///
+    /// The tree starts over from the beginning the next time the entity is ticked.
+    pub fn reset_for_entity(&mut self, id: BehaviourId, entity: Entity) {
+        if let Some(Some(behaviour)) = self.slot_mut(id) {
+            behaviour.reset_entity(entity);
+        }
+        self.warmed_up.remove(&(id, entity));
+    }
+
+    /// Forgets all progress the tree made for the entity, including its [run count][BehaviourTrees::run_count].
+    ///
+    /// Called automatically when an entity loses the tree's [`BehaviourId`], depending on the [`CleanupPolicy`].
+    pub fn forget_entity_tree(&mut self, entity: Entity, id: BehaviourId) {
+        self.reset_for_entity(id, entity);
+        self.run_counts.remove(&(id, entity));
+    }
+
+    /// Forgets all progress every tree made for the entity, including its [run counts][BehaviourTrees::run_count] and observers.
+    pub fn forget_entity(&mut self, entity: Entity) {
+        for behaviour in self.trees.iter_mut().flatten() {
+            behaviour.reset_entity(entity);
+        }
+
+        self.run_counts.retain(|(_, counted), _| *counted != entity);
+        self.warmed_up.retain(|(_, warmed)| *warmed != entity);
+        self.entity_observers
+            .retain(|(_, observed), _| *observed != entity);
+    }
+
+    /// Captures the state of all trees, e.g. for save games.
+    ///
+    /// Only node state (like retry counters and sequence progress) and whether trees are [paused][BehaviourTrees::pause] is captured, not the trees themselves.
+    /// With the `serde` feature enabled, the snapshot can be serialized.
+    pub fn snapshot(&self) -> BehaviourTreesSnapshot {
+        BehaviourTreesSnapshot {
+            trees: self
+                .trees
+                .iter()
+                .enumerate()
+                .map(|(index, tree)| {
+                    let id = self.id_at(index);
+                    tree.as_ref().map(|behaviour| TreeSnapshot {
+                        type_name: behaviour.type_name().to_owned(),
+                        version: id.version,
+                        paused: self.is_paused(id),
+                        state: behaviour.save_state(),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Restores the state of all trees from a [`snapshot`][BehaviourTrees::snapshot].
+    ///
+    /// Trees from the snapshot that don't exist here are ignored. Use [`restore_with_registry`][BehaviourTrees::restore_with_registry] to recreate them.
+    /// So are trees whose slot was reused since, i.e. whose [`BehaviourId`] changed [version][BehaviourId::version],
+    /// and trees that were [replaced][BehaviourTrees::replace] with one of a different [type][Behaviour::type_name].
+    pub fn restore(&mut self, snapshot: &BehaviourTreesSnapshot) {
+        for (index, saved) in snapshot.trees.iter().enumerate() {
+            let Some(saved) = saved else {
+                continue;
+            };
+
+            let id = BehaviourId {
+                index,
+                version: saved.version,
+            };
+            let Some(Some(behaviour)) = self.slot_mut(id) else {
+                continue;
+            };
+            // the tree may have been replaced since, keeping its ID.
+            if behaviour.type_name() != saved.type_name {
+                continue;
+            }
+            behaviour.load_state(&saved.state);
+
+            match saved.paused {
+                true => self.pause(id),
+                false => self.resume(id),
+            }
+        }
+    }
+
+    /// Like [`restore`][BehaviourTrees::restore], but trees missing from `self` are recreated from `registry` first, keeping their [`BehaviourId`]s.
+    ///
+    /// Trees whose type isn't registered are left empty, and so are slots that were reused for a newer tree since the snapshot was taken.
+    /// See [`restore_registered`][BehaviourTrees::restore_registered] to use the types [registered][BehaviourTrees::register_type] here.
+    pub fn restore_with_registry(
+        &mut self,
+        snapshot: &BehaviourTreesSnapshot,
+        registry: &BehaviourTypeRegistry,
+    ) {
+        if self.trees.len() < snapshot.trees.len() {
+            self.trees.resize_with(snapshot.trees.len(), || None);
+        }
+        if self.versions.len() < snapshot.trees.len() {
+            self.versions.resize(snapshot.trees.len(), 0);
+        }
+
+        for (index, saved) in snapshot.trees.iter().enumerate() {
+            let Some(saved) = saved else {
+                continue;
+            };
+
+            // rolling a slot back to an older version would make IDs of its removed trees valid again.
+            if self.trees[index].is_some() || self.versions[index] > saved.version {
+                continue;
+            }
+
+            if let Some(behaviour) = registry.deserialize(&saved.type_name, &saved.state) {
+                self.trees[index] = Some(behaviour);
+                self.versions[index] = saved.version;
+                self.summary_outdated = true;
+            }
+        }
+        let trees = &self.trees;
+        self.free.retain(|index| trees[*index].is_none());
+
+        self.restore(snapshot);
+    }
+
+    /// Registers a behaviour type with these trees, so it can be [rebuilt][BehaviourTrees::build_type] from `name` or from its [type name][Behaviour::type_name].
+    ///
+    /// Only built-in behaviours without configuration are registered automatically, see [`BehaviourTypeRegistry`]. Custom nodes can be registered e.g. in a plugin's `build`:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_behaviour_tree_core::prelude::*;
+    /// # struct Wander;
+    /// # impl Behaviour for Wander {
+    /// #     fn initialize(&mut self, _: &mut World) {}
+    /// #     fn run(&mut self, _: Entity, _: &mut World) -> Status { Status::Running }
+    /// # }
+    /// let mut trees = BehaviourTrees::default();
+    /// trees.register_type::<Wander>("wander", |_| Box::new(Wander));
+    ///
+    /// assert!(trees.build_type("wander").is_some());
+    /// ```
+    /// Replaces any type previously registered under the same name.
+    pub fn register_type<T: Behaviour + 'static>(
+        &mut self,
+        name: &'static str,
+        factory: BehaviourDeserializer,
+    ) {
+        self.types.register(name, factory);
+        self.types.register_type::<T>(factory);
+    }
+
+    /// Builds a behaviour [registered][BehaviourTrees::register_type] under the name or type name, without any saved state. Returns `None` if there's none.
+    pub fn build_type(&self, name: &str) -> Option<Box<dyn Behaviour>> {
+        self.types.deserialize(name, &BehaviourState::default())
+    }
+
+    /// Like [`restore_with_registry`][BehaviourTrees::restore_with_registry], with the types [registered][BehaviourTrees::register_type] here.
+    pub fn restore_registered(&mut self, snapshot: &BehaviourTreesSnapshot) {
+        let types = std::mem::take(&mut self.types);
+        self.restore_with_registry(snapshot, &types);
+        self.types = types;
+    }
+}
+
+/// The state of all trees in [`BehaviourTrees`] at one point in time. See [`BehaviourTrees::snapshot`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BehaviourTreesSnapshot {
+    trees: Vec<Option<TreeSnapshot>>,
+}
+
+impl BehaviourTreesSnapshot {
+    /// The captured state of the tree with the given ID.
+    pub fn get(&self, id: BehaviourId) -> Option<&BehaviourState> {
+        self.trees
+            .get(id.index)?
+            .as_ref()
+            .filter(|tree| tree.version == id.version)
+            .map(|tree| &tree.state)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct TreeSnapshot {
+    type_name: String,
+    version: u32,
+    paused: bool,
+    state: BehaviourState,
+}
+
+/// Rebuilds a behaviour from the state it was saved with, see [`Behaviour::save_state`].
+///
+/// The state is loaded into the rebuilt behaviour afterwards, so deserializers only need it to rebuild the tree itself, if at all.
+pub type BehaviourDeserializer = fn(&BehaviourState) -> Box<dyn Behaviour>;
+
+/// Maps behaviour type names to functions rebuilding them.
+///
+/// Used by [`BehaviourTrees::restore_with_registry`] to recreate trees that only exist in a snapshot.
+///
+/// Built-in behaviours without any configuration, like [`Despawn`], are registered by default.
+/// All others are built from systems, children or values that aren't part of a snapshot, so they need to be registered with a deserializer that builds them again.
+#[derive(Resource)]
+pub struct BehaviourTypeRegistry {
+    deserializers: HashMap<String, BehaviourDeserializer>,
+}
+
+impl Default for BehaviourTypeRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            deserializers: HashMap::default(),
+        };
+        registry.register_type::<Despawn>(|_| Box::new(Despawn));
+        registry
+    }
+}
+
+impl BehaviourTypeRegistry {
+    /// Registers a deserializer for the given type name. Replaces any deserializer previously registered for it.
+    pub fn register(&mut self, type_name: impl Into<String>, deserializer: BehaviourDeserializer) {
+        self.deserializers.insert(type_name.into(), deserializer);
+    }
+
+    /// Registers a deserializer for `T`, using its [type name][Behaviour::type_name].
+    pub fn register_type<T: Behaviour>(&mut self, deserializer: BehaviourDeserializer) {
+        self.register(std::any::type_name::<T>(), deserializer);
+    }
+
+    /// Whether a deserializer is registered for the given type name.
+    pub fn contains(&self, type_name: &str) -> bool {
+        self.deserializers.contains_key(type_name)
+    }
+
+    /// Rebuilds a behaviour from its type name and saved state. Returns `None` if the type isn't registered.
+    pub fn deserialize(
+        &self,
+        type_name: &str,
+        state: &BehaviourState,
+    ) -> Option<Box<dyn Behaviour>> {
+        self.deserializers
+            .get(type_name)
+            .map(|deserializer| deserializer(state))
+    }
+}
+
+/// Human-readable names for trees, used when [displaying][BehaviourId::display_with_registry] their IDs.
+///
+/// Names of [removed][BehaviourTrees::remove] trees are dropped by [`BehaviourTreePlugin`] at the end of each tick, so they don't carry over to new trees in the same slot.
+#[derive(Resource, Default)]
+pub struct BehaviourNameRegistry {
+    names: HashMap<BehaviourId, String>,
+}
+
+impl BehaviourNameRegistry {
+    /// Names the tree with the given ID. Replaces any name it had before.
+    pub fn set_name(&mut self, id: BehaviourId, name: impl Into<String>) {
+        self.names.insert(id, name.into());
+    }
+
+    /// The name of the tree with the given ID, if it has one.
+    pub fn name(&self, id: BehaviourId) -> Option<&str> {
+        self.names.get(&id).map(String::as_str)
+    }
+
+    /// Removes the name of the tree with the given ID, returning it if it had one.
+    pub fn remove(&mut self, id: BehaviourId) -> Option<String> {
+        self.names.remove(&id)
+    }
+}
+
+fn forget_removed_names(trees: Res<BehaviourTrees>, mut names: ResMut<BehaviourNameRegistry>) {
+    // only touch the registry if there's something to forget, so it isn't marked as changed every tick.
+    if names.names.keys().any(|id| !trees.is_valid(*id)) {
+        names.names.retain(|id, _| trees.is_valid(*id));
+    }
+}
+
+/// Skips processing the behaviour tree for this entity.
+#[derive(Component, PartialEq, Eq, Debug, Default)]
+pub struct Skip;
+
+/// Runs the entity's tree until it succeeds or fails once, then removes its [`BehaviourId`] along with this component.
+///
+/// Meant for one-shot actions, like playing an effect:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::prelude::*;
+/// # use bevy_behaviour_tree_core::plugin::TickOnce;
+/// fn spawn_explosion(mut commands: Commands, trees: Res<EffectTrees>) {
+///     commands.spawn((trees.explosion, TickOnce));
+/// }
+/// # #[derive(Resource)]
+/// # struct EffectTrees { explosion: BehaviourId }
+/// ```
+/// Trees from [`BehaviourIds`] aren't affected. An [`EntityBehaviourTree`] is removed the same way once it's done.
+#[derive(Component, PartialEq, Eq, Debug, Default)]
+pub struct TickOnce;
+
+/// Removes the tree from [`TickOnce`] entities once it's done.
+pub(crate) fn finish_tick_once(world: &mut World, entity: Entity, id: BehaviourId, status: Status) {
+    if status == Status::Running {
+        return;
+    }
+
+    // the tree may have despawned the entity.
+    let Some(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+
+    if !entity.contains::<TickOnce>() {
+        return;
+    }
+
+    if id.is_null() {
+        entity.remove::<(EntityBehaviourTree, TickOnce)>();
+    } else if entity.get::<BehaviourId>() == Some(&id) {
+        entity.remove::<(BehaviourId, TickOnce)>();
+    }
+}
+
+/// Adds one line per node to a [`BehaviourTrees::debug_dump`], indented by depth.
+fn dump_shape(dump: &mut String, shape: &BehaviourShape, depth: usize) {
+    dump.push_str(&"  ".repeat(depth));
+
+    match shape {
+        BehaviourShape::Leaf => dump.push_str("leaf\n"),
+        BehaviourShape::Node {
+            kind,
+            config,
+            children,
+        } => {
+            dump.push_str(kind);
+            if !config.is_empty() {
+                dump.push_str(&format!(" ({config})"));
+            }
+            dump.push('\n');
+
+            for child in children {
+                dump_shape(dump, child, depth + 1);
+            }
+        }
+    }
+}
+
+/// An ID for a behaviour tree.
+/// This is a component type. If this is on an entity, that entity is ticked for the given tree.
+///
+/// Defaults to the [null ID][BehaviourId::null], which entities can hold to mean "no tree assigned".
+///
+/// Slots of [removed][BehaviourTrees::remove] trees are reused with a new [version][BehaviourId::version],
+/// so IDs of removed trees never point at the tree that replaced them. See [`BehaviourTrees::is_valid`].
+#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BehaviourId {
+    pub(crate) index: usize,
+    pub(crate) version: u32,
+}
+
+impl BehaviourId {
+    /// An ID that never belongs to a tree. Entities with it aren't ticked.
+    pub const fn null() -> Self {
+        Self {
+            index: usize::MAX,
+            version: 0,
+        }
+    }
+
+    /// Whether this is the [null ID][BehaviourId::null].
+    pub const fn is_null(&self) -> bool {
+        self.index == usize::MAX
+    }
+
+    /// The slot the tree is stored in.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// How many times the slot was reused before this ID was handed out.
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Displays the tree's name from the registry, falling back to the [regular format][Display] for trees without one.
+    /// ```
+    /// # use bevy_behaviour_tree_core::{prelude::*, behaviour::IntoBehaviour, plugin::BehaviourNameRegistry};
+    /// # let mut trees = BehaviourTrees::default();
+    /// let guard = trees.create(always_succeed().into_behaviour());
+    /// let mut registry = BehaviourNameRegistry::default();
+    /// registry.set_name(guard, "guard");
+    ///
+    /// assert_eq!(guard.display_with_registry(&registry).to_string(), "guard");
+    /// ```
+    pub fn display_with_registry<'a>(
+        &'a self,
+        registry: &'a BehaviourNameRegistry,
+    ) -> impl Display + 'a {
+        NamedBehaviourId {
+            id: *self,
+            registry,
+        }
+    }
+}
+
+/// Shows trees as `tree#0`, or `tree#null` for the [null ID][BehaviourId::null]. Reused slots show their version too, like `tree#0v1`.
+impl Display for BehaviourId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_null() {
+            write!(f, "tree#null")
+        } else {
+            write!(f, "tree#{}", self.index)?;
+            if self.version > 0 {
+                write!(f, "v{}", self.version)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+struct NamedBehaviourId<'a> {
+    id: BehaviourId,
+    registry: &'a BehaviourNameRegistry,
+}
+
+impl Display for NamedBehaviourId<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.registry.name(self.id) {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+impl Default for BehaviourId {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
+/// Runs several trees on the same entity, in addition to its [`BehaviourId`] if it has one.
+///
+/// The trees are ticked independently of each other, in the order of their IDs.
+#[derive(Component, Clone, PartialEq, Eq, Hash, Debug, Reflect, Default)]
+#[reflect(Component)]
+pub struct BehaviourIds(pub Vec<BehaviourId>);
+
+/// A tree owned by a single entity, instead of being shared through [`BehaviourTrees`].
+///
+/// Entity trees are ticked right after the shared ones, and go through the same steps: they respect [`Skip`], the [entity filter][BehaviourTreePlugin::with_entity_filter],
+/// the [`EntityStepper`], [`TickOnce`] and everything else the plugin is configured with.
+///
+/// Anything keyed by tree, like [observers][BehaviourTrees::observe], [run limits][BehaviourTrees::set_max_runs_per_entity] or [tick counts][BehaviourTrees::ticks_for],
+/// sees entity trees under the [null ID][BehaviourId::null].
+#[derive(Component)]
+pub struct EntityBehaviourTree {
+    // `None` while the tree is running.
+    behaviour: Option<Box<dyn Behaviour>>,
+    initialized: bool,
+}
+
+impl EntityBehaviourTree {
+    /// A tree for a single entity.
+    pub fn new<T: Behaviour + 'static>(behaviour: T) -> Self {
+        Self::new_boxed(Box::new(behaviour))
+    }
+
+    /// Like [`new`][EntityBehaviourTree::new], for trees that are already boxed.
+    pub fn new_boxed(behaviour: Box<dyn Behaviour>) -> Self {
+        Self {
+            behaviour: Some(behaviour),
+            initialized: false,
+        }
+    }
+
+    /// The tree, unless it's currently running.
+    pub(crate) fn behaviour(&self) -> Option<&dyn Behaviour> {
+        self.behaviour.as_deref()
+    }
+
+    /// Swaps in a new tree in place, returning the old one. Counterpart to [`BehaviourTrees::replace`].
+    ///
+    /// The new tree is initialized the next time it runs. Unlike removing and re-inserting the component,
+    /// this keeps `Added<EntityBehaviourTree>` from firing again, while still marking the component as changed.
+    pub fn replace(&mut self, behaviour: Box<dyn Behaviour>) -> Option<Box<dyn Behaviour>> {
+        self.initialized = false;
+        self.behaviour.replace(behaviour)
+    }
+}
+
+/// Spawns `count` entities with `bundle`, each with its own clone of the tree.
+///
+/// Unlike sharing one [`BehaviourId`], every entity's tree can then be [restored][BehaviourTrees::restore] or replaced individually.
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, plugin::batch_spawn};
+/// fn spawn_agents(mut commands: Commands, mut trees: ResMut<BehaviourTrees>) {
+///     let agents = batch_spawn(500, always_succeed(), Transform::default(), &mut commands, &mut trees);
+/// }
+/// ```
+pub fn batch_spawn<T: Behaviour + Clone, B: Bundle + Clone>(
+    count: usize,
+    behaviour: T,
+    bundle: B,
+    commands: &mut Commands,
+    trees: &mut BehaviourTrees,
+) -> Vec<Entity> {
+    (0..count)
+        .map(|_| {
+            let id = trees.create(behaviour.clone());
+            commands.spawn((bundle.clone(), id)).id()
+        })
+        .collect()
+}
+
+/// Builder for [`batch_spawn`].
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_behaviour_tree_core::{prelude::*, plugin::BatchSpawner};
+/// fn spawn_agents(mut commands: Commands, mut trees: ResMut<BehaviourTrees>) {
+///     let agents = BatchSpawner::new(always_succeed())
+///         .with_bundle(Transform::default())
+///         .spawn_n(500, &mut commands, &mut trees);
+/// }
+/// ```
+pub struct BatchSpawner<T: Behaviour + Clone, B: Bundle + Clone = ()> {
+    behaviour: T,
+    bundle: B,
+}
+
+impl<T: Behaviour + Clone> BatchSpawner<T> {
+    /// Spawns entities with clones of `behaviour`, and nothing else.
+    pub fn new(behaviour: T) -> Self {
+        Self {
+            behaviour,
+            bundle: (),
+        }
+    }
+}
+
+impl<T: Behaviour + Clone, B: Bundle + Clone> BatchSpawner<T, B> {
+    /// Also spawns every entity with a clone of `bundle`.
+    pub fn with_bundle<NewB: Bundle + Clone>(self, bundle: NewB) -> BatchSpawner<T, NewB> {
+        BatchSpawner {
+            behaviour: self.behaviour,
+            bundle,
+        }
+    }
+
+    /// Spawns `count` entities. See [`batch_spawn`].
+    pub fn spawn_n(
+        &self,
+        count: usize,
+        commands: &mut Commands,
+        trees: &mut BehaviourTrees,
+    ) -> Vec<Entity> {
+        batch_spawn(
+            count,
+            self.behaviour.clone(),
+            self.bundle.clone(),
+            commands,
+            trees,
+        )
+    }
+}
+
+/// Runs the tree with the given ID once for the entity, initializing it first if needed.
+///
+/// The tree is only moved out of [`BehaviourTrees`] while it runs, so behaviours can run other trees through this as well.
+/// Returns `None` if the tree doesn't exist or is already running.
+pub(crate) fn tick(world: &mut World, entity: Entity, id: BehaviourId) -> Option<Status> {
+    let (mut behaviour, initialized, warmed_up) = {
+        let mut trees = world.get_resource_mut::<BehaviourTrees>()?;
+        let behaviour = trees.slot_mut(id)?.take()?;
+        (
+            behaviour,
+            trees.initialized.contains(&id),
+            trees.warmed_up.contains(&(id, entity)),
+        )
+    };
+
+    let (ran, result) = run_guarded(world, entity, id, &mut behaviour, initialized, warmed_up);
+
+    // always put the behaviour back, even if it panicked. Leave trees that replaced it while running alone, though.
+    if let Some(mut trees) = world.get_resource_mut::<BehaviourTrees>() {
+        if let Some(None) = trees.slot(id) {
+            if ran {
+                trees.mark_ran(id, entity, behaviour.as_ref());
+            }
+            trees.trees[id.index] = Some(behaviour);
+        }
+    }
+
+    Some(handle_panic(world, entity, id, result))
+}
+
+/// Runs a tree that was taken out of its storage, unless it's nested too deeply. Catches panics if there's a [`PanicHandler`].
+///
+/// Returns whether the tree actually ran, along with its status or the panic payload.
+fn run_guarded(
+    world: &mut World,
+    entity: Entity,
+    id: BehaviourId,
+    behaviour: &mut Box<dyn Behaviour>,
+    initialized: bool,
+    warmed_up: bool,
+) -> (bool, std::thread::Result<Status>) {
+    let too_deep = world
+        .get_resource_mut::<TreeDepthGuard>()
+        .is_some_and(|mut guard| {
+            guard.depth += 1;
+            guard.depth > guard.max_depth
+        });
+
+    let catch = world.contains_resource::<PanicHandler>();
+
+    let result = if too_deep {
+        warn!("{entity:?} exceeded the maximum tree depth when running tree {id}");
+        Ok(Status::Failure)
+    } else {
+        let mut run = || {
+            if !initialized {
+                behaviour.initialize(world);
+            }
+
+            if !warmed_up {
+                behaviour.warm_up(entity, world);
+            }
+
+            behaviour.run(entity, world)
+        };
+
+        match catch {
+            true => catch_unwind(AssertUnwindSafe(run)),
+            false => Ok(run()),
+        }
+    };
+
+    if let Some(mut guard) = world.get_resource_mut::<TreeDepthGuard>() {
+        guard.depth -= 1;
+    }
+
+    (!too_deep, result)
+}
+
+/// Passes the payload of a caught panic to the [`PanicHandler`], counting the tree as failed.
+fn handle_panic(
+    world: &mut World,
+    entity: Entity,
+    id: BehaviourId,
+    result: std::thread::Result<Status>,
+) -> Status {
+    match result {
+        Ok(status) => status,
+        Err(payload) => {
+            // panics are only caught with a handler.
+            if let Some(handler) = world
+                .get_resource::<PanicHandler>()
+                .map(|handler| handler.0)
+            {
+                handler(world, entity, id, payload);
+            }
+            Status::Failure
+        }
+    }
+}
+
+/// Ticks an entry from [`queued_entities`]: the entity's [`EntityBehaviourTree`] for the null ID, a shared tree otherwise.
+pub(crate) fn tick_queued(world: &mut World, entity: Entity, id: BehaviourId) -> Option<Status> {
+    match id.is_null() {
+        true => tick_entity_tree(world, entity),
+        false => tick(world, entity, id),
+    }
+}
+
+/// All entities to tick this frame, sorted by tree.
+pub(crate) fn queued_entities<Filter: ReadOnlyWorldQuery + 'static>(
+    world: &mut World,
+) -> Vec<(Entity, BehaviourId)> {
+    let mut query = world
+        .query_filtered::<(Entity, &BehaviourId), (Without<Skip>, Filter)>()
+        .iter(world)
+        .map(|(entity, id)| (entity, *id))
+        .collect::<Vec<_>>(); // collect so we can reborrow world for initialization/running.
+
+    query.extend(
+        world
+            .query_filtered::<(Entity, &BehaviourIds), (Without<Skip>, Filter)>()
+            .iter(world)
+            .flat_map(|(entity, ids)| ids.0.iter().map(move |id| (entity, *id))),
+    );
+
+    let trees = world.resource::<BehaviourTrees>();
+    query.retain(|(entity, id)| {
+        !id.is_null() && !trees.is_paused(*id) && !trees.run_limit_reached(*id, *entity)
+    });
+
+    // sort to *hopefully* squeeze out some performance.
+    query.sort_by_key(|(_, id)| *id);
+
+    // entity trees go last, queued under the null ID.
+    let entity_trees = world
+        .query_filtered::<Entity, (With<EntityBehaviourTree>, Without<Skip>, Filter)>()
+        .iter(world)
+        .collect::<Vec<_>>();
+
+    let trees = world.resource::<BehaviourTrees>();
+    query.extend(
+        entity_trees
+            .into_iter()
+            .filter(|entity| !trees.run_limit_reached(BehaviourId::null(), *entity))
+            .map(|entity| (entity, BehaviourId::null())),
+    );
+
+    if let Some(sort) = world.get_resource::<TickSorter>() {
+        (sort.0)(world, &mut query);
+    }
+
+    query
+}
+
+/// The order set with [`BehaviourTreePlugin::with_sort_key`].
+#[derive(Resource, Clone, Copy)]
+struct TickSorter(fn(&World, &mut [(Entity, BehaviourId)]));
+
+fn sort_by_component<C: Component + Ord>(world: &World, queued: &mut [(Entity, BehaviourId)]) {
+    queued.sort_by(
+        |(a, _), (b, _)| match (world.get::<C>(*a), world.get::<C>(*b)) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        },
+    );
+}
+
+/// Runs the entity's [`EntityBehaviourTree`] once, if it has one.
+fn tick_entity_tree(world: &mut World, entity: Entity) -> Option<Status> {
+    // taking the tree out shouldn't count as a change.
+    let (mut behaviour, initialized) =
+        world
+            .get_mut::<EntityBehaviourTree>(entity)
+            .and_then(|mut tree| {
+                let tree = tree.bypass_change_detection();
+                Some((tree.behaviour.take()?, tree.initialized))
+            })?;
+
+    // the tree only ever runs for this entity, so it's warmed up along with initializing it.
+    let id = BehaviourId::null();
+    let (ran, result) = run_guarded(world, entity, id, &mut behaviour, initialized, initialized);
+
+    // the tree may have despawned its entity, or been replaced while it ran.
+    if let Some(mut tree) = world.get_mut::<EntityBehaviourTree>(entity) {
+        let tree = tree.bypass_change_detection();
+        if tree.behaviour.is_none() {
+            tree.behaviour = Some(behaviour);
+            tree.initialized |= ran;
+        }
+    }
+
+    Some(handle_panic(world, entity, id, result))
+}
+
+fn run_ticks<Filter: ReadOnlyWorldQuery + 'static>(world: &mut World) {
+    if let (Some(limiter), Some(time)) = (
+        world.get_resource::<TickLimiter>(),
+        world.get_resource::<Time>(),
+    ) {
+        if limiter.should_skip(time) {
+            return;
+        }
+    }
+
+    if world.contains_resource::<EntityStepper>() {
+        step::<Filter>(world);
+        return;
+    }
+
+    let mut timings = world
+        .contains_resource::<BehaviourTreeDiagnostics>()
+        .then(HashMap::<BehaviourId, _>::default);
+
+    let queued = queued_entities::<Filter>(world);
+    world
+        .resource_mut::<BehaviourTrees>()
+        .count_entities(&queued);
+
+    for (entity, id) in queued {
+        let start = timings.is_some().then(Instant::now);
+
+        if let Some(status) = tick_queued(world, entity, id) {
+            world
+                .resource_mut::<BehaviourTrees>()
+                .finish_run(id, entity, status);
+            finish_tick_once(world, entity, id, status);
+            record_status(world, entity, id, status);
+        }
+
+        if let (Some(timings), Some(start)) = (&mut timings, start) {
+            *timings.entry(id).or_default() += start.elapsed();
+        }
+    }
+
+    if let Some(timings) = timings {
+        record_timings(world, timings);
+    }
 }
@@ -1,3 +1,174 @@
 //! `bevy-behaviour-tree-macros` contains convenience macros for `bevy-behaviour-tree`.
 //! See that package for more info.
 #![warn(missing_docs)]
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Declares a behaviour tree inline.
+///
+/// - `sequence { ... }` and `select { ... }` take a comma separated list of nodes.
+/// - `leaf(expr)` is any behaviour, usually a system.
+/// - `name: node` gives the node a [name](https://docs.rs/bevy-behaviour-tree/latest/bevy_behaviour_tree/behaviour/trait.Behaviour.html#method.with_name).
+/// - Decorators are applied with method calls after a node, like `leaf(walk).retry(3)`.
+///   This requires the decorator traits to be in scope, which they are with the prelude.
+///
+/// ```ignore
+/// let tree = behaviour_tree! {
+///     sequence {
+///         leaf(find_target),
+///         attack: select {
+///             leaf(shoot).run_if(has_ammo),
+///             leaf(punch),
+///         }.retry(3),
+///     }
+/// };
+/// ```
+#[proc_macro]
+pub fn behaviour_tree(input: TokenStream) -> TokenStream {
+    let tokens = input.into_iter().collect::<Vec<_>>();
+
+    match node(&tokens) {
+        Ok(tree) => tree,
+        Err((span, message)) => compile_error(span, message),
+    }
+}
+
+type Error = (Span, &'static str);
+
+fn node(tokens: &[TokenTree]) -> Result<TokenStream, Error> {
+    let Some(first) = tokens.first() else {
+        return Err((Span::call_site(), "expected a node"));
+    };
+
+    // `name: node`. Path separators (`::`) are joint, so they aren't mistaken for names.
+    if let (TokenTree::Ident(name), Some(TokenTree::Punct(colon))) = (first, tokens.get(1)) {
+        if colon.as_char() == ':' && colon.spacing() == Spacing::Alone {
+            let named = node(&tokens[2..])?;
+            return Ok(with_name(named, name));
+        }
+    }
+
+    let TokenTree::Ident(kind) = first else {
+        return Err((first.span(), "expected `sequence`, `select` or `leaf`"));
+    };
+
+    let head = match (kind.to_string().as_str(), tokens.get(1)) {
+        (compositor @ ("sequence" | "select"), Some(TokenTree::Group(children)))
+            if children.delimiter() == Delimiter::Brace =>
+        {
+            let mut group = TokenStream::new();
+            for child in split_commas(children.stream()) {
+                group.extend(node(&child)?);
+                group.extend([punct(',')]);
+            }
+
+            let mut call = path(&[
+                "bevy_behaviour_tree",
+                "compositor",
+                "Compositor",
+                compositor,
+            ]);
+            call.extend([TokenTree::Group(Group::new(
+                Delimiter::Parenthesis,
+                TokenStream::from(TokenTree::Group(Group::new(Delimiter::Parenthesis, group))),
+            ))]);
+            call
+        }
+        ("sequence" | "select", _) => {
+            return Err((kind.span(), "expected `{ ... }` after compositor"));
+        }
+        ("leaf", Some(TokenTree::Group(behaviour)))
+            if behaviour.delimiter() == Delimiter::Parenthesis =>
+        {
+            behaviour.stream()
+        }
+        ("leaf", _) => return Err((kind.span(), "expected `(...)` after `leaf`")),
+        _ => return Err((kind.span(), "expected `sequence`, `select` or `leaf`")),
+    };
+
+    let decorators = &tokens[2..];
+    if let Some(token) = decorators.first() {
+        if !matches!(token, TokenTree::Punct(dot) if dot.as_char() == '.') {
+            return Err((token.span(), "expected `,` or a decorator like `.invert()`"));
+        }
+    }
+
+    let mut output = TokenStream::from(TokenTree::Group(Group::new(Delimiter::Parenthesis, head)));
+    output.extend(decorators.iter().cloned());
+    Ok(output)
+}
+
+/// Splits at top-level commas. Commas inside groups (like function arguments) are left alone.
+fn split_commas(stream: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+
+    for token in stream {
+        match &token {
+            TokenTree::Punct(comma) if comma.as_char() == ',' => parts.push(Vec::new()),
+            _ => parts.last_mut().unwrap().push(token),
+        }
+    }
+
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+fn with_name(node: TokenStream, name: &Ident) -> TokenStream {
+    let mut into_behaviour = path(&[
+        "bevy_behaviour_tree",
+        "behaviour",
+        "IntoBehaviour",
+        "into_behaviour",
+    ]);
+    into_behaviour.extend([TokenTree::Group(Group::new(Delimiter::Parenthesis, node))]);
+
+    let mut arguments = into_behaviour;
+    arguments.extend([
+        punct(','),
+        TokenTree::Literal(Literal::string(&name.to_string())),
+    ]);
+
+    let mut call = path(&["bevy_behaviour_tree", "behaviour", "Behaviour", "with_name"]);
+    call.extend([TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        arguments,
+    ))]);
+    call
+}
+
+/// An absolute path, like `::a::b`.
+fn path(segments: &[&str]) -> TokenStream {
+    let mut path = TokenStream::new();
+    for segment in segments {
+        path.extend([
+            TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+            TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+            TokenTree::Ident(Ident::new(segment, Span::call_site())),
+        ]);
+    }
+    path
+}
+
+fn punct(char: char) -> TokenTree {
+    TokenTree::Punct(Punct::new(char, Spacing::Alone))
+}
+
+fn compile_error(span: Span, message: &str) -> TokenStream {
+    let mut error = path(&["core", "compile_error"]);
+    error.extend([
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Parenthesis,
+            TokenStream::from(TokenTree::Literal(Literal::string(message))),
+        )),
+    ]);
+
+    // point the error at the offending token instead of the whole macro call.
+    error
+        .into_iter()
+        .map(|mut token| {
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
@@ -1 +1,4 @@
 pub use bevy_behaviour_tree_core::*;
+
+#[cfg(feature = "macros")]
+pub use bevy_behaviour_tree_macros::behaviour_tree;
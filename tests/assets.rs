@@ -0,0 +1,123 @@
+#![cfg(feature = "assets")]
+use std::time::Duration;
+
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_behaviour_tree::{
+    assets::{
+        BehaviourAssetPlugin, BehaviourDefinition, BehaviourNodeRegistry, BehaviourPrototype,
+    },
+    prelude::*,
+};
+
+#[derive(Component, Default)]
+struct Ticks(u32);
+
+fn count(In(entity): In<Entity>, mut ticks: Query<&mut Ticks>) -> Status {
+    ticks.get_mut(entity).unwrap().0 += 1;
+    Status::Success
+}
+
+fn succeed(_: In<Entity>) -> Status {
+    Status::Success
+}
+
+#[test]
+fn load_prototype() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            asset_folder: "tests/assets".into(),
+            ..Default::default()
+        },
+        BehaviourTreePlugin::default(),
+        BehaviourAssetPlugin::default(),
+    ));
+
+    let mut registry = app.world.resource_mut::<BehaviourNodeRegistry>();
+    registry.register_system(count);
+    registry.register_leaf("succeed", succeed);
+
+    let handle = app
+        .world
+        .resource::<AssetServer>()
+        .load::<BehaviourPrototype, _>("two_leaves.btree");
+    let entity = app.world.spawn((handle.clone(), Ticks(0))).id();
+
+    // loading happens in the background.
+    for _ in 0..500 {
+        if app.world.get::<BehaviourId>(entity).is_some() {
+            break;
+        }
+        app.update();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(
+        app.world.get::<BehaviourId>(entity).is_some(),
+        "tree never loaded"
+    );
+
+    // the sequence counts every other tick.
+    for _ in 0..4 {
+        app.update();
+    }
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 2);
+
+    // changing the prototype replaces the tree.
+    app.world
+        .resource_mut::<Assets<BehaviourPrototype>>()
+        .get_mut(&handle)
+        .unwrap()
+        .0 = BehaviourDefinition {
+        node: "count".into(),
+        children: vec![],
+        decorators: vec![],
+    };
+
+    app.update();
+    let before = app.world.get::<Ticks>(entity).unwrap().0;
+
+    for _ in 0..4 {
+        app.update();
+    }
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, before + 4);
+}
+
+#[test]
+fn prototype_schedule() {
+    use bevy::ecs::schedule::ScheduleLabel;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Ai;
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        BehaviourTreePlugin::in_schedule(Ai),
+        BehaviourAssetPlugin::in_schedule(Ai),
+    ));
+
+    app.world
+        .resource_mut::<BehaviourNodeRegistry>()
+        .register_system(count);
+
+    let handle = app
+        .world
+        .resource_mut::<Assets<BehaviourPrototype>>()
+        .add(BehaviourPrototype(BehaviourDefinition {
+            node: "count".into(),
+            children: vec![],
+            decorators: vec![],
+        }));
+    let entity = app.world.spawn((handle, Ticks(0))).id();
+
+    // nothing happens outside of the configured schedule.
+    app.update();
+    assert!(app.world.get::<BehaviourId>(entity).is_none());
+
+    app.world.run_schedule(Ai);
+    assert!(app.world.get::<BehaviourId>(entity).is_some());
+    app.world.run_schedule(Ai);
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 1);
+}
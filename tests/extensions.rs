@@ -1,7 +1,6 @@
 //! I'd like for the API to be (somewhat) easily user-extendable. 
 //! This test is to ensure that there's always a way to do that.
-// TODO: this is currently less than ideal, since it requires a feature flag *and* type shenanigans with markers.
-#![feature(return_position_impl_trait_in_trait)]
+// TODO: this is currently less than ideal, since it requires type shenanigans with markers.
 use bevy::prelude::{In, Entity};
 use bevy_behaviour_tree::{prelude::*, TodoBehaviour, behaviour::{IntoBehaviour, SelfMarker}};
 
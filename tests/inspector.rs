@@ -0,0 +1,18 @@
+#![cfg(feature = "inspector")]
+use bevy::{asset::AssetPlugin, prelude::*, render::render_resource::Shader};
+use bevy_behaviour_tree::{inspector::BehaviourTreeInspectorPlugin, prelude::*};
+
+#[test]
+fn build() {
+    let mut app = App::new();
+    // egui loads its shader when it's built, even without a renderer.
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .add_asset::<Shader>()
+        .add_plugins((BehaviourTreePlugin::default(), BehaviourTreeInspectorPlugin));
+
+    app.finish();
+    app.cleanup();
+
+    // the panel itself needs a window, so it's only tested manually.
+    assert!(app.is_plugin_added::<BehaviourTreeInspectorPlugin>());
+}
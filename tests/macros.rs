@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bevy_behaviour_tree::{behaviour_tree, prelude::*};
+
+#[derive(Component)]
+struct HasRun(bool);
+
+fn fail(_: In<Entity>) -> Status {
+    Status::Failure
+}
+
+fn succeed(_: In<Entity>) -> Status {
+    Status::Success
+}
+
+fn mark_run(In(entity): In<Entity>, mut has_run: Query<&mut HasRun>) -> Status {
+    has_run.get_mut(entity).unwrap().0 = true;
+    Status::Success
+}
+
+fn panic_if_run(_: In<Entity>) -> Status {
+    panic!(":(");
+}
+
+fn run_to_completion(mut behaviour: impl Behaviour) -> (Status, bool) {
+    let mut world = World::default();
+    behaviour.initialize(&mut world);
+
+    let entity = world.spawn(HasRun(false)).id();
+
+    let mut status = Status::Running;
+    for _ in 0..10 {
+        status = behaviour.run(entity, &mut world);
+        if status != Status::Running {
+            break;
+        }
+    }
+
+    (status, world.get::<HasRun>(entity).unwrap().0)
+}
+
+#[test]
+fn select() {
+    let by_hand = (fail, fail, mark_run, panic_if_run).select();
+    let by_macro = behaviour_tree! {
+        select {
+            leaf(fail),
+            leaf(fail),
+            leaf(mark_run),
+            leaf(panic_if_run),
+        }
+    };
+
+    assert_eq!(run_to_completion(by_hand), (Status::Success, true));
+    assert_eq!(run_to_completion(by_macro), (Status::Success, true));
+}
+
+#[test]
+fn nesting() {
+    let tree = behaviour_tree! {
+        sequence {
+            leaf(succeed),
+            fallback: select { leaf(fail), leaf(mark_run) },
+            never: leaf(succeed).invert().invert().invert(),
+        }
+        .invert()
+    };
+
+    // the last child fails, so the sequence does as well.
+    assert_eq!(run_to_completion(tree), (Status::Success, true));
+
+    let named = behaviour_tree!(root: leaf(|_: In<Entity>| Status::Success));
+    assert_eq!(named.type_name(), "root");
+}
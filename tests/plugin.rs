@@ -0,0 +1,1345 @@
+use bevy::prelude::*;
+use bevy_behaviour_tree::{
+    behaviour::IntoBehaviour, debug::EntityStepper, plugin::TickLimiter, prelude::*,
+};
+
+#[derive(Component, Clone, Default)]
+struct Ticks(u32);
+
+fn count_ticks(In(entity): In<Entity>, mut ticks: Query<&mut Ticks>) -> Status {
+    ticks.get_mut(entity).unwrap().0 += 1;
+    Status::Running
+}
+
+#[test]
+fn pause_tree() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let paused = trees.create(count_ticks.into_behaviour());
+    let running = trees.create(count_ticks.into_behaviour());
+    trees.pause(paused);
+
+    let waiting = app.world.spawn((paused, Ticks::default())).id();
+    let ticking = app.world.spawn((running, Ticks::default())).id();
+
+    app.update();
+    assert_eq!(app.world.get::<Ticks>(waiting).unwrap().0, 0);
+    assert_eq!(app.world.get::<Ticks>(ticking).unwrap().0, 1);
+
+    app.world.resource_mut::<BehaviourTrees>().resume(paused);
+    app.update();
+    assert_eq!(app.world.get::<Ticks>(waiting).unwrap().0, 1);
+}
+
+#[test]
+fn entity_filter() {
+    #[derive(Component)]
+    struct ActiveAI;
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default().with_entity_filter::<With<ActiveAI>>());
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(count_ticks.into_behaviour());
+
+    let active = app.world.spawn((id, Ticks::default(), ActiveAI)).id();
+    let inactive = app.world.spawn((id, Ticks::default())).id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    assert_eq!(app.world.get::<Ticks>(active).unwrap().0, 3);
+    assert_eq!(app.world.get::<Ticks>(inactive).unwrap().0, 0);
+}
+
+#[test]
+fn local_trees() {
+    use std::{cell::RefCell, rc::Rc};
+
+    use bevy_behaviour_tree::local::{local_fn, LocalBehaviourTreePlugin, LocalBehaviourTrees};
+
+    let mut app = App::new();
+    app.add_plugins(LocalBehaviourTreePlugin::default());
+
+    // `Rc` is neither `Send` nor `Sync`.
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let id = {
+        let seen = seen.clone();
+        app.world
+            .non_send_resource_mut::<LocalBehaviourTrees>()
+            .create(local_fn(move |entity: Entity, _: &mut World| {
+                seen.borrow_mut().push(entity);
+                Status::Running
+            }))
+    };
+
+    let entity = app.world.spawn(id).id();
+
+    app.update();
+    app.update();
+
+    assert_eq!(*seen.borrow(), vec![entity, entity]);
+}
+
+#[test]
+fn diagnostics() {
+    use bevy::diagnostic::DiagnosticsStore;
+    use bevy_behaviour_tree::{
+        diagnostics::BehaviourTreeDiagnosticsPlugin, plugin::BehaviourNameRegistry,
+    };
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default().with_diagnostics());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let id = trees.create(count_ticks.into_behaviour());
+    let guard = trees.create(count_ticks.into_behaviour());
+    app.world
+        .resource_mut::<BehaviourNameRegistry>()
+        .set_name(guard, "guard");
+    app.world.spawn((id, Ticks::default()));
+    app.world.spawn((guard, Ticks::default()));
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let store = app.world.resource::<DiagnosticsStore>();
+    let diagnostic = store
+        .get(BehaviourTreeDiagnosticsPlugin::diagnostic_id(id))
+        .expect("no diagnostic registered for tree");
+
+    assert_eq!(diagnostic.name, "behaviour_tree/tree#0");
+    assert_eq!(diagnostic.history_len(), 10);
+
+    let named = store
+        .get(BehaviourTreeDiagnosticsPlugin::diagnostic_id(guard))
+        .unwrap();
+    assert_eq!(named.name, "behaviour_tree/guard");
+}
+
+#[test]
+fn entity_stepper() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default().with_entity_stepper());
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(count_ticks.into_behaviour());
+
+    let first = app.world.spawn((id, Ticks(0))).id();
+    let second = app.world.spawn((id, Ticks(0))).id();
+
+    let ticks = |app: &App| {
+        (
+            app.world.get::<Ticks>(first).unwrap().0,
+            app.world.get::<Ticks>(second).unwrap().0,
+        )
+    };
+
+    // nothing runs until told to advance.
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(ticks(&app), (0, 0));
+
+    app.world.resource_mut::<EntityStepper>().should_advance = true;
+    app.update();
+    assert_eq!(ticks(&app), (1, 0));
+    assert!(!app.world.resource::<EntityStepper>().should_advance);
+    assert_eq!(app.world.resource::<EntityStepper>().processed(), 1);
+
+    app.update();
+    assert_eq!(ticks(&app), (1, 0));
+
+    app.world.resource_mut::<EntityStepper>().should_advance = true;
+    app.update();
+    assert_eq!(ticks(&app), (1, 1));
+
+    // next round starts from the beginning.
+    app.world.resource_mut::<EntityStepper>().should_advance = true;
+    app.update();
+    assert_eq!(ticks(&app), (2, 1));
+    assert_eq!(app.world.resource::<EntityStepper>().processed(), 1);
+}
+
+#[test]
+fn entity_stepper_limits_and_diagnostics() {
+    use bevy::{
+        diagnostic::DiagnosticsStore,
+        utils::{Duration, Instant},
+    };
+    use bevy_behaviour_tree::diagnostics::BehaviourTreeDiagnosticsPlugin;
+
+    let mut app = App::new();
+    app.add_plugins(
+        BehaviourTreePlugin::default()
+            .with_entity_stepper()
+            .with_diagnostics()
+            .with_frame_budget_limit(Duration::from_millis(50)),
+    );
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(count_ticks.into_behaviour());
+    let entity = app.world.spawn((id, Ticks(0))).id();
+
+    let start = Instant::now();
+    let mut time = Time::new(start);
+    let mut step = |app: &mut App, millis| {
+        time.update_with_instant(start + Duration::from_millis(millis));
+        app.insert_resource(time.clone());
+        app.world.resource_mut::<EntityStepper>().should_advance = true;
+        app.update();
+        app.world.get::<Ticks>(entity).unwrap().0
+    };
+
+    assert_eq!(step(&mut app, 16), 1);
+    // slow frames are skipped, even while stepping.
+    assert_eq!(step(&mut app, 116), 1);
+    assert_eq!(step(&mut app, 132), 2);
+
+    let diagnostic = app
+        .world
+        .resource::<DiagnosticsStore>()
+        .get(BehaviourTreeDiagnosticsPlugin::diagnostic_id(id))
+        .expect("no diagnostic registered for stepped tree");
+    assert_eq!(diagnostic.history_len(), 2);
+}
+
+#[test]
+fn entity_stepper_entity_tree() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default().with_entity_stepper());
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(count_ticks.into_behaviour());
+
+    let shared = app.world.spawn((id, Ticks(0))).id();
+    let own = app
+        .world
+        .spawn((
+            EntityBehaviourTree::new(count_ticks.into_behaviour()),
+            Ticks(0),
+        ))
+        .id();
+
+    let ticks = |app: &App| {
+        (
+            app.world.get::<Ticks>(shared).unwrap().0,
+            app.world.get::<Ticks>(own).unwrap().0,
+        )
+    };
+
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(ticks(&app), (0, 0));
+
+    // entity trees are stepped through after the shared ones.
+    app.world.resource_mut::<EntityStepper>().should_advance = true;
+    app.update();
+    assert_eq!(ticks(&app), (1, 0));
+
+    app.world.resource_mut::<EntityStepper>().should_advance = true;
+    app.update();
+    assert_eq!(ticks(&app), (1, 1));
+    assert_eq!(app.world.resource::<EntityStepper>().remaining(), 0);
+}
+
+#[test]
+fn entity_count() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let id = trees.create(count_ticks.into_behaviour());
+    let unused = trees.create(count_ticks.into_behaviour());
+
+    for _ in 0..10 {
+        app.world.spawn((id, Ticks(0)));
+    }
+    app.world.spawn((id, Ticks(0), Skip));
+
+    // counts are only updated when ticking.
+    assert_eq!(app.world.resource::<BehaviourTrees>().entity_count(id), 0);
+
+    app.update();
+
+    let trees = app.world.resource::<BehaviourTrees>();
+    assert_eq!(trees.entity_count(id), 10);
+    assert_eq!(trees.entity_count(unused), 0);
+}
+
+#[test]
+fn batch_spawn() {
+    use bevy_behaviour_tree::plugin::{batch_spawn, BatchSpawner};
+
+    #[derive(Resource, Default)]
+    struct Agents(Vec<Entity>);
+
+    #[derive(Clone)]
+    struct CountTicks;
+
+    impl Behaviour for CountTicks {
+        fn initialize(&mut self, _: &mut World) {}
+
+        fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+            world.get_mut::<Ticks>(entity).unwrap().0 += 1;
+            Status::Running
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default())
+        .init_resource::<Agents>()
+        .add_systems(
+            Startup,
+            |mut commands: Commands,
+             mut trees: ResMut<BehaviourTrees>,
+             mut agents: ResMut<Agents>| {
+                agents.0 = batch_spawn(5, CountTicks, Ticks(0), &mut commands, &mut trees);
+                agents
+                    .0
+                    .extend(BatchSpawner::new(CountTicks).with_bundle(Ticks(0)).spawn_n(
+                        5,
+                        &mut commands,
+                        &mut trees,
+                    ));
+            },
+        );
+
+    app.update();
+
+    let agents = app.world.resource::<Agents>().0.clone();
+    assert_eq!(agents.len(), 10);
+
+    let ids = agents
+        .iter()
+        .map(|agent| *app.world.get::<BehaviourId>(*agent).unwrap())
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(ids.len(), 10);
+
+    for agent in agents {
+        assert_eq!(app.world.get::<Ticks>(agent).unwrap().0, 1);
+    }
+}
+
+#[test]
+fn error_handler() {
+    #[derive(Resource, Default)]
+    struct Panics(Vec<String>);
+
+    fn panic_once(In(entity): In<Entity>, mut ticks: Query<&mut Ticks>) -> Status {
+        let mut ticks = ticks.get_mut(entity).unwrap();
+        ticks.0 += 1;
+        if ticks.0 == 1 {
+            panic!("first tick");
+        }
+        Status::Running
+    }
+
+    let mut app = App::new();
+    app.add_plugins(
+        BehaviourTreePlugin::default().with_error_handler(|world, _, _, payload| {
+            let message = payload.downcast_ref::<&str>().unwrap().to_string();
+            world.resource_mut::<Panics>().0.push(message);
+        }),
+    )
+    .init_resource::<Panics>();
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(panic_once.into_behaviour());
+    let entity = app.world.spawn((id, Ticks(0))).id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    assert_eq!(app.world.resource::<Panics>().0, vec!["first tick"]);
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 3);
+
+    // trees run outside of the schedule are caught too.
+    let entity = app.world.spawn(Ticks(0)).id();
+    let status = app
+        .world
+        .resource_scope(|world, mut trees: Mut<BehaviourTrees>| {
+            trees.tick_entity(entity, id, world)
+        });
+    assert_eq!(status, Some(Status::Failure));
+    assert_eq!(app.world.resource::<Panics>().0, vec!["first tick"; 2]);
+}
+
+#[test]
+fn max_runs_per_entity() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let id = trees.create(count_ticks.into_behaviour());
+    trees.set_max_runs_per_entity(id, 2);
+
+    let entity = app.world.spawn((id, Ticks(0))).id();
+
+    for _ in 0..5 {
+        app.update();
+    }
+
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 2);
+    assert_eq!(app.world.resource::<BehaviourTrees>().entity_count(id), 0);
+
+    app.world
+        .resource_mut::<BehaviourTrees>()
+        .clear_run_count(id, entity);
+    app.update();
+
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 3);
+    assert_eq!(
+        app.world.resource::<BehaviourTrees>().run_count(id, entity),
+        1
+    );
+}
+
+#[test]
+fn frame_budget_limit() {
+    use bevy::utils::{Duration, Instant};
+
+    let mut app = App::new();
+    app.add_plugins(
+        BehaviourTreePlugin::default().with_frame_budget_limit(Duration::from_millis(50)),
+    );
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(count_ticks.into_behaviour());
+    let entity = app.world.spawn((id, Ticks(0))).id();
+
+    // without the time plugin, `Time` only changes when updated by hand.
+    let start = Instant::now();
+    let mut time = Time::new(start);
+
+    time.update_with_instant(start + Duration::from_millis(16));
+    app.insert_resource(time.clone());
+    app.update();
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 1);
+
+    time.update_with_instant(start + Duration::from_millis(116));
+    app.insert_resource(time);
+    app.update();
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 1);
+
+    app.world
+        .resource_mut::<TickLimiter>()
+        .tick_if_frame_over_budget = true;
+    app.update();
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 2);
+}
+
+#[test]
+fn observers() {
+    use std::sync::{Arc, Mutex};
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let finished = Arc::new(Mutex::new(Vec::new()));
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let running = trees.create(count_ticks.into_behaviour());
+    let done = trees.create(always_succeed());
+
+    let first = trees.observe(done, {
+        let finished = finished.clone();
+        move |entity, status| finished.lock().unwrap().push((1, entity, status))
+    });
+    trees.observe(done, {
+        let finished = finished.clone();
+        move |entity, status| finished.lock().unwrap().push((2, entity, status))
+    });
+    trees.observe(running, {
+        let finished = finished.clone();
+        move |entity, status| finished.lock().unwrap().push((3, entity, status))
+    });
+
+    let entity = app.world.spawn(done).id();
+    app.world.spawn((running, Ticks(0)));
+
+    app.update();
+
+    assert_eq!(
+        *finished.lock().unwrap(),
+        vec![(1, entity, Status::Success), (2, entity, Status::Success)]
+    );
+
+    assert!(app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .unobserve(done, first));
+    app.update();
+
+    assert_eq!(finished.lock().unwrap().len(), 3);
+    assert_eq!(finished.lock().unwrap()[2], (2, entity, Status::Success));
+}
+
+#[test]
+fn multiple_trees() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let ids = vec![
+        trees.create(count_ticks.into_behaviour()),
+        trees.create(count_ticks.into_behaviour()),
+    ];
+
+    let entity = app.world.spawn((BehaviourIds(ids.clone()), Ticks(0))).id();
+    let skipped = app.world.spawn((BehaviourIds(ids), Ticks(0), Skip)).id();
+
+    app.update();
+
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 2);
+    assert_eq!(app.world.get::<Ticks>(skipped).unwrap().0, 0);
+}
+
+#[test]
+fn entity_cleanup() {
+    use bevy_behaviour_tree::plugin::CleanupPolicy;
+
+    fn tracked_entities(app: &App, id: BehaviourId) -> usize {
+        let trees = app.world.resource::<BehaviourTrees>();
+        trees.snapshot().get(id).unwrap().entities.len()
+    }
+
+    for (policy, ticks) in [
+        (CleanupPolicy::Immediate, 0),
+        (CleanupPolicy::Deferred(1), 1),
+        (CleanupPolicy::Deferred(3), 3),
+    ] {
+        let mut app = App::new();
+        app.add_plugins(BehaviourTreePlugin::default().with_deferred_cleanup(policy));
+
+        let id = app
+            .world
+            .resource_mut::<BehaviourTrees>()
+            .create((always_succeed(), count_ticks).sequence());
+        let entity = app.world.spawn((id, Ticks(0))).id();
+
+        app.update();
+        assert_eq!(tracked_entities(&app, id), 1);
+
+        app.world.despawn(entity);
+
+        app.update();
+        for _ in 0..ticks {
+            assert_eq!(tracked_entities(&app, id), 1, "{policy:?}");
+            app.update();
+        }
+        assert_eq!(tracked_entities(&app, id), 0, "{policy:?}");
+    }
+}
+
+#[test]
+fn entity_cleanup_lost_trees() {
+    fn tracked(app: &App, id: BehaviourId, entity: Entity) -> bool {
+        let trees = app.world.resource::<BehaviourTrees>();
+        trees
+            .snapshot()
+            .get(id)
+            .unwrap()
+            .entities
+            .contains_key(&entity)
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let mut progress = || trees.create((always_succeed(), count_ticks).sequence());
+    let (single, many, returning) = (progress(), progress(), progress());
+
+    let entity = app
+        .world
+        .spawn((single, BehaviourIds(vec![many]), Ticks(0)))
+        .id();
+    let comeback = app.world.spawn((returning, Ticks(0))).id();
+    app.update();
+
+    // only the tree the entity lost forgets it.
+    app.world.entity_mut(entity).remove::<BehaviourId>();
+    app.update();
+    app.update();
+    assert!(!tracked(&app, single, entity));
+    assert!(tracked(&app, many, entity));
+
+    // entities that get their tree back before the cleanup keep their progress.
+    app.world.entity_mut(comeback).remove::<BehaviourId>();
+    app.update();
+    app.world.entity_mut(comeback).insert(returning);
+    app.update();
+    assert!(tracked(&app, returning, comeback));
+}
+
+#[test]
+fn global_blackboard() {
+    use std::sync::{Arc, Mutex};
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let guard = trees.create(GlobalBlackboardSet::new("alarm", 3u32));
+    let civilian = trees.create(GlobalBlackboardCheck::new("alarm", |level: &u32| {
+        *level > 2
+    }));
+
+    let checks = Arc::new(Mutex::new(Vec::new()));
+    trees.observe(civilian, {
+        let checks = checks.clone();
+        move |_, status| checks.lock().unwrap().push(status)
+    });
+
+    // trees run in the order of their IDs, so the guard sets the value first.
+    app.world.spawn(guard);
+    app.world.spawn(civilian);
+
+    app.update();
+
+    assert_eq!(*checks.lock().unwrap(), vec![Status::Success]);
+    assert_eq!(
+        app.world
+            .resource::<GlobalBlackboard>()
+            .get::<u32>("alarm")
+            .as_deref(),
+        Some(&3)
+    );
+}
+
+#[test]
+fn reflect_trees() {
+    use bevy::ecs::reflect::ReflectResource;
+    use bevy_behaviour_tree::plugin::BehaviourTreesSummary;
+
+    fn reflected_summary(app: &App) -> BehaviourTreesSummary {
+        let registry = app.world.resource::<AppTypeRegistry>().read();
+        let reflect_resource = registry
+            .get_type_data::<ReflectResource>(std::any::TypeId::of::<BehaviourTrees>())
+            .unwrap();
+        let reflected = reflect_resource.reflect(&app.world).unwrap();
+
+        let bevy::reflect::ReflectRef::Struct(trees) = reflected.reflect_ref() else {
+            panic!("expected a struct");
+        };
+        trees
+            .field("summary")
+            .and_then(|summary| summary.downcast_ref::<BehaviourTreesSummary>())
+            .unwrap()
+            .clone()
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let id = trees.create(count_ticks.into_behaviour());
+    let other = trees.create(count_ticks.into_behaviour());
+
+    app.world.spawn((id, Ticks::default()));
+    app.update();
+
+    let summary = reflected_summary(&app);
+    assert_eq!(summary.len, 2);
+    assert_eq!(summary.active_count, 1);
+    assert_eq!(summary.trees.len(), 2);
+    assert_eq!(summary.trees[0].0, id);
+
+    // removed trees drop out of it with the next tick.
+    app.world.resource_mut::<BehaviourTrees>().remove(id);
+    app.update();
+
+    let summary = reflected_summary(&app);
+    assert_eq!(summary.len, 1);
+    assert_eq!(summary.active_count, 0);
+    assert_eq!(summary.trees.len(), 1);
+    assert_eq!(summary.trees[0].0, other);
+}
+
+#[test]
+fn replace_entity_tree() {
+    #[derive(Component, Clone)]
+    struct Started;
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let entity = app
+        .world
+        .spawn((
+            EntityBehaviourTree::new(count_ticks.into_behaviour()),
+            Ticks::default(),
+        ))
+        .id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 3);
+
+    let old = app
+        .world
+        .get_mut::<EntityBehaviourTree>(entity)
+        .unwrap()
+        .replace(Box::new((Insert(Started), count_ticks).sequence()));
+    assert!(old.is_some());
+
+    // the new tree starts with its first child.
+    app.update();
+    assert!(app.world.get::<Started>(entity).is_some());
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 3);
+
+    app.update();
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 4);
+}
+
+#[test]
+fn sort_key() {
+    #[derive(Component, PartialEq, Eq, PartialOrd, Ord)]
+    struct TurnOrder(i32);
+
+    #[derive(Resource, Default)]
+    struct Turns(Vec<i32>);
+
+    fn take_turn(
+        In(entity): In<Entity>,
+        orders: Query<&TurnOrder>,
+        mut turns: ResMut<Turns>,
+    ) -> Status {
+        turns.0.push(orders.get(entity).unwrap().0);
+        Status::Success
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default().with_sort_key::<TurnOrder>())
+        .init_resource::<Turns>();
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(take_turn.into_behaviour());
+
+    for order in [2, 1, 3] {
+        app.world.spawn((id, TurnOrder(order)));
+    }
+
+    app.update();
+
+    assert_eq!(app.world.resource::<Turns>().0, [1, 2, 3]);
+}
+
+#[test]
+fn dependencies() {
+    use bevy::ecs::component::ComponentId;
+    use bevy_behaviour_tree::behaviour::SelfMarker;
+
+    #[derive(Default)]
+    struct MoveForward(Option<ComponentId>);
+
+    impl IntoBehaviour<SelfMarker> for MoveForward {
+        fn into_behaviour(self) -> impl Behaviour {
+            self
+        }
+    }
+
+    impl Behaviour for MoveForward {
+        fn initialize(&mut self, world: &mut World) {
+            self.0 = Some(world.init_component::<Transform>());
+        }
+
+        fn dependencies(&self) -> Vec<ComponentId> {
+            self.0.into_iter().collect()
+        }
+
+        fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+            if let Some(mut transform) = world.get_mut::<Transform>(entity) {
+                transform.translation.z += 1.;
+            }
+            Status::Success
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create((MoveForward::default(), count_ticks).sequence());
+
+    assert!(app
+        .world
+        .resource::<BehaviourTrees>()
+        .dependencies(id)
+        .is_empty());
+
+    app.world
+        .spawn((id, Ticks::default(), Transform::default()));
+    app.update();
+
+    let transform = app.world.component_id::<Transform>().unwrap();
+    let ticks = app.world.component_id::<Ticks>().unwrap();
+    let dependencies = app.world.resource::<BehaviourTrees>().dependencies(id);
+
+    assert!(dependencies.contains(&transform));
+    assert!(dependencies.contains(&ticks));
+
+    // trees run outside of the schedule record them too.
+    let entity = app
+        .world
+        .spawn((Ticks::default(), Transform::default()))
+        .id();
+    app.world
+        .resource_scope(|world, mut trees: Mut<BehaviourTrees>| {
+            let id = trees.create(MoveForward::default());
+            trees.tick_entity(entity, id, world);
+            assert_eq!(trees.dependencies(id), [transform]);
+        });
+}
+
+#[test]
+fn tick_entity_all() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(count_ticks.into_behaviour());
+
+    let entity = app
+        .world
+        .spawn((
+            id,
+            EntityBehaviourTree::new((count_ticks, always_succeed()).sequence()),
+            Ticks::default(),
+        ))
+        .id();
+
+    app.world
+        .resource_scope(|world, mut trees: Mut<BehaviourTrees>| {
+            assert_eq!(
+                trees.tick_entity_all(entity, world),
+                [Status::Running, Status::Running]
+            );
+        });
+
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 2);
+}
+
+#[test]
+fn warm_up() {
+    use bevy_behaviour_tree::behaviour::SelfMarker;
+
+    #[derive(Component)]
+    struct WarmUps(u32);
+
+    struct Prepare;
+
+    impl IntoBehaviour<SelfMarker> for Prepare {
+        fn into_behaviour(self) -> impl Behaviour {
+            self
+        }
+    }
+
+    impl Behaviour for Prepare {
+        fn initialize(&mut self, _: &mut World) {}
+
+        fn warm_up(&mut self, entity: Entity, world: &mut World) {
+            match world.get_mut::<WarmUps>(entity) {
+                Some(mut warm_ups) => warm_ups.0 += 1,
+                None => {
+                    world.entity_mut(entity).insert(WarmUps(1));
+                }
+            }
+        }
+
+        fn run(&mut self, entity: Entity, world: &mut World) -> Status {
+            // inserted by `warm_up` before the first run.
+            assert!(world.get::<WarmUps>(entity).is_some());
+            Status::Running
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create((Prepare,).sequence());
+
+    let first = app.world.spawn(id).id();
+    let second = app.world.spawn(id).id();
+
+    for _ in 0..3 {
+        app.update();
+    }
+
+    assert_eq!(app.world.get::<WarmUps>(first).unwrap().0, 1);
+    assert_eq!(app.world.get::<WarmUps>(second).unwrap().0, 1);
+
+    app.world
+        .resource_mut::<BehaviourTrees>()
+        .reset_for_entity(id, first);
+    app.update();
+
+    // resetting warms the entity up again, the other one is left alone.
+    assert_eq!(app.world.get::<WarmUps>(first).unwrap().0, 2);
+    assert_eq!(app.world.get::<WarmUps>(second).unwrap().0, 1);
+}
+
+#[test]
+fn trees_from_fn() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let count = trees.create_fn(|entity, world| {
+        world.get_mut::<Ticks>(entity).unwrap().0 += 1;
+        Status::Running
+    });
+    let inverted = trees.create(
+        BehaviourTrees::from_fn(|entity, world| {
+            world.get_mut::<Ticks>(entity).unwrap().0 += 10;
+            Status::Success
+        })
+        .invert(),
+    );
+
+    let first = app.world.spawn((count, Ticks::default())).id();
+    let second = app.world.spawn((inverted, Ticks::default())).id();
+
+    app.update();
+    app.update();
+
+    assert_eq!(app.world.get::<Ticks>(first).unwrap().0, 2);
+    assert_eq!(app.world.get::<Ticks>(second).unwrap().0, 20);
+}
+
+#[test]
+fn null_id() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let id = trees.create(count_ticks.into_behaviour());
+
+    assert!(BehaviourId::null().is_null());
+    assert!(!id.is_null());
+    assert_eq!(BehaviourId::default(), BehaviourId::null());
+    assert!(!trees.contains(BehaviourId::null()));
+
+    let entity = app.world.spawn((id, Ticks::default())).id();
+    let unassigned = app
+        .world
+        .spawn((BehaviourId::default(), Ticks::default()))
+        .id();
+
+    app.update();
+
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 1);
+    assert_eq!(app.world.get::<Ticks>(unassigned).unwrap().0, 0);
+
+    let trees = app.world.resource::<BehaviourTrees>();
+    assert_eq!(trees.entity_count(BehaviourId::null()), 0);
+    assert_eq!(trees.active_count(), 1);
+}
+
+#[test]
+fn move_tree() {
+    fn count_tens(In(entity): In<Entity>, mut ticks: Query<&mut Ticks>) -> Status {
+        ticks.get_mut(entity).unwrap().0 += 10;
+        Status::Running
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let known = trees.create(count_ticks.into_behaviour());
+    let entity = app.world.spawn((known, Ticks::default())).id();
+
+    app.update();
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 1);
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let loaded = trees.create(count_tens.into_behaviour());
+    trees.move_to(loaded, known);
+
+    assert!(trees.contains(known));
+    assert!(!trees.contains(loaded));
+
+    app.update();
+    assert_eq!(app.world.get::<Ticks>(entity).unwrap().0, 11);
+}
+
+#[test]
+fn entity_observers() {
+    use std::sync::{Arc, Mutex};
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let finished = Arc::new(Mutex::new(Vec::new()));
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let id = trees.create(always_succeed());
+
+    let observed = app.world.spawn(id).id();
+    app.world.spawn(id);
+
+    let handle = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .observe_entity(id, observed, {
+            let finished = finished.clone();
+            move |entity, status| finished.lock().unwrap().push((entity, status))
+        });
+
+    app.update();
+    app.update();
+
+    assert_eq!(
+        *finished.lock().unwrap(),
+        vec![(observed, Status::Success), (observed, Status::Success)]
+    );
+
+    assert!(app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .unobserve(id, handle));
+    app.update();
+
+    assert_eq!(finished.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn tick_counters() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let id = trees.create(count_ticks.into_behaviour());
+    let unused = trees.create(count_ticks.into_behaviour());
+
+    for _ in 0..3 {
+        app.world.spawn((id, Ticks::default()));
+    }
+
+    for _ in 0..5 {
+        app.update();
+    }
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    assert_eq!(trees.total_ticks(), 15);
+    assert_eq!(trees.ticks_for(id), 15);
+    assert_eq!(trees.ticks_for(unused), 0);
+
+    trees.reset_counters();
+    assert_eq!(trees.total_ticks(), 0);
+    assert_eq!(trees.ticks_for(id), 0);
+}
+
+#[test]
+fn entities_for() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let id = trees.create(count_ticks.into_behaviour());
+    let other = trees.create(count_ticks.into_behaviour());
+
+    let first = app.world.spawn((id, Ticks::default())).id();
+    let second = app.world.spawn((id, Ticks::default(), Skip)).id();
+    let both = app
+        .world
+        .spawn((BehaviourIds(vec![other, id]), Ticks::default()))
+        .id();
+    app.world.spawn((other, Ticks::default()));
+
+    let trees = app.world.resource::<BehaviourTrees>();
+    let mut entities = trees.entities_for(id, &app.world);
+    entities.sort();
+
+    assert_eq!(entities, vec![first, second, both]);
+}
+
+#[test]
+fn tick_once() {
+    use bevy_behaviour_tree::plugin::TickOnce;
+
+    fn count_to_three(In(entity): In<Entity>, mut ticks: Query<&mut Ticks>) -> Status {
+        let mut ticks = ticks.get_mut(entity).unwrap();
+        ticks.0 += 1;
+        if ticks.0 < 3 {
+            Status::Running
+        } else {
+            Status::Success
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(count_to_three.into_behaviour());
+
+    let once = app.world.spawn((id, Ticks::default(), TickOnce)).id();
+    let repeated = app.world.spawn((id, Ticks::default())).id();
+
+    for _ in 0..5 {
+        app.update();
+    }
+
+    assert_eq!(app.world.get::<Ticks>(once).unwrap().0, 3);
+    assert!(app.world.get::<BehaviourId>(once).is_none());
+    assert!(app.world.get::<TickOnce>(once).is_none());
+
+    assert_eq!(app.world.get::<Ticks>(repeated).unwrap().0, 5);
+    assert!(app.world.get::<BehaviourId>(repeated).is_some());
+}
+
+#[test]
+fn stale_ids() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let old = trees.create(count_ticks.into_behaviour());
+    trees.remove(old);
+    let new = trees.create(count_ticks.into_behaviour());
+
+    assert_eq!(new.index(), old.index());
+    assert_ne!(new, old);
+    assert!(!trees.is_valid(old));
+    assert!(!trees.contains(old));
+    assert!(trees.is_valid(new));
+
+    let stale = app.world.spawn((old, Ticks::default())).id();
+    let fresh = app.world.spawn((new, Ticks::default())).id();
+    app.update();
+
+    assert_eq!(app.world.get::<Ticks>(stale).unwrap().0, 0);
+    assert_eq!(app.world.get::<Ticks>(fresh).unwrap().0, 1);
+}
+
+#[test]
+fn shared_state() {
+    #[derive(Default)]
+    struct Remaining(u32);
+
+    fn decrement(_: In<Entity>, mut remaining: ResMut<SharedState<Remaining>>) -> Status {
+        let remaining = &mut remaining.get_mut().0;
+        if *remaining == 0 {
+            return Status::Success;
+        }
+        *remaining -= 1;
+        Status::Running
+    }
+
+    fn patrol(_: In<Entity>, mut sector: ResMut<SharedState<()>>) -> Status {
+        sector.take_slot().into()
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default())
+        .insert_resource(SharedState::new(Remaining(5)))
+        .insert_resource(SharedState::new(()).with_slots(1));
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let counter = trees.create(decrement.into_behaviour());
+    let patroller = trees.create(patrol.into_behaviour());
+    app.world.spawn(counter);
+    app.world.spawn(counter);
+    app.world.spawn(patroller);
+    app.world.spawn(patroller);
+
+    app.update();
+    app.update();
+    app.update();
+
+    assert_eq!(app.world.resource::<SharedState<Remaining>>().get().0, 0);
+    assert_eq!(
+        app.world.resource::<SharedState<()>>().slots_left(),
+        Some(0)
+    );
+}
+
+#[test]
+fn status_history() {
+    use bevy_behaviour_tree::debug::{BehaviourHistoryPlugin, BehaviourStatusHistory};
+
+    let mut app = App::new();
+    app.add_plugins((
+        BehaviourTreePlugin::default(),
+        BehaviourHistoryPlugin::new(10),
+    ));
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(count_ticks.into_behaviour());
+    let entity = app.world.spawn((id, Ticks::default())).id();
+
+    let mut ticks = Vec::new();
+    for _ in 0..20 {
+        app.update();
+        ticks.push(app.world.read_change_tick());
+    }
+
+    let history = &app.world.get::<BehaviourStatusHistory>(entity).unwrap().0;
+    assert_eq!(history.len(), 10);
+    assert!(history
+        .iter()
+        .all(|(tree, status, _)| *tree == id && *status == Status::Running));
+
+    // recorded in order, one per update, during the last 10 updates.
+    for (index, (_, _, tick)) in history.iter().enumerate() {
+        let update = 10 + index;
+        assert!(tick.get() > ticks[update - 1].get());
+        assert!(tick.get() <= ticks[update].get());
+    }
+}
+
+#[test]
+fn create_conditional() {
+    #[derive(Component)]
+    struct Awake;
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create_conditional(count_ticks, has_component::<Awake>());
+
+    let awake = app.world.spawn((id, Ticks::default(), Awake)).id();
+    let asleep = app.world.spawn((id, Ticks::default())).id();
+
+    app.update();
+    app.update();
+
+    assert_eq!(app.world.get::<Ticks>(awake).unwrap().0, 2);
+    assert_eq!(app.world.get::<Ticks>(asleep).unwrap().0, 0);
+}
+
+#[test]
+fn debug_dump() {
+    use bevy_behaviour_tree::plugin::BehaviourNameRegistry;
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let guard = trees.create((count_ticks, count_ticks.retry(3)).sequence());
+    let idle = trees.create(count_ticks.into_behaviour());
+    app.world
+        .resource_mut::<BehaviourNameRegistry>()
+        .set_name(guard, "guard");
+
+    app.world.spawn((guard, Ticks::default()));
+    app.world.spawn((guard, Ticks::default()));
+    // counted once, even though it uses the tree twice.
+    app.world
+        .spawn((guard, BehaviourIds(vec![guard]), Ticks::default()));
+    app.update();
+
+    let dump = app
+        .world
+        .resource::<BehaviourTrees>()
+        .debug_dump(&app.world);
+
+    assert!(dump.contains(&format!("{guard} guard:")));
+    assert!(dump.contains("3 entities, initialized"));
+    assert!(dump.contains("retry (max_tries: 3)"));
+    assert!(dump.contains(&format!("{idle}:")));
+    assert!(dump.contains("0 entities, not initialized"));
+}
+
+#[test]
+fn forget_removed_names() {
+    use bevy_behaviour_tree::plugin::BehaviourNameRegistry;
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default());
+
+    let mut trees = app.world.resource_mut::<BehaviourTrees>();
+    let removed = trees.create(count_ticks.into_behaviour());
+    let kept = trees.create(count_ticks.into_behaviour());
+
+    let mut names = app.world.resource_mut::<BehaviourNameRegistry>();
+    names.set_name(removed, "removed");
+    names.set_name(kept, "kept");
+
+    app.world.resource_mut::<BehaviourTrees>().remove(removed);
+    app.update();
+
+    let names = app.world.resource::<BehaviourNameRegistry>();
+    assert_eq!(names.name(removed), None);
+    assert_eq!(names.name(kept), Some("kept"));
+}
+
+#[test]
+fn system_sets() {
+    #[derive(Resource, Default)]
+    struct Observed(Vec<(u32, u32)>);
+
+    #[derive(Resource, Default)]
+    struct Frame(u32);
+
+    fn start_frame(mut frame: ResMut<Frame>) {
+        frame.0 += 1;
+    }
+
+    fn observe(frame: Res<Frame>, ticks: Query<&Ticks>, mut observed: ResMut<Observed>) {
+        observed.0.push((frame.0, ticks.single().0));
+    }
+
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default())
+        .init_resource::<Observed>()
+        .init_resource::<Frame>()
+        .add_systems(
+            Update,
+            (
+                observe.in_set(BehaviourTreeSet::PostTick),
+                start_frame.in_set(BehaviourTreeSet::PreTick),
+            ),
+        );
+
+    let id = app
+        .world
+        .resource_mut::<BehaviourTrees>()
+        .create(count_ticks.into_behaviour());
+    app.world.spawn((id, Ticks::default()));
+
+    app.update();
+    app.update();
+
+    assert_eq!(app.world.resource::<Observed>().0, [(1, 1), (2, 2)]);
+}
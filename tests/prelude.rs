@@ -0,0 +1,44 @@
+//! Everything commonly needed should be available through the prelude, without clashing with bevy's.
+use bevy::prelude::*;
+use bevy_behaviour_tree::prelude::*;
+
+#[derive(Component, Clone)]
+struct Marker;
+
+#[derive(Event, Clone)]
+struct Ping;
+
+#[test]
+fn prelude() {
+    let mut app = App::new();
+    app.add_plugins(BehaviourTreePlugin::default())
+        .add_event::<Ping>()
+        // bevy's `resource_exists` is still usable alongside our prelude.
+        .add_systems(
+            Update,
+            (|mut pings: EventWriter<Ping>| pings.send(Ping))
+                .run_if(resource_exists::<Events<Ping>>()),
+        );
+
+    let id = app.world.resource_mut::<BehaviourTrees>().create(
+        (
+            Insert(Marker),
+            has_component::<Marker>(),
+            Remove::<Marker>::new(),
+            has_component::<Marker>().invert(),
+            SendEvent(Ping),
+            event_received::<Ping>(),
+            WaitForEvent::<Ping>::new(),
+            Despawn,
+        )
+            .sequence(),
+    );
+
+    let entity = app.world.spawn(id).id();
+
+    for _ in 0..20 {
+        app.update();
+    }
+
+    assert!(app.world.get_entity(entity).is_none());
+}